@@ -9,6 +9,24 @@ use crate::{
     TypeSpace,
 };
 
+/*
+ * The tags whose `post` function also gets an `ensure` function.
+ */
+const ENSURE_TAGS: &[&str] = &[
+    "disks",
+    "images",
+    "instances",
+    "organizations",
+    "projects",
+    "routers",
+    "routes",
+    "silos",
+    "snapshots",
+    "sshkeys",
+    "subnets",
+    "vpcs",
+];
+
 /*
  * Generate a function for each Operation.
  */
@@ -78,7 +96,10 @@ pub fn generate_files(
                 out.push('\n');
             };
 
+            let span = get_fn_span(&op_id, &tag, m, p);
+
             let mut print_fn = |docs: &str,
+                                span: &str,
                                 bounds: &Vec<String>,
                                 fn_params_str: &Vec<String>,
                                 body_param: &Option<String>,
@@ -88,6 +109,7 @@ pub fn generate_files(
                                 fn_name: &str| {
                 // Print the function docs.
                 a(docs);
+                a(span);
 
                 if bounds.is_empty() {
                     a(&format!("pub async fn {}(", fn_name,));
@@ -121,7 +143,7 @@ pub fn generate_files(
             let (body_param, body_func) = if let Some(b) = &o.request_body {
                 if let Ok(b) = b.item() {
                     if b.is_binary()? {
-                        bounds.push("B: Into<reqwest::Body>".to_string());
+                        bounds.push("B: Into<bytes::Bytes>".to_string());
                         (Some("B".to_string()), Some("body".to_string()))
                     } else {
                         let (ct, mt) = b.content.first().unwrap();
@@ -183,7 +205,7 @@ pub fn generate_files(
                         } else if let Some(s) = &mt.schema {
                             let tid = ts.select(None, s, "")?;
                             let rt = ts.render_type(&tid, false)?;
-                            bounds.push("T: Into<reqwest::Body>".to_string());
+                            bounds.push("T: Into<bytes::Bytes>".to_string());
                             if rt == "String" {
                                 (Some("T".to_string()), Some("body".to_string()))
                             } else {
@@ -233,6 +255,7 @@ pub fn generate_files(
 
             let fn_inner = get_fn_inner(
                 &oid,
+                &op_id,
                 m,
                 &body_func,
                 &response_type,
@@ -283,6 +306,7 @@ pub fn generate_files(
             // Print our standard function.
             print_fn(
                 &docs,
+                &span,
                 &bounds,
                 &fn_params_str,
                 &body_param,
@@ -292,6 +316,58 @@ pub fn generate_files(
                 &fn_name,
             );
 
+            // If this creates a resource that can be fetched by name, add a
+            // create-or-get version of the function as well.
+            if http::Method::POST == m && fn_name == "post" && ENSURE_TAGS.contains(&tag.as_str())
+            {
+                if let Some((get_path, get_op_id, name_param)) = get_item_path(api, p) {
+                    let docs = get_fn_docs_ensure(&docs, m, p, &get_path);
+
+                    let mut post_args: Vec<String> = fn_params_str
+                        .iter()
+                        .map(|param| param.split(':').next().unwrap().to_string())
+                        .collect();
+                    post_args.push("body".to_string());
+
+                    let get_template = parse(&get_path)?.compile(Default::default());
+
+                    let fn_inner = format!(
+                        r#"match self.post({}).await {{
+                            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+                            Err(e) if crate::utils::is_already_exists(&e) => {{
+                                let {} = body.name.as_str();
+                                {}
+                                let get = self.client.get("{}", &url, None);
+                                #[cfg(feature = "tracing")]
+                                let get = tracing::Instrument::instrument(get, {});
+                                let resource = get.await?;
+                                crate::utils::Ensured::existing(body, resource)
+                            }}
+                            Err(e) => Err(e),
+                        }}"#,
+                        post_args.join(", "),
+                        name_param,
+                        get_template,
+                        get_op_id,
+                        get_child_span(&get_op_id, &tag, "GET", &get_path)
+                    );
+
+                    fn_names.push("ensure".to_string() + &tag);
+
+                    print_fn(
+                        &docs,
+                        &get_fn_span_ensure(&op_id, &tag, p),
+                        &bounds,
+                        &fn_params_str,
+                        &body_param,
+                        &format!("crate::utils::Ensured<{}>", frt),
+                        "",
+                        &fn_inner,
+                        "ensure",
+                    );
+                }
+            }
+
             // TODO: Remove this code once we have restored examples. Leaving this here for now
             // will be useful to know how the examples were being generated
             // Add the docs to our spec.
@@ -360,6 +436,7 @@ pub fn generate_files(
 
                 let fn_inner = get_fn_inner(
                     &oid,
+                    &op_id,
                     m,
                     &body_func,
                     &response_type,
@@ -770,6 +847,7 @@ fn get_fn_params(
 #[allow(clippy::too_many_arguments)]
 fn get_fn_inner(
     oid: &str,
+    op_id: &str,
     m: &str,
     body_func: &Option<String>,
     response_type: &str,
@@ -779,7 +857,7 @@ fn get_fn_inner(
 ) -> Result<String> {
     let body = if let Some(f) = &body_func {
         if f == "json" {
-            "Some(reqwest::Body::from(serde_json::to_vec(body)?))"
+            "Some(bytes::Bytes::from(serde_json::to_vec(body)?))"
         } else {
             "Some(body.into())"
         }
@@ -788,11 +866,14 @@ fn get_fn_inner(
     };
 
     if all_pages && pagination_property.is_empty() {
-        return Ok(format!("self.client.get_all_pages(&url, {}).await", body));
+        return Ok(format!(
+            "self.client.get_all_pages(\"{}\", &url, {}).await",
+            op_id, body
+        ));
     } else if all_pages {
         // We will do a custom function here.
         let inner = format!(
-            r#"let mut resp: {} = self.client.{}(&url, {}).await?;
+            r#"let mut resp: {} = self.client.{}("{}", &url, {}).await?;
 
             let mut {} = resp.{};
             let mut page = resp.next_page;
@@ -800,9 +881,9 @@ fn get_fn_inner(
             // Paginate if we should.
             while !page.is_empty() {{
                 if !url.contains('?') {{
-                    resp = self.client.{}(&format!("{{}}?page={{}}", url, page), {}).await?;
+                    resp = self.client.{}("{}", &format!("{{}}?page={{}}", url, page), {}).await?;
                 }} else {{
-                    resp = self.client.{}(&format!("{{}}&page={{}}", url, page), {}).await?;
+                    resp = self.client.{}("{}", &format!("{{}}&page={{}}", url, page), {}).await?;
                 }}
 
 
@@ -819,12 +900,15 @@ fn get_fn_inner(
             Ok({})"#,
             response_type,
             m.to_lowercase(),
+            op_id,
             body,
             pagination_property,
             pagination_property,
             m.to_lowercase(),
+            op_id,
             body,
             m.to_lowercase(),
+            op_id,
             body,
             pagination_property,
             pagination_property,
@@ -848,20 +932,22 @@ fn get_fn_inner(
     {
         if inner_response_type.is_empty() {
             return Ok(format!(
-                "self.client.{}(&url, {}).await",
+                "self.client.{}(\"{}\", &url, {}).await",
                 m.to_lowercase(),
+                op_id,
                 body
             ));
         }
 
         // Okay we have an inner response type, let's return that instead.
         return Ok(format!(
-            r#"let resp: {} = self.client.{}(&url, {}).await?;
+            r#"let resp: {} = self.client.{}("{}", &url, {}).await?;
 
                 // Return our response data.
                 Ok(resp.{})"#,
             response_type,
             m.to_lowercase(),
+            op_id,
             body,
             pagination_property
         ));
@@ -873,13 +959,84 @@ fn get_fn_inner(
 
     Ok(r#"self.client.post_media(
             &url,
-            Some(reqwest::Body::from(serde_json::to_vec(body)?)),
+            Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
             crate::utils::MediaType::Json,
             crate::auth::AuthenticationConstraint::JWT,
         ).await"#
         .to_string())
 }
 
+/*
+ * The `tracing` span that a generated function runs in, when the feature is
+ * enabled. The client records the status, retries and request id on it.
+ */
+fn get_fn_span(op_id: &str, tag: &str, m: &str, p: &str) -> String {
+    format!(
+        r#"#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "{}",
+        skip_all,
+        err,
+        fields(
+            operation_id = "{}",
+            tag = "{}",
+            method = "{}",
+            path = "{}",
+            status = tracing::field::Empty,
+            retry_count = tracing::field::Empty,
+            request_id = tracing::field::Empty,
+        )
+    )
+)]"#,
+        op_id, op_id, tag, m, p
+    )
+}
+
+/*
+ * The span for an `ensure` function. It covers both the `POST` and the `GET`
+ * that may follow, which each run in a child span of their own.
+ */
+fn get_fn_span_ensure(op_id: &str, tag: &str, p: &str) -> String {
+    format!(
+        r#"#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "{}_ensure",
+        skip_all,
+        err,
+        fields(
+            tag = "{}",
+            path = "{}",
+        )
+    )
+)]"#,
+        op_id.strip_suffix("_post").unwrap_or(op_id),
+        tag,
+        p
+    )
+}
+
+/*
+ * The span for a request that a generated function makes without going
+ * through the generated function for that request.
+ */
+fn get_child_span(op_id: &str, tag: &str, m: &str, p: &str) -> String {
+    format!(
+        r#"tracing::info_span!(
+            "{}",
+            operation_id = "{}",
+            tag = "{}",
+            method = "{}",
+            path = "{}",
+            status = tracing::field::Empty,
+            retry_count = tracing::field::Empty,
+            request_id = tracing::field::Empty,
+        )"#,
+        op_id, op_id, tag, m, p
+    )
+}
+
 fn get_fn_docs(
     o: &openapiv3::Operation,
     m: &str,
@@ -1007,6 +1164,45 @@ fn get_fn_docs_all(o: &openapiv3::Operation, m: &str, p: &str, fn_name: &str) ->
     Ok(out.trim().to_string())
 }
 
+fn get_fn_docs_ensure(docs: &str, m: &str, p: &str, get_path: &str) -> String {
+    let performs = format!(
+        "* This function performs a `{}` to the `{}` endpoint.",
+        m, p
+    );
+
+    docs.replacen(
+        &performs,
+        &format!(
+            "{}\n*\n* As opposed to `post`, if the resource already exists this function fetches it from the `{}` endpoint rather than failing, and reports any fields that differ from the request.",
+            performs, get_path
+        ),
+        1,
+    )
+}
+
+/*
+ * Find the path to fetch a single item of the collection at `p`, and the name
+ * of the parameter that identifies the item.
+ */
+fn get_item_path(api: &openapiv3::OpenAPI, p: &str) -> Option<(String, String, String)> {
+    let prefix = format!("{}/", p);
+    api.paths.iter().find_map(|(pn, path)| {
+        let param = pn.strip_prefix(&prefix)?;
+        if param.contains('/') || !param.starts_with('{') || !param.ends_with('}') {
+            return None;
+        }
+        let get = path.item().ok()?.get.as_ref()?;
+
+        Some((
+            pn.to_string(),
+            get.operation_id
+                .clone()
+                .unwrap_or_else(|| path_to_operation_id(pn, "GET")),
+            to_snake_case(param.trim_start_matches('{').trim_end_matches('}')),
+        ))
+    })
+}
+
 fn is_page_param(s: &str) -> bool {
     s == "next_page" || s == "page_token" || s == "limit"
 }
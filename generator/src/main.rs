@@ -1667,6 +1667,23 @@ impl TypeSpace {
                         }
                     }
 
+                    // Oxide's network address schemas do not carry a format, only
+                    // their name, so map those onto our validating types here.
+                    if let Some(n) = name {
+                        let t = match struct_name(n).as_str() {
+                            "Ipv4Net" => Some("crate::types::Ipv4Net"),
+                            "Ipv6Net" => Some("crate::types::Ipv6Net"),
+                            "MacAddr" => Some("crate::types::MacAddr"),
+                            _ => None,
+                        };
+                        if let Some(t) = t {
+                            return Ok((
+                                Some(uid.to_string()),
+                                TypeDetails::Basic(t.to_string(), s.schema_data.clone()),
+                            ));
+                        }
+                    }
+
                     match &st.format {
                         // It is far too risky to not make all the DateTime/Dates optional
                         // otherwise you are just risking a panic when the vendor passes back
@@ -1779,7 +1796,31 @@ impl TypeSpace {
                             )),
                             "ip" => Ok((
                                 Some(uid.to_string()),
-                                TypeDetails::Basic("String".to_string(), s.schema_data.clone()),
+                                TypeDetails::Basic(
+                                    "std::net::IpAddr".to_string(),
+                                    s.schema_data.clone(),
+                                ),
+                            )),
+                            "ipv4net" => Ok((
+                                Some(uid.to_string()),
+                                TypeDetails::Basic(
+                                    "crate::types::Ipv4Net".to_string(),
+                                    s.schema_data.clone(),
+                                ),
+                            )),
+                            "ipv6net" => Ok((
+                                Some(uid.to_string()),
+                                TypeDetails::Basic(
+                                    "crate::types::Ipv6Net".to_string(),
+                                    s.schema_data.clone(),
+                                ),
+                            )),
+                            "mac" => Ok((
+                                Some(uid.to_string()),
+                                TypeDetails::Basic(
+                                    "crate::types::MacAddr".to_string(),
+                                    s.schema_data.clone(),
+                                ),
                             )),
                             "uri" => Ok((
                                 Some(uid.to_string()),
@@ -2354,6 +2395,44 @@ fn gen(api: &OpenAPI, tags: Vec<String>) -> Result<String> {
     a("pub mod types;");
     a("#[doc(hidden)]");
     a("pub mod utils;");
+    a("/// IP address utilization reporting for VPC subnets.");
+    a("pub mod utilization;");
+    a("/// Export the topology of a VPC as a graph.");
+    a("pub mod topology;");
+    a("/// How the client sends requests, and how to send them another way.");
+    a("pub mod transport;");
+    a("/// Hooks that run around every request.");
+    a("pub mod middleware;");
+    a("/// What the client leaves out of its logs.");
+    a("pub mod redact;");
+    a("/// Where the token sent with each request comes from.");
+    a("pub mod token;");
+    a("/// Authenticating with a session cookie rather than a token.");
+    a("pub mod cookies;");
+    a("/// Named connection profiles from a configuration file.");
+    a("pub mod config;");
+    a("/// Declarative management of organizations and projects from a manifest.");
+    a("pub mod declarative;");
+    a("/// Cascading deletion of a project and everything in it.");
+    a("pub mod teardown;");
+    a("/// Copy the layout of a project, and optionally its data, into a new project.");
+    a("pub mod clone;");
+    a("/// Scheduled disk snapshots with time-based retention.");
+    a("pub mod retention;");
+    a("/// Map resource ids back to the names that address them in the API.");
+    a("pub mod resolver;");
+    a("/// Find resources by name or id across every organization and project.");
+    a("pub mod search;");
+    a("/// Watch a collection for changes by polling it.");
+    a("pub mod watch;");
+    a("/// Resource usage totals per project, per organization and fleet-wide.");
+    a("pub mod usage;");
+    a("/// Find resources that are likely no longer in use.");
+    a("pub mod orphans;");
+    a("/// Start, stop, reboot or delete many instances at once.");
+    a("pub mod bulk;");
+    a("/// Record requests and responses, and replay them without a server.");
+    a("pub mod cassette;");
 
     /*
      * Import the module for each tag.
@@ -3086,7 +3165,9 @@ bytes = {{ version = "1", features = ["serde"] }}
 chrono = {{ version = "0.4", features = ["serde"] }}
 chrono-humanize = "^0.2.1"
 dirs = {{ version = "^4.0.0", optional = true }}
+futures = "0.3"
 http = "^0.2.4"
+hyper = {{ version = "0.14", features = ["client", "http1"] }}
 hyperx = "1"
 ipnetwork = "^0.18"
 log = {{ version = "^0.4", features = ["serde"] }}
@@ -3098,9 +3179,13 @@ schemars = {{ version = "0.8", features = ["bytes", "chrono", "url", "uuid"] }}
 serde = {{ version = "1", features = ["derive"] }}
 serde_json = "1"
 serde_with = "1"
+serde_yaml = "0.8"
 serde_urlencoded = "^0.7"
 tabled = {{ version = "0.5.0", features = ["color"] }}
 thiserror = "^1"
+tokio = {{ version = "1", features = ["net", "process", "rt", "sync", "time"] }}
+toml = "^0.5"
+tracing = {{ version = "0.1.36", optional = true }}
 url = {{ version = "2", features = ["serde"] }}
 uuid = {{ version = "^0.8", features = ["serde", "v4"] }}
 
@@ -3111,10 +3196,13 @@ Inflector = "^0.11.4"
 nom_pem = "4"
 pretty_assertions = "1"
 tokio = {{ version = "1.8.0", features = ["full"] }}
+tracing-subscriber = {{ version = "0.3", default-features = false, features = ["registry", "std"] }}
 
 [features]
 # enable etag-based http_cache functionality
 httpcache = ["dirs"]
+# open a tracing span for every generated call
+tracing = ["dep:tracing"]
 
 [package.metadata.docs.rs]
 all-features = true
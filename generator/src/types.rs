@@ -43,6 +43,9 @@ pub fn generate_types(api: &openapiv3::OpenAPI, ts: &mut TypeSpace) -> Result<St
             } else if sn == "Ipv6Net" {
                 a(crate::types_templates::IPV6_NET);
 
+                continue;
+            } else if sn == "MacAddr" {
+                a(crate::types_templates::MAC_ADDR);
                 continue;
             } else if sn == "Error" {
                 a(crate::types_templates::ERROR);
@@ -100,6 +103,7 @@ pub fn generate_types(api: &openapiv3::OpenAPI, ts: &mut TypeSpace) -> Result<St
                         && sn != "RouterRoute"
                         && sn != "ImageCreate"
                         && sn != "DiskCreate"
+                        && sn != "NetworkInterface"
                     {
                         a("Default,");
                     }
@@ -126,6 +130,10 @@ pub fn generate_types(api: &openapiv3::OpenAPI, ts: &mut TypeSpace) -> Result<St
                     }
                     a("}");
                     a("");
+
+                    if sn == "NetworkInterface" {
+                        a(crate::types_templates::NETWORK_INTERFACE_DEFAULT);
+                    }
                 }
                 TypeDetails::Basic(..) => {}
                 TypeDetails::Unknown => {}
@@ -146,7 +154,18 @@ pub fn generate_types(api: &openapiv3::OpenAPI, ts: &mut TypeSpace) -> Result<St
     // Iterate over anything we missed.
     if let Some(components) = &api.components {
         for (_i, (sn, s)) in components.schemas.iter().enumerate() {
-            if sn == "Ipv6Net" || sn == "Ipv4Net" {
+            if sn == "Ipv6Net" || sn == "Ipv4Net" || sn == "MacAddr" {
+                // These are rendered from templates, make sure we have them even
+                // if nothing above referenced them by name.
+                if seen.insert(sn.to_string(), true).is_none() {
+                    if sn == "Ipv4Net" {
+                        a(crate::types_templates::IPV4_NET);
+                    } else if sn == "Ipv6Net" {
+                        a(crate::types_templates::IPV6_NET);
+                    } else {
+                        a(crate::types_templates::MAC_ADDR);
+                    }
+                }
                 continue;
             }
 
@@ -158,6 +158,13 @@ pub const IPV4_NET: &str = r##"/// An `Ipv4Net` represents a IPv4 subnetwork, in
 #[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
 pub struct Ipv4Net(pub ipnetwork::Ipv4Network);
 
+impl Default for Ipv4Net {
+    /// `0.0.0.0/0`, every IPv4 address.
+    fn default() -> Self {
+        Ipv4Net(ipnetwork::Ipv4Network::new(std::net::Ipv4Addr::UNSPECIFIED, 0).unwrap())
+    }
+}
+
 impl Ipv4Net {
     /// Return `true` if this IPv4 subnetwork is from an RFC 1918 private
     /// address space.
@@ -236,6 +243,13 @@ pub const IPV6_NET: &str = r##"/// An `Ipv6Net` represents a IPv6 subnetwork, in
 #[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
 pub struct Ipv6Net(pub ipnetwork::Ipv6Network);
 
+impl Default for Ipv6Net {
+    /// `::/0`, every IPv6 address.
+    fn default() -> Self {
+        Ipv6Net(ipnetwork::Ipv6Network::new(std::net::Ipv6Addr::UNSPECIFIED, 0).unwrap())
+    }
+}
+
 impl Ipv6Net {
     /// The length for all VPC IPv6 prefixes
     pub const VPC_IPV6_PREFIX_LENGTH: u8 = 48;
@@ -386,6 +400,89 @@ impl std::str::FromStr for IpNet {
 }
 "#;
 
+pub const MAC_ADDR: &str = r##"/// A Media Access Control address, in EUI-48 format
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl std::fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}", a, b, c, d, e, g)
+    }
+}
+
+impl std::str::FromStr for MacAddr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut octets = [0u8; 6];
+        let mut parts = s.split(':');
+        for octet in octets.iter_mut() {
+            let part = parts
+                .next()
+                .ok_or_else(|| format!("invalid MAC address {}", s))?;
+            // `from_str_radix` would also take a sign, as in `+f`.
+            if part.len() != 2 || !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(format!("invalid MAC address {}", s));
+            }
+            *octet = u8::from_str_radix(part, 16)
+                .map_err(|_| format!("invalid MAC address {}", s))?;
+        }
+        if parts.next().is_some() {
+            return Err(format!("invalid MAC address {}", s));
+        }
+        Ok(MacAddr(octets))
+    }
+}
+
+impl Serialize for MacAddr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for MacAddr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for MacAddr {
+    fn schema_name() -> String {
+        "MacAddr".to_string()
+    }
+
+    fn json_schema(
+        _: &mut schemars::gen::SchemaGenerator,
+    ) -> schemars::schema::Schema {
+        schemars::schema::Schema::Object(
+            schemars::schema::SchemaObject {
+                metadata: Some(Box::new(schemars::schema::Metadata {
+                    title: Some("A MAC address".to_string()),
+                    description: Some("A Media Access Control address, in EUI-48 format".to_string()),
+                    examples: vec!["ff:ff:ff:ff:ff:ff".into()],
+                    ..Default::default()
+                })),
+                instance_type: Some(schemars::schema::SingleOrVec::Single(Box::new(schemars::schema::InstanceType::String))),
+                string: Some(Box::new(schemars::schema::StringValidation {
+                    // Six octets of two hex digits, separated by ":".
+                    max_length: Some(17),
+                    min_length: Some(17),
+                    pattern: Some("^([0-9a-fA-F]{2}:){5}[0-9a-fA-F]{2}$".to_string()),
+                })),
+                ..Default::default()
+            }
+        )
+    }
+}"##;
+
 pub const DISK_SOURCE: &str = r##"#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "type")]
@@ -538,3 +635,22 @@ impl std::str::FromStr for DiskSourceType {
     }
 }
 "##;
+
+pub const NETWORK_INTERFACE_DEFAULT: &str = r#"impl Default for NetworkInterface {
+    // `std::net::IpAddr` has no default, so this cannot be derived.
+    fn default() -> Self {
+        NetworkInterface {
+            id: Default::default(),
+            name: Default::default(),
+            description: Default::default(),
+            instance_id: Default::default(),
+            ip: std::net::Ipv4Addr::UNSPECIFIED.into(),
+            mac: Default::default(),
+            subnet_id: Default::default(),
+            time_created: Default::default(),
+            time_modified: Default::default(),
+            vpc_id: Default::default(),
+        }
+    }
+}
+"#;
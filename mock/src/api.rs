@@ -0,0 +1,1577 @@
+//! Request handling: routing, validation and the effect of each endpoint on
+//! the store.
+use std::{collections::BTreeMap, net::IpAddr};
+
+use chrono::Utc;
+use hyper::{Method, StatusCode};
+use oxide_api::{
+    types::{
+        Disk, DiskCreate, DiskIdentifier, DiskSource, DiskState, FleetRolesPolicy, GlobalImage,
+        Image, ImageCreate, ImageSource, Instance, InstanceCreate, InstanceDiskAttachment,
+        InstanceMigrate, InstanceNetworkInterfaceAttachment, InstanceState, IpNet, Ipv4Net,
+        Ipv6Net, MacAddr, NetworkInterface, NetworkInterfaceCreate, Organization,
+        OrganizationCreate, OrganizationRolesPolicy, OrganizationUpdate, Project, ProjectCreate,
+        ProjectRolesPolicy, ProjectUpdate, RouteDestination, RouteTarget, RouterRoute,
+        RouterRouteCreateParams, RouterRouteKind, RouterRouteUpdateParams, Saga, SagaState,
+        SessionUser, Silo, SiloCreate, SiloRolesPolicy, Snapshot, SnapshotCreate, SshKey,
+        SshKeyCreate, Vpc, VpcCreate, VpcFirewallRule, VpcFirewallRuleAction,
+        VpcFirewallRuleDirection, VpcFirewallRuleFilter, VpcFirewallRuleHostFilter,
+        VpcFirewallRuleProtocol, VpcFirewallRuleStatus, VpcFirewallRuleTarget,
+        VpcFirewallRuleUpdateParams, VpcRouter, VpcRouterCreate, VpcRouterKind, VpcRouterUpdate,
+        VpcSubnet, VpcSubnetCreate, VpcSubnetUpdate, VpcUpdate,
+    },
+    utils::DisplayOptionDateTime,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{store::Store, ApiError};
+
+/// The number of items in a page if the request does not say.
+const DEFAULT_LIMIT: usize = 100;
+const MAX_NAME_LENGTH: usize = 63;
+const MIN_MEMORY: u64 = 1 << 30;
+const MIN_DISK_SIZE: u64 = 1 << 30;
+const MAX_DISKS_PER_INSTANCE: usize = 8;
+const MAX_NICS_PER_INSTANCE: usize = 8;
+const MAX_FIREWALL_RULES: usize = 1024;
+/// The mock does not fetch images from their URL, so it cannot know their
+/// size. They are taken to be as small as a disk can be.
+const URL_IMAGE_SIZE: u64 = MIN_DISK_SIZE;
+/// The IPv4 block of the subnet created with each VPC.
+const DEFAULT_SUBNET_IPV4_BLOCK: &str = "172.30.0.0/22";
+/// The number of addresses at the start of a subnet reserved for the system.
+const RESERVED_ADDRESSES: u32 = 5;
+
+/// The collections that can be listed, with names replaced by `*`.
+const COLLECTIONS: &[&str] = &[
+    "organizations",
+    "organizations/*/projects",
+    "organizations/*/projects/*/instances",
+    "organizations/*/projects/*/instances/*/network-interfaces",
+    "organizations/*/projects/*/disks",
+    "organizations/*/projects/*/snapshots",
+    "organizations/*/projects/*/images",
+    "organizations/*/projects/*/vpcs",
+    "organizations/*/projects/*/vpcs/*/subnets",
+    "organizations/*/projects/*/vpcs/*/routers",
+    "organizations/*/projects/*/vpcs/*/routers/*/routes",
+    "images",
+    "silos",
+    "session/*/sshkeys",
+];
+
+/// The collections whose items can be updated.
+const UPDATABLE: &[&str] = &[
+    "organizations",
+    "projects",
+    "vpcs",
+    "subnets",
+    "routers",
+    "routes",
+];
+
+/// A request, with the body not yet parsed.
+pub(crate) struct Request {
+    pub(crate) method: Method,
+    pub(crate) path: String,
+    pub(crate) query: BTreeMap<String, String>,
+    pub(crate) body: Vec<u8>,
+}
+
+pub(crate) enum Reply {
+    Json(StatusCode, Value),
+    NoContent,
+}
+
+/// The state of the mock API.
+pub(crate) struct Api {
+    store: Store,
+    /// The id of the user every request is made as.
+    user_id: String,
+}
+
+impl Default for Api {
+    fn default() -> Self {
+        Api {
+            store: Store::default(),
+            user_id: new_id(),
+        }
+    }
+}
+
+impl Api {
+    pub(crate) fn handle(&mut self, req: &Request) -> Result<Reply, ApiError> {
+        let segments: Vec<String> = req
+            .path
+            .trim_start_matches('/')
+            .split('/')
+            .map(|s| {
+                percent_encoding::percent_decode_str(s)
+                    .decode_utf8_lossy()
+                    .to_string()
+            })
+            .collect();
+        let s: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let method = &req.method;
+
+        match s.as_slice() {
+            ["policy"] => self.policy::<FleetRolesPolicy>(method, &[], &req.body),
+            ["organizations", _, "policy"] => {
+                self.policy::<OrganizationRolesPolicy>(method, &s[..2], &req.body)
+            }
+            ["organizations", _, "projects", _, "policy"] => {
+                self.policy::<ProjectRolesPolicy>(method, &s[..4], &req.body)
+            }
+            ["silos", _, "policy"] => self.policy::<SiloRolesPolicy>(method, &s[..2], &req.body),
+            ["session", "me"] => {
+                allow(method, &[Method::GET])?;
+                Ok(ok(&SessionUser {
+                    id: self.user_id.to_string(),
+                }))
+            }
+            ["session", me, ..] if *me != "me" => Err(ApiError::route_not_found()),
+            ["sagas" | "users"] => {
+                allow(method, &[Method::GET])?;
+                self.list(&s, &req.query)
+            }
+            ["sagas", id] => {
+                allow(method, &[Method::GET])?;
+                self.store
+                    .get("/sagas", id)
+                    .map(|saga| Reply::Json(StatusCode::OK, saga.clone()))
+                    .ok_or_else(|| ApiError::not_found_by_id("saga", id))
+            }
+            ["organizations", _, "projects", _, "instances", _, action @ ("start" | "stop" | "reboot" | "migrate")] =>
+            {
+                allow(method, &[Method::POST])?;
+                self.instance_action(&s[..6], action, &req.body)
+            }
+            ["organizations", _, "projects", _, "instances", _, "disks"] => {
+                allow(method, &[Method::GET])?;
+                self.instance_disks(&s[..6], &req.query)
+            }
+            ["organizations", _, "projects", _, "instances", _, "disks", action @ ("attach" | "detach")] =>
+            {
+                allow(method, &[Method::POST])?;
+                self.attach_disk(&s[..6], *action == "attach", &req.body)
+            }
+            ["organizations", _, "projects", _, "vpcs", _, "firewall", "rules"] => {
+                self.firewall_rules(method, &s[..6], &req.body)
+            }
+            ["organizations", _, "projects", _, "vpcs", _, "subnets", _, "network-interfaces"] => {
+                allow(method, &[Method::GET])?;
+                self.subnet_network_interfaces(&s[..8], &req.query)
+            }
+            _ if !s.len().is_multiple_of(2) && COLLECTIONS.contains(&shape(&s).as_str()) => {
+                match *method {
+                    Method::GET => self.list(&s, &req.query),
+                    Method::POST => {
+                        let created = self.create(&s, &req.body)?;
+                        Ok(Reply::Json(StatusCode::CREATED, created))
+                    }
+                    _ => Err(ApiError::method_not_allowed()),
+                }
+            }
+            _ if s.len().is_multiple_of(2)
+                && COLLECTIONS.contains(&shape(&s[..s.len() - 1]).as_str()) =>
+            {
+                let collection = s[s.len() - 2];
+                match *method {
+                    Method::GET => Ok(Reply::Json(StatusCode::OK, self.require(&s)?.clone())),
+                    Method::PUT if UPDATABLE.contains(&collection) => {
+                        Ok(Reply::Json(StatusCode::OK, self.update(&s, &req.body)?))
+                    }
+                    Method::DELETE => {
+                        self.delete(&s)?;
+                        Ok(Reply::NoContent)
+                    }
+                    _ => Err(ApiError::method_not_allowed()),
+                }
+            }
+            _ => Err(ApiError::route_not_found()),
+        }
+    }
+
+    /// Look up the item at `s`, which must exist.
+    fn require(&self, s: &[&str]) -> Result<&Value, ApiError> {
+        self.lookup(s)?.ok_or_else(ApiError::route_not_found)
+    }
+
+    /// Look up the item at `s`, and each item it is beneath, returning the
+    /// first that does not exist as an error. There is no item at paths such
+    /// as `session/me`, which are not in a collection.
+    fn lookup(&self, s: &[&str]) -> Result<Option<&Value>, ApiError> {
+        let mut found = None;
+        for i in (0..s.len().saturating_sub(1)).step_by(2) {
+            let kind = match kind(s[i]) {
+                Some(kind) => kind,
+                // Not a collection, as with `session/me`.
+                None => continue,
+            };
+            found = Some(
+                self.store
+                    .get(&path(&s[..=i]), s[i + 1])
+                    .ok_or_else(|| ApiError::not_found(kind, s[i + 1]))?,
+            );
+        }
+        Ok(found)
+    }
+
+    /// Look up an item beneath a project by name.
+    fn require_in(
+        &self,
+        project: &[&str],
+        collection: &str,
+        name: &str,
+    ) -> Result<&Value, ApiError> {
+        let mut s = project.to_vec();
+        s.extend([collection, name]);
+        self.require(&s)
+    }
+
+    fn list(&self, s: &[&str], query: &BTreeMap<String, String>) -> Result<Reply, ApiError> {
+        self.lookup(&s[..s.len() - 1])?;
+        let items = self.store.list(&path(s)).into_iter().cloned().collect();
+        Ok(Reply::Json(StatusCode::OK, page(items, query)?))
+    }
+
+    fn create(&mut self, s: &[&str], body: &[u8]) -> Result<Value, ApiError> {
+        let parent = &s[..s.len() - 1];
+        match s[s.len() - 1] {
+            "organizations" => {
+                let body: OrganizationCreate = parse(body)?;
+                self.check_new_name(s, &body.name)?;
+                let organization = Organization {
+                    id: new_id(),
+                    name: body.name,
+                    description: body.description,
+                    time_created: now(),
+                    time_modified: now(),
+                };
+                Ok(self.insert(s, &organization.name, &organization))
+            }
+            "projects" => {
+                let organization_id = id_of(self.require(parent)?);
+                let body: ProjectCreate = parse(body)?;
+                self.check_new_name(s, &body.name)?;
+                let project = Project {
+                    id: new_id(),
+                    name: body.name,
+                    description: body.description,
+                    organization_id,
+                    time_created: now(),
+                    time_modified: now(),
+                };
+                Ok(self.insert(s, &project.name, &project))
+            }
+            "instances" => self.create_instance(parent, body),
+            "disks" => {
+                let project_id = id_of(self.require(parent)?);
+                let body: DiskCreate = parse(body)?;
+                let disk = self.new_disk(parent, &project_id, body, &[])?;
+                self.record_saga();
+                Ok(self.insert(s, &disk.name, &disk))
+            }
+            "snapshots" => {
+                let project_id = id_of(self.require(parent)?);
+                let body: SnapshotCreate = parse(body)?;
+                self.check_new_name(s, &body.name)?;
+                let disk = self.require_in(parent, "disks", &body.disk)?;
+                let snapshot = Snapshot {
+                    id: new_id(),
+                    name: body.name,
+                    description: body.description,
+                    disk_id: id_of(disk),
+                    project_id,
+                    size: disk["size"].as_u64().unwrap_or_default(),
+                    time_created: now(),
+                    time_modified: now(),
+                };
+                self.record_saga();
+                Ok(self.insert(s, &snapshot.name, &snapshot))
+            }
+            "images" if s.len() == 1 => {
+                let body: ImageCreate = parse(body)?;
+                self.check_new_name(s, &body.name)?;
+                let (block_size, size, url) = self.image_source(None, &body)?;
+                let image = GlobalImage {
+                    id: new_id(),
+                    name: body.name,
+                    description: body.description,
+                    block_size,
+                    digest: None,
+                    size,
+                    time_created: now(),
+                    time_modified: now(),
+                    url,
+                    version: String::new(),
+                };
+                Ok(self.insert(s, &image.name, &image))
+            }
+            "images" => {
+                let project_id = id_of(self.require(parent)?);
+                let body: ImageCreate = parse(body)?;
+                self.check_new_name(s, &body.name)?;
+                let (block_size, size, url) = self.image_source(Some(parent), &body)?;
+                let image = Image {
+                    id: new_id(),
+                    name: body.name,
+                    description: body.description,
+                    block_size,
+                    digest: None,
+                    project_id,
+                    size,
+                    time_created: now(),
+                    time_modified: now(),
+                    url,
+                    version: String::new(),
+                };
+                Ok(self.insert(s, &image.name, &image))
+            }
+            "vpcs" => self.create_vpc(parent, body),
+            "subnets" => {
+                let vpc = self.require(parent)?.clone();
+                let body: VpcSubnetCreate = parse(body)?;
+                self.check_new_name(s, &body.name)?;
+                let subnet = self.new_subnet(parent, &vpc, body)?;
+                Ok(self.insert(s, &subnet.name, &subnet))
+            }
+            "routers" => {
+                let vpc_id = id_of(self.require(parent)?);
+                let body: VpcRouterCreate = parse(body)?;
+                self.check_new_name(s, &body.name)?;
+                let router = VpcRouter {
+                    id: new_id(),
+                    name: body.name,
+                    description: body.description,
+                    kind: VpcRouterKind::Custom,
+                    time_created: now(),
+                    time_modified: now(),
+                    vpc_id,
+                };
+                Ok(self.insert(s, &router.name, &router))
+            }
+            "routes" => {
+                let vpc_router_id = id_of(self.require(parent)?);
+                let body: RouterRouteCreateParams = parse(body)?;
+                self.check_new_name(s, &body.name)?;
+                let route = RouterRoute {
+                    id: new_id(),
+                    name: body.name,
+                    description: body.description,
+                    destination: body.destination,
+                    kind: RouterRouteKind::Custom,
+                    target: body.target,
+                    time_created: now(),
+                    time_modified: now(),
+                    vpc_router_id,
+                };
+                Ok(self.insert(s, &route.name, &route))
+            }
+            "network-interfaces" => {
+                let instance = self.require(parent)?.clone();
+                if instance["run_state"] != "stopped" {
+                    return Err(ApiError::invalid_request(
+                        "instance must be stopped to attach a new network interface",
+                    ));
+                }
+                let body: NetworkInterfaceCreate = parse(body)?;
+                let existing: Vec<Value> = self.store.list(&path(s)).into_iter().cloned().collect();
+                let nic = self.new_network_interface(&parent[..4], &instance, body, &existing)?;
+                Ok(self.insert(s, &nic.name, &nic))
+            }
+            "sshkeys" => {
+                let body: SshKeyCreate = parse(body)?;
+                self.check_new_name(s, &body.name)?;
+                if !is_ssh_public_key(&body.public_key) {
+                    return Err(ApiError::invalid_value(
+                        "public_key",
+                        "not a valid SSH public key",
+                    ));
+                }
+                let key = SshKey {
+                    id: new_id(),
+                    name: body.name,
+                    description: body.description,
+                    public_key: body.public_key,
+                    silo_user_id: self.user_id.to_string(),
+                    time_created: now(),
+                    time_modified: now(),
+                };
+                Ok(self.insert(s, &key.name, &key))
+            }
+            "silos" => {
+                let body: SiloCreate = parse(body)?;
+                self.check_new_name(s, &body.name)?;
+                let silo = Silo {
+                    id: new_id(),
+                    name: body.name,
+                    description: body.description,
+                    discoverable: body.discoverable,
+                    time_created: now(),
+                    time_modified: now(),
+                };
+                Ok(self.insert(s, &silo.name, &silo))
+            }
+            _ => Err(ApiError::method_not_allowed()),
+        }
+    }
+
+    fn insert<T: Serialize>(&mut self, s: &[&str], name: &str, item: &T) -> Value {
+        let value = to_value(item);
+        self.store.insert(&path(s), name, value.clone());
+        value
+    }
+
+    /// Check a name is valid and not taken in the collection at `s`.
+    fn check_new_name(&self, s: &[&str], name: &str) -> Result<(), ApiError> {
+        check_name("name", name)?;
+        match self.store.get(&path(s), name) {
+            Some(_) => Err(ApiError::already_exists(
+                kind(s[s.len() - 1]).unwrap_or_default(),
+                name,
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn record_saga(&mut self) {
+        let saga = Saga {
+            id: new_id(),
+            state: SagaState::Succeeded,
+        };
+        self.store.insert("/sagas", &saga.id, to_value(&saga));
+    }
+
+    fn create_instance(&mut self, project: &[&str], body: &[u8]) -> Result<Value, ApiError> {
+        let project_id = id_of(self.require(project)?);
+        let body: InstanceCreate = parse(body)?;
+        let mut s = project.to_vec();
+        s.push("instances");
+        self.check_new_name(&s, &body.name)?;
+        if body.ncpus == 0 {
+            return Err(ApiError::invalid_value(
+                "ncpus",
+                "instances must have at least 1 vCPU",
+            ));
+        }
+        if body.memory < MIN_MEMORY {
+            return Err(ApiError::invalid_value(
+                "size",
+                "memory must be at least 1 GiB",
+            ));
+        }
+        if body.disks.len() > MAX_DISKS_PER_INSTANCE {
+            return Err(ApiError::invalid_value(
+                "disks",
+                &format!(
+                    "cannot attach more than {} disks to instance",
+                    MAX_DISKS_PER_INSTANCE
+                ),
+            ));
+        }
+
+        let instance = Instance {
+            id: new_id(),
+            name: body.name,
+            description: body.description,
+            hostname: body.hostname,
+            memory: body.memory,
+            ncpus: body.ncpus,
+            project_id: project_id.to_string(),
+            run_state: InstanceState::Running,
+            time_created: now(),
+            time_modified: now(),
+            time_run_state_updated: now(),
+        };
+
+        // Check everything before changing anything, so that a failed request
+        // leaves nothing behind.
+        let mut new_disks: Vec<Disk> = Vec::new();
+        let mut attached: Vec<String> = Vec::new();
+        for disk in body.disks {
+            match disk {
+                InstanceDiskAttachment::Create {
+                    description,
+                    disk_source,
+                    name,
+                    size,
+                } => {
+                    let create = DiskCreate {
+                        name,
+                        description,
+                        disk_source,
+                        size,
+                    };
+                    let disk = self.new_disk(project, &project_id, create, &new_disks)?;
+                    new_disks.push(disk);
+                }
+                InstanceDiskAttachment::Attach(name) => {
+                    let disk = self.require_in(project, "disks", &name)?;
+                    if disk["state"]["state"] != "detached" || attached.contains(&name) {
+                        return Err(ApiError::invalid_request(&format!(
+                            "cannot attach disk \"{}\": disk is attached to another instance",
+                            name
+                        )));
+                    }
+                    attached.push(name);
+                }
+            }
+        }
+
+        let creates = match body.network_interfaces {
+            Some(InstanceNetworkInterfaceAttachment::Create(creates)) => creates,
+            Some(InstanceNetworkInterfaceAttachment::None) => Vec::new(),
+            // The default interface is only added if the project has the
+            // default VPC and subnet to put it in.
+            _ if self
+                .require_in(project, "vpcs", "default")
+                .and_then(|_| {
+                    self.require(&[project, &["vpcs", "default", "subnets", "default"]].concat())
+                })
+                .is_ok() =>
+            {
+                vec![NetworkInterfaceCreate {
+                    name: "net0".to_string(),
+                    description: "The default network interface".to_string(),
+                    ip: None,
+                    subnet_name: "default".to_string(),
+                    vpc_name: "default".to_string(),
+                }]
+            }
+            _ => Vec::new(),
+        };
+        if creates.len() > MAX_NICS_PER_INSTANCE {
+            return Err(ApiError::invalid_value(
+                "network_interfaces",
+                &format!(
+                    "cannot create more than {} network interfaces for an instance",
+                    MAX_NICS_PER_INSTANCE
+                ),
+            ));
+        }
+        let instance_value = to_value(&instance);
+        let mut nics: Vec<Value> = Vec::new();
+        for create in creates {
+            let nic = self.new_network_interface(project, &instance_value, create, &nics)?;
+            nics.push(to_value(&nic));
+        }
+
+        let attached_state = to_value(&DiskState::Attached(instance.id.to_string()));
+        let disks = path(&[project, &["disks"]].concat());
+        for disk in new_disks {
+            let mut disk = to_value(&disk);
+            disk["state"] = attached_state.clone();
+            let name = disk["name"].as_str().unwrap_or_default().to_string();
+            self.store.insert(&disks, &name, disk);
+        }
+        for name in attached {
+            if let Some(disk) = self.store.get_mut(&disks, &name) {
+                disk["state"] = attached_state.clone();
+            }
+        }
+        let nic_collection = format!("{}/{}/network-interfaces", path(&s), instance.name);
+        for nic in nics {
+            let name = nic["name"].as_str().unwrap_or_default().to_string();
+            self.store.insert(&nic_collection, &name, nic);
+        }
+        self.record_saga();
+        Ok(self.insert(&s, &instance.name, &instance))
+    }
+
+    /// Check a disk can be created in a project, and return it if so. The
+    /// disks in `pending` are about to be created as well.
+    fn new_disk(
+        &self,
+        project: &[&str],
+        project_id: &str,
+        body: DiskCreate,
+        pending: &[Disk],
+    ) -> Result<Disk, ApiError> {
+        let disks = [project, &["disks"]].concat();
+        self.check_new_name(&disks, &body.name)?;
+        if pending.iter().any(|d| d.name == body.name) {
+            return Err(ApiError::already_exists("disk", &body.name));
+        }
+
+        let project_path = path(project);
+        let (block_size, image_id, snapshot_id, min_size) = match &body.disk_source {
+            DiskSource::Blank { block_size } => {
+                let block_size = check_block_size(*block_size)?;
+                (block_size, String::new(), String::new(), 0)
+            }
+            DiskSource::Snapshot { snapshot_id } => {
+                let snapshot = self
+                    .store
+                    .find_by_id(&project_path, "snapshots", snapshot_id)
+                    .ok_or_else(|| ApiError::not_found_by_id("snapshot", snapshot_id))?;
+                let size = snapshot["size"].as_u64().unwrap_or_default();
+                (512, String::new(), snapshot_id.to_string(), size)
+            }
+            DiskSource::Image { image_id } => {
+                let image = self
+                    .store
+                    .find_by_id(&project_path, "images", image_id)
+                    .ok_or_else(|| ApiError::not_found_by_id("image", image_id))?;
+                let block_size = image["block_size"].as_u64().unwrap_or(512);
+                let size = image["size"].as_u64().unwrap_or_default();
+                (block_size, image_id.to_string(), String::new(), size)
+            }
+            DiskSource::GlobalImage { image_id } => {
+                let image = self
+                    .store
+                    .list("/images")
+                    .into_iter()
+                    .find(|i| i["id"] == image_id.as_str())
+                    .ok_or_else(|| ApiError::not_found_by_id("image", image_id))?;
+                let block_size = image["block_size"].as_u64().unwrap_or(512);
+                let size = image["size"].as_u64().unwrap_or_default();
+                (block_size, image_id.to_string(), String::new(), size)
+            }
+        };
+
+        if body.size < MIN_DISK_SIZE {
+            return Err(ApiError::invalid_value(
+                "size",
+                "total size must be at least 1 GiB",
+            ));
+        }
+        if !body.size.is_multiple_of(block_size) {
+            return Err(ApiError::invalid_value(
+                "size",
+                &format!("total size must be a multiple of {}", block_size),
+            ));
+        }
+        if body.size < min_size {
+            return Err(ApiError::invalid_value(
+                "size",
+                &format!(
+                    "disk size {} must be at least the size of its source, {}",
+                    body.size, min_size
+                ),
+            ));
+        }
+
+        Ok(Disk {
+            id: new_id(),
+            device_path: format!("/mnt/{}", body.name),
+            name: body.name,
+            description: body.description,
+            block_size,
+            image_id,
+            project_id: project_id.to_string(),
+            size: body.size,
+            snapshot_id,
+            state: DiskState::Detached,
+            time_created: now(),
+            time_modified: now(),
+        })
+    }
+
+    /// The block size, size and URL of an image to be created in a project,
+    /// or globally if `project` is unset.
+    fn image_source(
+        &self,
+        project: Option<&[&str]>,
+        body: &ImageCreate,
+    ) -> Result<(u64, u64, String), ApiError> {
+        let block_size = check_block_size(body.block_size)?;
+        match &body.source {
+            ImageSource::Url(url) => {
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    return Err(ApiError::invalid_value("url", "must be an HTTP(S) URL"));
+                }
+                Ok((block_size, URL_IMAGE_SIZE, url.to_string()))
+            }
+            ImageSource::Snapshot(id) => {
+                let snapshot = self
+                    .store
+                    .find_by_id(&project.map(path).unwrap_or_default(), "snapshots", id)
+                    .ok_or_else(|| ApiError::not_found_by_id("snapshot", id))?;
+                Ok((
+                    block_size,
+                    snapshot["size"].as_u64().unwrap_or_default(),
+                    String::new(),
+                ))
+            }
+        }
+    }
+
+    fn create_vpc(&mut self, project: &[&str], body: &[u8]) -> Result<Value, ApiError> {
+        let project_id = id_of(self.require(project)?);
+        let body: VpcCreate = parse(body)?;
+        let s = [project, &["vpcs"]].concat();
+        self.check_new_name(&s, &body.name)?;
+        check_name("dns_name", &body.dns_name)?;
+        let ipv6_prefix = match body.ipv6_prefix {
+            Some(prefix) if !prefix.is_vpc_prefix() => {
+                return Err(ApiError::invalid_value(
+                    "ipv6_prefix",
+                    "VPC IPv6 address prefixes must be in the Unique Local Address range \
+                     `fd00::/48`",
+                ));
+            }
+            Some(prefix) => prefix,
+            None => random_vpc_prefix(),
+        };
+
+        let vpc = Vpc {
+            id: new_id(),
+            name: body.name,
+            description: body.description,
+            dns_name: body.dns_name,
+            ipv6_prefix,
+            project_id,
+            system_router_id: new_id(),
+            time_created: now(),
+            time_modified: now(),
+        };
+        let vpc_value = self.insert(&s, &vpc.name, &vpc);
+        let vpc_path = [s.as_slice(), &[vpc.name.as_str()]].concat();
+
+        // Every VPC comes with a system router, a default subnet and
+        // firewall rules that allow traffic within the VPC, SSH, ICMP and RDP.
+        let router = VpcRouter {
+            id: vpc.system_router_id.to_string(),
+            name: "system".to_string(),
+            description: "Routes are automatically added to this router as VPC subnets are created"
+                .to_string(),
+            kind: VpcRouterKind::System,
+            time_created: now(),
+            time_modified: now(),
+            vpc_id: vpc.id.to_string(),
+        };
+        let routers = [vpc_path.as_slice(), &["routers"]].concat();
+        self.insert(&routers, &router.name, &router);
+        let route = RouterRoute {
+            id: new_id(),
+            name: "default".to_string(),
+            description: "The default route of a VPC".to_string(),
+            destination: RouteDestination::IpNet(IpNet::V4(
+                "0.0.0.0/0".parse::<Ipv4Net>().expect("valid network"),
+            )),
+            kind: RouterRouteKind::Default,
+            target: RouteTarget::InternetGateway("outbound".to_string()),
+            time_created: now(),
+            time_modified: now(),
+            vpc_router_id: router.id.to_string(),
+        };
+        let routes = [routers.as_slice(), &["system", "routes"]].concat();
+        self.insert(&routes, &route.name, &route);
+
+        let subnet = self.new_subnet(
+            &vpc_path,
+            &vpc_value,
+            VpcSubnetCreate {
+                name: "default".to_string(),
+                description: format!("The default subnet for {}", vpc.name),
+                ipv4_block: DEFAULT_SUBNET_IPV4_BLOCK.parse().expect("valid network"),
+                ipv6_block: None,
+            },
+        )?;
+        let subnets = [vpc_path.as_slice(), &["subnets"]].concat();
+        self.insert(&subnets, &subnet.name, &subnet);
+
+        let vpc_target = || vec![VpcFirewallRuleTarget::Vpc(vpc.name.to_string())];
+        let rule =
+            |name: &str, description: &str, filters: VpcFirewallRuleFilter| VpcFirewallRule {
+                id: new_id(),
+                name: name.to_string(),
+                description: description.to_string(),
+                action: VpcFirewallRuleAction::Allow,
+                direction: VpcFirewallRuleDirection::Inbound,
+                filters,
+                priority: 65534,
+                status: VpcFirewallRuleStatus::Enabled,
+                targets: vpc_target(),
+                time_created: now(),
+                time_modified: now(),
+                vpc_id: vpc.id.to_string(),
+            };
+        let rules = vec![
+            rule(
+                "allow-internal-inbound",
+                "allow inbound traffic to all instances within the VPC if originated within the \
+                 VPC",
+                VpcFirewallRuleFilter {
+                    hosts: vec![VpcFirewallRuleHostFilter::Vpc(vpc.name.to_string())],
+                    ..Default::default()
+                },
+            ),
+            rule(
+                "allow-ssh",
+                "allow inbound TCP connections on port 22 from anywhere",
+                VpcFirewallRuleFilter {
+                    ports: vec!["22".to_string()],
+                    protocols: vec![VpcFirewallRuleProtocol::Tcp],
+                    ..Default::default()
+                },
+            ),
+            rule(
+                "allow-icmp",
+                "allow inbound ICMP traffic from anywhere",
+                VpcFirewallRuleFilter {
+                    protocols: vec![VpcFirewallRuleProtocol::Icmp],
+                    ..Default::default()
+                },
+            ),
+            rule(
+                "allow-rdp",
+                "allow inbound TCP connections on port 3389 from anywhere",
+                VpcFirewallRuleFilter {
+                    ports: vec!["3389".to_string()],
+                    protocols: vec![VpcFirewallRuleProtocol::Tcp],
+                    ..Default::default()
+                },
+            ),
+        ];
+        self.store
+            .set_singleton(&firewall_path(&vpc_path), to_value(&rules));
+
+        Ok(vpc_value)
+    }
+
+    /// Check a subnet can be created in a VPC, and return it if so.
+    fn new_subnet(
+        &self,
+        vpc_path: &[&str],
+        vpc: &Value,
+        body: VpcSubnetCreate,
+    ) -> Result<VpcSubnet, ApiError> {
+        let ipv4_block = body.ipv4_block;
+        if !ipv4_block.is_private() || ipv4_block.prefix() < 8 || ipv4_block.prefix() > 26 {
+            return Err(ApiError::invalid_value(
+                "ipv4_block",
+                "VPC Subnet IPv4 address ranges must be from a private range, with a prefix \
+                 length between 8 and 26, inclusive",
+            ));
+        }
+        let vpc_prefix: Ipv6Net = serde_json::from_value(vpc["ipv6_prefix"].clone())
+            .map_err(|e| ApiError::internal(&e.to_string()))?;
+
+        let existing: Vec<VpcSubnet> = self
+            .store
+            .list(&path(&[vpc_path, &["subnets"]].concat()))
+            .into_iter()
+            .filter_map(|s| serde_json::from_value(s.clone()).ok())
+            .collect();
+        for other in &existing {
+            if other.ipv4_block.contains(ipv4_block.network())
+                || ipv4_block.contains(other.ipv4_block.network())
+            {
+                return Err(ApiError::invalid_request(&format!(
+                    "IPv4 block {} overlaps with that of VPC Subnet \"{}\"",
+                    ipv4_block, other.name
+                )));
+            }
+        }
+
+        let ipv6_block = match body.ipv6_block {
+            Some(block) if !block.is_vpc_subnet(&vpc_prefix) => {
+                return Err(ApiError::invalid_value(
+                    "ipv6_block",
+                    "VPC Subnet IPv6 address ranges must be a /64 within the VPC's prefix",
+                ));
+            }
+            Some(block) => {
+                if let Some(other) = existing.iter().find(|s| s.ipv6_block == block) {
+                    return Err(ApiError::invalid_request(&format!(
+                        "IPv6 block {} overlaps with that of VPC Subnet \"{}\"",
+                        block, other.name
+                    )));
+                }
+                block
+            }
+            None => (0..=u16::MAX)
+                .map(|i| {
+                    let mut segments = vpc_prefix.network().segments();
+                    segments[3] = i;
+                    format!("{}/64", std::net::Ipv6Addr::from(segments))
+                        .parse::<Ipv6Net>()
+                        .expect("valid network")
+                })
+                .find(|block| !existing.iter().any(|s| s.ipv6_block == *block))
+                .ok_or_else(|| ApiError::invalid_request("no IPv6 blocks left in the VPC"))?,
+        };
+
+        Ok(VpcSubnet {
+            id: new_id(),
+            name: body.name,
+            description: body.description,
+            ipv4_block,
+            ipv6_block,
+            time_created: now(),
+            time_modified: now(),
+            vpc_id: id_of(vpc),
+        })
+    }
+
+    /// Check a network interface can be created for an instance, and return
+    /// it if so. `existing` are the instance's other interfaces.
+    fn new_network_interface(
+        &self,
+        project: &[&str],
+        instance: &Value,
+        body: NetworkInterfaceCreate,
+        existing: &[Value],
+    ) -> Result<NetworkInterface, ApiError> {
+        check_name("name", &body.name)?;
+        if existing.iter().any(|n| n["name"] == body.name.as_str()) {
+            return Err(ApiError::already_exists("network-interface", &body.name));
+        }
+        if existing.len() >= MAX_NICS_PER_INSTANCE {
+            return Err(ApiError::invalid_request(&format!(
+                "instances may not have more than {} network interfaces",
+                MAX_NICS_PER_INSTANCE
+            )));
+        }
+        let vpc = self.require_in(project, "vpcs", &body.vpc_name)?;
+        let vpc_id = id_of(vpc);
+        if existing.iter().any(|n| n["vpc_id"] != vpc_id.as_str()) {
+            return Err(ApiError::invalid_request(
+                "an instance's network interfaces must all be in the same VPC",
+            ));
+        }
+        let subnet: VpcSubnet = serde_json::from_value(
+            self.require(
+                &[
+                    project,
+                    &["vpcs", &body.vpc_name, "subnets", &body.subnet_name],
+                ]
+                .concat(),
+            )?
+            .clone(),
+        )
+        .map_err(|e| ApiError::internal(&e.to_string()))?;
+
+        let mut used: Vec<Value> = self
+            .store
+            .all(&path(project), "network-interfaces")
+            .filter(|n| n["subnet_id"] == subnet.id.as_str())
+            .map(|n| n["ip"].clone())
+            .collect();
+        used.extend(existing.iter().map(|n| n["ip"].clone()));
+        let is_free = |ip: &std::net::Ipv4Addr| !used.contains(&json!(ip.to_string()));
+        let network = subnet.ipv4_block;
+        let first = u32::from(network.network()) + RESERVED_ADDRESSES;
+        let last = u32::from(network.broadcast());
+        let ip = match body.ip {
+            Some(IpAddr::V4(ip)) => {
+                let n = u32::from(ip);
+                if n < first || n >= last {
+                    return Err(ApiError::invalid_request(&format!(
+                        "IP address {} is not available in VPC Subnet \"{}\" ({})",
+                        ip, subnet.name, network
+                    )));
+                }
+                if !is_free(&ip) {
+                    return Err(ApiError::invalid_request(&format!(
+                        "IP address {} is already in use",
+                        ip
+                    )));
+                }
+                ip
+            }
+            Some(IpAddr::V6(ip)) => {
+                return Err(ApiError::invalid_value(
+                    "ip",
+                    &format!("IPv6 address {} is not supported", ip),
+                ));
+            }
+            None => (first..last)
+                .map(std::net::Ipv4Addr::from)
+                .find(is_free)
+                .ok_or_else(|| {
+                    ApiError::invalid_request(&format!(
+                        "no available IP addresses in VPC Subnet \"{}\"",
+                        subnet.name
+                    ))
+                })?,
+        };
+
+        let random = Uuid::new_v4();
+        let random = random.as_bytes();
+        Ok(NetworkInterface {
+            id: new_id(),
+            name: body.name,
+            description: body.description,
+            instance_id: id_of(instance),
+            ip: IpAddr::V4(ip),
+            // Oxide's range of MAC addresses for guests.
+            mac: MacAddr([
+                0xa8,
+                0x40,
+                0x25,
+                0xf0 | (random[0] & 0x07),
+                random[1],
+                random[2],
+            ]),
+            subnet_id: subnet.id,
+            time_created: now(),
+            time_modified: now(),
+            vpc_id,
+        })
+    }
+
+    fn update(&mut self, s: &[&str], body: &[u8]) -> Result<Value, ApiError> {
+        let collection = s[s.len() - 2];
+        let mut item = self.require(s)?.clone();
+        let (name, description, dns_name) = match collection {
+            "organizations" => {
+                let body: OrganizationUpdate = parse(body)?;
+                (body.name, body.description, String::new())
+            }
+            "projects" => {
+                let body: ProjectUpdate = parse(body)?;
+                (body.name, body.description, String::new())
+            }
+            "vpcs" => {
+                let body: VpcUpdate = parse(body)?;
+                if !body.dns_name.is_empty() {
+                    check_name("dns_name", &body.dns_name)?;
+                }
+                (body.name, body.description, body.dns_name)
+            }
+            "subnets" => {
+                let body: VpcSubnetUpdate = parse(body)?;
+                (body.name, body.description, String::new())
+            }
+            "routers" => {
+                let body: VpcRouterUpdate = parse(body)?;
+                (body.name, body.description, String::new())
+            }
+            _ => {
+                let body: RouterRouteUpdateParams = parse(body)?;
+                if item["kind"] != "custom" {
+                    return Err(ApiError::invalid_request(&format!(
+                        "routes of kind {} are not modifiable",
+                        item["kind"]
+                    )));
+                }
+                item["destination"] = to_value(&body.destination);
+                item["target"] = to_value(&body.target);
+                (body.name, body.description, String::new())
+            }
+        };
+
+        let collection_path = &s[..s.len() - 1];
+        let old_name = s[s.len() - 1];
+        if !name.is_empty() && name != old_name {
+            self.check_new_name(collection_path, &name)?;
+        }
+        if !description.is_empty() {
+            item["description"] = Value::String(description);
+        }
+        if !dns_name.is_empty() {
+            item["dns_name"] = Value::String(dns_name);
+        }
+        item["time_modified"] = to_value(&now());
+        self.store.insert(&path(collection_path), old_name, item);
+        if !name.is_empty() && name != old_name {
+            self.store.rename(&path(collection_path), old_name, &name);
+        }
+        Ok(self
+            .store
+            .get(
+                &path(collection_path),
+                if name.is_empty() { old_name } else { &name },
+            )
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn delete(&mut self, s: &[&str]) -> Result<(), ApiError> {
+        let item = self.require(s)?.clone();
+        let (collection, name) = (path(&s[..s.len() - 1]), s[s.len() - 1]);
+        let children = self.store.children(&collection, name);
+
+        match s[s.len() - 2] {
+            "organizations" | "projects" if !children.is_empty() => {
+                return Err(ApiError::invalid_request(&format!(
+                    "{} to be deleted contains one or more {}",
+                    kind(s[s.len() - 2]).unwrap_or_default(),
+                    children[0]
+                )));
+            }
+            "instances" => {
+                let state = item["run_state"].as_str().unwrap_or_default();
+                if !matches!(state, "stopped" | "failed" | "creating") {
+                    return Err(ApiError::invalid_request(&format!(
+                        "instance cannot be deleted in state \"{}\"",
+                        state
+                    )));
+                }
+                // Its disks are detached, and its network interfaces go with it.
+                let disks = path(&[&s[..4], &["disks"]].concat());
+                let names: Vec<String> = self
+                    .store
+                    .list(&disks)
+                    .into_iter()
+                    .filter(|d| d["state"]["instance"] == item["id"])
+                    .filter_map(|d| d["name"].as_str().map(str::to_string))
+                    .collect();
+                for name in names {
+                    if let Some(disk) = self.store.get_mut(&disks, &name) {
+                        disk["state"] = to_value(&DiskState::Detached);
+                    }
+                }
+            }
+            "disks" => {
+                let state = item["state"]["state"].as_str().unwrap_or_default();
+                if !matches!(state, "detached" | "faulted") {
+                    return Err(ApiError::invalid_request(&format!(
+                        "disk cannot be deleted in state \"{}\"",
+                        state
+                    )));
+                }
+                self.record_saga();
+            }
+            "vpcs" => {
+                if children.contains(&"subnets") {
+                    return Err(ApiError::invalid_request(
+                        "VPC cannot be deleted while VPC Subnets exist",
+                    ));
+                }
+                let routers = format!("{}/{}/routers", collection, name);
+                if self
+                    .store
+                    .list(&routers)
+                    .iter()
+                    .any(|r| r["kind"] != "system")
+                {
+                    return Err(ApiError::invalid_request(
+                        "VPC cannot be deleted while custom routers exist",
+                    ));
+                }
+            }
+            "subnets"
+                if self
+                    .store
+                    .all(&path(&s[..4]), "network-interfaces")
+                    .any(|n| n["subnet_id"] == item["id"]) =>
+            {
+                return Err(ApiError::invalid_request(
+                    "VPC Subnet cannot be deleted while network interfaces in the subnet exist",
+                ));
+            }
+            "routers" if item["kind"] == "system" => {
+                return Err(ApiError::invalid_request("cannot delete system router"));
+            }
+            "routes" if item["kind"] != "custom" => {
+                return Err(ApiError::invalid_request(&format!(
+                    "routes of kind {} are not modifiable",
+                    item["kind"]
+                )));
+            }
+            "network-interfaces" => {
+                let instance = self.require(&s[..6])?;
+                if instance["run_state"] != "stopped" {
+                    return Err(ApiError::invalid_request(
+                        "instance must be stopped to detach a network interface",
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        self.store.remove(&collection, name);
+        Ok(())
+    }
+
+    fn instance_action(
+        &mut self,
+        s: &[&str],
+        action: &str,
+        body: &[u8],
+    ) -> Result<Reply, ApiError> {
+        let instance = self.require(s)?;
+        let state = instance["run_state"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let target = match (action, state.as_str()) {
+            ("start", "running") | ("start", "stopped") => InstanceState::Running,
+            ("stop", "running") | ("stop", "stopped") => InstanceState::Stopped,
+            ("reboot", "running") => InstanceState::Running,
+            ("migrate", "running") => {
+                let body: InstanceMigrate = parse(body)?;
+                if Uuid::parse_str(&body.dst_sled_uuid).is_err() {
+                    return Err(ApiError::invalid_value("dst_sled_uuid", "must be a UUID"));
+                }
+                InstanceState::Running
+            }
+            _ => {
+                return Err(ApiError::invalid_request(&format!(
+                    "instance state cannot be changed from state \"{}\" by {}",
+                    state, action
+                )));
+            }
+        };
+
+        let (collection, name) = (path(&s[..s.len() - 1]), s[s.len() - 1]);
+        let instance = self
+            .store
+            .get_mut(&collection, name)
+            .expect("instance was found");
+        if action == "reboot" || instance["run_state"] != target.to_string().as_str() {
+            instance["run_state"] = to_value(&target);
+            instance["time_run_state_updated"] = to_value(&now());
+        }
+        Ok(Reply::Json(StatusCode::ACCEPTED, instance.clone()))
+    }
+
+    fn instance_disks(
+        &self,
+        s: &[&str],
+        query: &BTreeMap<String, String>,
+    ) -> Result<Reply, ApiError> {
+        let instance_id = self.require(s)?["id"].clone();
+        let items = self
+            .store
+            .list(&path(&[&s[..4], &["disks"]].concat()))
+            .into_iter()
+            .filter(|d| d["state"]["instance"] == instance_id)
+            .cloned()
+            .collect();
+        Ok(Reply::Json(StatusCode::OK, page(items, query)?))
+    }
+
+    fn attach_disk(&mut self, s: &[&str], attach: bool, body: &[u8]) -> Result<Reply, ApiError> {
+        let instance = self.require(s)?.clone();
+        let body: DiskIdentifier = parse(body)?;
+        let disks = path(&[&s[..4], &["disks"]].concat());
+        let disk = self.require_in(&s[..4], "disks", &body.name)?;
+        let attached_here = disk["state"]["instance"] == instance["id"];
+
+        let state = instance["run_state"].as_str().unwrap_or_default();
+        if !matches!(state, "stopped" | "creating") {
+            return Err(ApiError::invalid_request(&format!(
+                "cannot {} disk \"{}\": instance is {}",
+                if attach { "attach" } else { "detach" },
+                body.name,
+                state
+            )));
+        }
+        let new_state = if attach {
+            if !attached_here && disk["state"]["state"] != "detached" {
+                return Err(ApiError::invalid_request(&format!(
+                    "cannot attach disk \"{}\": disk is attached to another instance",
+                    body.name
+                )));
+            }
+            let count = self
+                .store
+                .list(&disks)
+                .iter()
+                .filter(|d| d["state"]["instance"] == instance["id"])
+                .count();
+            if !attached_here && count >= MAX_DISKS_PER_INSTANCE {
+                return Err(ApiError::invalid_request(&format!(
+                    "cannot attach more than {} disks to instance",
+                    MAX_DISKS_PER_INSTANCE
+                )));
+            }
+            DiskState::Attached(id_of(&instance))
+        } else {
+            if !attached_here {
+                return Err(ApiError::invalid_request(&format!(
+                    "disk \"{}\" is not attached to instance \"{}\"",
+                    body.name, instance["name"]
+                )));
+            }
+            DiskState::Detached
+        };
+
+        let disk = self
+            .store
+            .get_mut(&disks, &body.name)
+            .expect("disk was found");
+        disk["state"] = to_value(&new_state);
+        Ok(Reply::Json(StatusCode::ACCEPTED, disk.clone()))
+    }
+
+    fn subnet_network_interfaces(
+        &self,
+        s: &[&str],
+        query: &BTreeMap<String, String>,
+    ) -> Result<Reply, ApiError> {
+        let subnet_id = self.require(s)?["id"].clone();
+        let items = self
+            .store
+            .all(&path(&s[..4]), "network-interfaces")
+            .filter(|n| n["subnet_id"] == subnet_id)
+            .cloned()
+            .collect();
+        Ok(Reply::Json(StatusCode::OK, page(items, query)?))
+    }
+
+    fn firewall_rules(
+        &mut self,
+        method: &Method,
+        s: &[&str],
+        body: &[u8],
+    ) -> Result<Reply, ApiError> {
+        let vpc_id = id_of(self.require(s)?);
+        let rules_path = firewall_path(s);
+        match *method {
+            Method::GET => {}
+            Method::PUT => {
+                let body: VpcFirewallRuleUpdateParams = parse(body)?;
+                if body.rules.len() > MAX_FIREWALL_RULES {
+                    return Err(ApiError::invalid_value(
+                        "rules",
+                        &format!("max length is {}", MAX_FIREWALL_RULES),
+                    ));
+                }
+                let mut rules: Vec<VpcFirewallRule> = Vec::new();
+                for rule in body.rules {
+                    check_name("name", &rule.name)?;
+                    if rules.iter().any(|r| r.name == rule.name) {
+                        return Err(ApiError::invalid_value(
+                            "name",
+                            &format!("duplicate rule name \"{}\"", rule.name),
+                        ));
+                    }
+                    rules.push(VpcFirewallRule {
+                        id: new_id(),
+                        name: rule.name,
+                        description: rule.description,
+                        action: rule.action,
+                        direction: rule.direction,
+                        filters: rule.filters,
+                        priority: rule.priority,
+                        status: rule.status,
+                        targets: rule.targets,
+                        time_created: now(),
+                        time_modified: now(),
+                        vpc_id: vpc_id.to_string(),
+                    });
+                }
+                self.store.set_singleton(&rules_path, to_value(&rules));
+            }
+            _ => return Err(ApiError::method_not_allowed()),
+        }
+
+        let rules = self
+            .store
+            .singleton(&rules_path)
+            .cloned()
+            .unwrap_or_else(|| json!([]));
+        Ok(Reply::Json(StatusCode::OK, json!({ "rules": rules })))
+    }
+
+    /// Get or replace the policy of the item at `s`, or of the fleet if `s`
+    /// is empty.
+    fn policy<T>(&mut self, method: &Method, s: &[&str], body: &[u8]) -> Result<Reply, ApiError>
+    where
+        T: Serialize + DeserializeOwned + Default,
+    {
+        if !s.is_empty() {
+            self.require(s)?;
+        }
+        let policy_path = format!("{}/policy", path(s));
+        match *method {
+            Method::GET => {}
+            Method::PUT => {
+                let policy = to_value(&parse::<T>(body)?);
+                for assignment in policy["role_assignments"].as_array().into_iter().flatten() {
+                    let id = assignment["identity_id"].as_str().unwrap_or_default();
+                    if Uuid::parse_str(id).is_err() {
+                        return Err(ApiError::bad_body(&format!(
+                            "identity_id \"{}\" is not a UUID",
+                            id
+                        )));
+                    }
+                }
+                self.store.set_singleton(&policy_path, policy);
+            }
+            _ => return Err(ApiError::method_not_allowed()),
+        }
+
+        let policy = self
+            .store
+            .singleton(&policy_path)
+            .cloned()
+            .unwrap_or_else(|| to_value(&T::default()));
+        Ok(Reply::Json(StatusCode::OK, policy))
+    }
+}
+
+fn allow(method: &Method, allowed: &[Method]) -> Result<(), ApiError> {
+    if allowed.contains(method) {
+        Ok(())
+    } else {
+        Err(ApiError::method_not_allowed())
+    }
+}
+
+fn ok<T: Serialize>(value: &T) -> Reply {
+    Reply::Json(StatusCode::OK, to_value(value))
+}
+
+/// The kind of resource in a collection, as it is named in error messages.
+fn kind(collection: &str) -> Option<&'static str> {
+    Some(match collection {
+        "organizations" => "organization",
+        "projects" => "project",
+        "instances" => "instance",
+        "disks" => "disk",
+        "snapshots" => "snapshot",
+        "images" => "image",
+        "vpcs" => "vpc",
+        "subnets" => "vpc-subnet",
+        "routers" => "vpc-router",
+        "routes" => "router-route",
+        "network-interfaces" => "network-interface",
+        "silos" => "silo",
+        "sshkeys" => "ssh-key",
+        _ => return None,
+    })
+}
+
+fn path(s: &[&str]) -> String {
+    format!("/{}", s.join("/"))
+}
+
+fn firewall_path(vpc: &[&str]) -> String {
+    format!("{}/firewall/rules", path(vpc))
+}
+
+/// The path with every name replaced by `*`, to compare with `COLLECTIONS`.
+fn shape(s: &[&str]) -> String {
+    s.iter()
+        .enumerate()
+        .map(|(i, segment)| if i % 2 == 1 { "*" } else { segment })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn page(mut items: Vec<Value>, query: &BTreeMap<String, String>) -> Result<Value, ApiError> {
+    let limit = match query.get("limit") {
+        Some(limit) => limit
+            .parse::<usize>()
+            .ok()
+            .filter(|limit| *limit > 0)
+            .ok_or_else(|| {
+                ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    "",
+                    &format!("unable to parse query string: invalid limit \"{}\"", limit),
+                )
+            })?,
+        None => DEFAULT_LIMIT,
+    };
+    let (key, descending) = match query.get("sort_by").map(String::as_str) {
+        Some("id_ascending") => ("id", false),
+        Some("name_descending") => ("name", true),
+        _ => ("name", false),
+    };
+    let sort_key = |item: &Value| item[key].as_str().unwrap_or_default().to_string();
+    items.sort_by_key(sort_key);
+    if descending {
+        items.reverse();
+    }
+
+    // The page token is the sort key of the last item on the previous page.
+    // The client's own paging sends it as `page`.
+    if let Some(token) = query.get("page_token").or_else(|| query.get("page")) {
+        items.retain(|item| {
+            let k = sort_key(item);
+            if descending {
+                k < *token
+            } else {
+                k > *token
+            }
+        });
+    }
+    let next_page = if items.len() > limit {
+        items.truncate(limit);
+        items.last().map(|item| Value::String(sort_key(item)))
+    } else {
+        None
+    };
+
+    Ok(json!({
+        "items": items,
+        "next_page": next_page,
+    }))
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> Result<T, ApiError> {
+    serde_json::from_slice(body).map_err(|e| ApiError::bad_body(&e.to_string()))
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).expect("API types serialize to JSON")
+}
+
+fn id_of(item: &Value) -> String {
+    item["id"].as_str().unwrap_or_default().to_string()
+}
+
+fn new_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+fn now() -> DisplayOptionDateTime {
+    DisplayOptionDateTime(Some(Utc::now()))
+}
+
+/// Names must begin with a lower case ASCII letter, be composed exclusively
+/// of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end
+/// with a '-'.
+fn check_name(field: &str, name: &str) -> Result<(), ApiError> {
+    let problem = if name.is_empty() {
+        Some("name requires at least one character")
+    } else if name.len() > MAX_NAME_LENGTH {
+        Some("name may contain at most 63 characters")
+    } else if !name.starts_with(|c: char| c.is_ascii_lowercase()) {
+        Some("name must begin with an ASCII lowercase character")
+    } else if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        Some("name contains invalid character")
+    } else if name.ends_with('-') {
+        Some("name cannot end with \"-\"")
+    } else {
+        None
+    };
+    match problem {
+        Some(problem) => Err(ApiError::bad_body(&format!("{}: {}", field, problem))),
+        None => Ok(()),
+    }
+}
+
+fn check_block_size(block_size: i64) -> Result<u64, ApiError> {
+    match block_size {
+        512 | 2048 | 4096 => Ok(block_size as u64),
+        _ => Err(ApiError::invalid_value(
+            "block_size",
+            "must be 512, 2048, or 4096",
+        )),
+    }
+}
+
+fn is_ssh_public_key(key: &str) -> bool {
+    let mut parts = key.split_whitespace();
+    matches!(
+        (parts.next(), parts.next()),
+        (Some(kind), Some(data))
+            if (kind.starts_with("ssh-") || kind.starts_with("ecdsa-"))
+                && data
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '='))
+    )
+}
+
+/// A random /48 in the Unique Local Address range, `fd00::/8`.
+fn random_vpc_prefix() -> Ipv6Net {
+    let random = Uuid::new_v4();
+    let r = random.as_bytes();
+    format!(
+        "fd{:02x}:{:02x}{:02x}:{:02x}{:02x}::/48",
+        r[0], r[1], r[2], r[3], r[4]
+    )
+    .parse()
+    .expect("valid network")
+}
@@ -0,0 +1,122 @@
+//! Errors returned by the mock, in the shape the real API returns them.
+use std::fmt;
+
+use hyper::StatusCode;
+use oxide_api::types::ErrorResponse;
+
+/// An error response. The `error_code` is one of those the client maps to
+/// `oxide_api::types::Error`, or empty for errors from the HTTP layer, such
+/// as a body that cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub error_code: String,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, error_code: &str, message: &str) -> Self {
+        ApiError {
+            status,
+            error_code: error_code.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn not_found(kind: &str, name: &str) -> Self {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            "ObjectNotFound",
+            &format!("not found: {} with name \"{}\"", kind, name),
+        )
+    }
+
+    pub fn not_found_by_id(kind: &str, id: &str) -> Self {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            "ObjectNotFound",
+            &format!("not found: {} with id \"{}\"", kind, id),
+        )
+    }
+
+    pub fn already_exists(kind: &str, name: &str) -> Self {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "ObjectAlreadyExists",
+            &format!("already exists: {} \"{}\"", kind, name),
+        )
+    }
+
+    pub fn invalid_request(message: &str) -> Self {
+        ApiError::new(StatusCode::BAD_REQUEST, "InvalidRequest", message)
+    }
+
+    pub fn invalid_value(label: &str, message: &str) -> Self {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "InvalidValue",
+            &format!("unsupported value for \"{}\": {}", label, message),
+        )
+    }
+
+    /// A request body that could not be parsed.
+    pub fn bad_body(message: &str) -> Self {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "",
+            &format!("unable to parse body: {}", message),
+        )
+    }
+
+    pub fn unauthorized() -> Self {
+        ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized",
+            "credentials missing or invalid",
+        )
+    }
+
+    pub fn forbidden() -> Self {
+        ApiError::new(StatusCode::FORBIDDEN, "Forbidden", "Forbidden")
+    }
+
+    pub fn route_not_found() -> Self {
+        ApiError::new(StatusCode::NOT_FOUND, "", "Not Found")
+    }
+
+    pub fn method_not_allowed() -> Self {
+        ApiError::new(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "MethodNotAllowed",
+            "Method Not Allowed",
+        )
+    }
+
+    pub fn unavailable(message: &str) -> Self {
+        ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "ServiceUnavailable",
+            message,
+        )
+    }
+
+    pub fn internal(message: &str) -> Self {
+        ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Internal", message)
+    }
+
+    pub(crate) fn body(&self, request_id: &str) -> ErrorResponse {
+        ErrorResponse {
+            error_code: self.error_code.to_string(),
+            message: self.message.to_string(),
+            request_id: request_id.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.status, self.error_code, self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
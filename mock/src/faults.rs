@@ -0,0 +1,89 @@
+//! Faults injected into the mock's responses.
+use std::time::Duration;
+
+use hyper::{Method, StatusCode};
+
+use crate::ApiError;
+
+/// A fault to inject into the responses to matching requests.
+#[derive(Debug, Clone, Default)]
+pub struct Fault {
+    /// Only requests with this method are affected, or requests with any
+    /// method if unset.
+    pub method: Option<Method>,
+    /// Only requests whose path starts with this are affected.
+    pub path_prefix: String,
+    /// How long to wait before responding.
+    pub latency: Duration,
+    /// The error to respond with instead of handling the request. The request
+    /// is handled as normal, after any latency, if unset.
+    pub error: Option<ApiError>,
+    /// How many requests to affect before the fault is removed, or every one
+    /// if unset.
+    pub times: Option<usize>,
+}
+
+impl Fault {
+    /// Delay every response by `latency`.
+    pub fn latency(latency: Duration) -> Self {
+        Fault {
+            latency,
+            ..Default::default()
+        }
+    }
+
+    /// Respond with `error` instead of handling the request.
+    pub fn error(error: ApiError) -> Self {
+        Fault {
+            error: Some(error),
+            ..Default::default()
+        }
+    }
+
+    /// Respond with a 503, as the API does when a service it depends on is
+    /// unavailable.
+    pub fn unavailable() -> Self {
+        Fault::error(ApiError::unavailable("Service Unavailable"))
+    }
+
+    /// Respond with the given status and error code.
+    pub fn status(status: StatusCode, error_code: &str, message: &str) -> Self {
+        Fault::error(ApiError::new(status, error_code, message))
+    }
+
+    /// Only affect requests with `method`.
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Only affect requests whose path starts with `path_prefix`.
+    pub fn path(mut self, path_prefix: &str) -> Self {
+        self.path_prefix = path_prefix.to_string();
+        self
+    }
+
+    /// Only affect the next `times` matching requests.
+    pub fn times(mut self, times: usize) -> Self {
+        self.times = Some(times);
+        self
+    }
+
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        self.method.as_ref().is_none_or(|m| m == method) && path.starts_with(&self.path_prefix)
+    }
+}
+
+/// Take the first fault matching a request, counting it against the fault's
+/// `times` and removing the fault once it has been used up.
+pub(crate) fn take(faults: &mut Vec<Fault>, method: &Method, path: &str) -> Option<Fault> {
+    let index = faults.iter().position(|f| f.matches(method, path))?;
+    let fault = faults[index].clone();
+    if let Some(times) = &mut faults[index].times {
+        *times = times.saturating_sub(1);
+        if *times == 0 {
+            faults.remove(index);
+        }
+    }
+    Some(fault)
+}
@@ -0,0 +1,237 @@
+//! An in-memory mock of the Oxide API, for testing code that uses
+//! `oxide-api` without a rack.
+//!
+//! [`MockServer::start`] serves the API on a local port, so a client only
+//! needs to be pointed at it:
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! let mock = oxide_api_mock::MockServer::start().await?;
+//! let client = oxide_api::Client::new("token", mock.url());
+//! let organizations = client
+//!     .organizations()
+//!     .get_all(oxide_api::types::NameOrIdSortMode::NameAscending)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Every request must carry a bearer token or a session cookie from
+//! `/login`, but any token and any username is accepted. All requests are
+//! made as the same user.
+
+mod api;
+mod error;
+mod faults;
+mod store;
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use hyper::{
+    header,
+    service::{make_service_fn, service_fn},
+    Body, Method, Response, Server, StatusCode,
+};
+use oxide_api::types::LoginParams;
+use tokio::sync::oneshot;
+
+use crate::api::{Api, Reply, Request};
+pub use crate::{error::ApiError, faults::Fault};
+
+/// The name of the cookie `/login` sets.
+const SESSION_COOKIE: &str = "session";
+
+#[derive(Default)]
+struct State {
+    api: Api,
+    faults: Vec<Fault>,
+    /// The ids of the sessions that have logged in and not out.
+    sessions: BTreeSet<String>,
+}
+
+/// A mock API server, which runs until it is dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Start a server with no resources on a free local port. This must be
+    /// called from within a Tokio runtime, which the server runs on.
+    pub async fn start() -> Result<Self> {
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| handle(state.clone(), req))) }
+        });
+        let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))?.serve(make_service);
+        let addr = server.local_addr();
+
+        let (shutdown, stop) = oneshot::channel::<()>();
+        tokio::spawn(server.with_graceful_shutdown(async {
+            stop.await.ok();
+        }));
+
+        Ok(MockServer {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// The URL to create a client with.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Inject a fault into the responses to matching requests. Faults are
+    /// checked in the order they were injected, and only the first that
+    /// matches a request applies to it.
+    pub fn inject(&self, fault: Fault) {
+        self.state.lock().unwrap().faults.push(fault);
+    }
+
+    /// Remove every injected fault.
+    pub fn clear_faults(&self) {
+        self.state.lock().unwrap().faults.clear();
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<State>>,
+    req: hyper::Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let (parts, body) = req.into_parts();
+    let path = parts.uri.path().to_string();
+
+    let fault = faults::take(&mut state.lock().unwrap().faults, &parts.method, &path);
+    if let Some(fault) = &fault {
+        if !fault.latency.is_zero() {
+            tokio::time::sleep(fault.latency).await;
+        }
+    }
+
+    // Logging in and out change the session rather than the API, so they are
+    // handled here.
+    let mut set_cookie = None;
+    let result = match fault.and_then(|f| f.error) {
+        Some(error) => Err(error),
+        None => match hyper::body::to_bytes(body).await {
+            Ok(body) if parts.method == Method::POST && path == "/login" => {
+                serde_json::from_slice::<LoginParams>(&body)
+                    .map_err(|e| ApiError::bad_body(&e.to_string()))
+                    .and_then(|params| {
+                        if params.username.is_empty() {
+                            return Err(ApiError::unauthorized());
+                        }
+                        let session = uuid::Uuid::new_v4().to_string();
+                        state.lock().unwrap().sessions.insert(session.clone());
+                        set_cookie = Some(format!(
+                            "{}={}; Path=/; HttpOnly; SameSite=Lax",
+                            SESSION_COOKIE, session
+                        ));
+                        Ok(Reply::NoContent)
+                    })
+            }
+            Ok(_) if parts.method == Method::POST && path == "/logout" => {
+                if let Some(session) = session_cookie(&parts.headers) {
+                    state.lock().unwrap().sessions.remove(&session);
+                }
+                set_cookie = Some(format!("{}=\"\"; Path=/; Max-Age=0", SESSION_COOKIE));
+                Ok(Reply::NoContent)
+            }
+            Ok(body) => {
+                let authenticated = authenticate(&parts.headers, &state.lock().unwrap().sessions);
+                authenticated.and_then(|()| {
+                    let query: BTreeMap<String, String> = serde_urlencoded::from_str(
+                        parts.uri.query().unwrap_or_default(),
+                    )
+                    .map_err(|e| {
+                        ApiError::new(
+                            StatusCode::BAD_REQUEST,
+                            "",
+                            &format!("unable to parse query string: {}", e),
+                        )
+                    })?;
+                    state.lock().unwrap().api.handle(&Request {
+                        method: parts.method.clone(),
+                        path,
+                        query,
+                        body: body.to_vec(),
+                    })
+                })
+            }
+            Err(e) => Err(ApiError::bad_body(&e.to_string())),
+        },
+    };
+
+    let (status, body) = match result {
+        Ok(Reply::Json(status, value)) => (status, Body::from(value.to_string())),
+        Ok(Reply::NoContent) => (StatusCode::NO_CONTENT, Body::empty()),
+        Err(error) => (
+            error.status,
+            Body::from(serde_json::to_string(&error.body(&request_id)).unwrap_or_default()),
+        ),
+    };
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    if let Ok(value) = header::HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert("x-request-id", value);
+    }
+    if let Some(value) = set_cookie.and_then(|c| header::HeaderValue::from_str(&c).ok()) {
+        response.headers_mut().insert(header::SET_COOKIE, value);
+    }
+    Ok(response)
+}
+
+/// Any bearer token is accepted, but there must be one, or else the cookie
+/// of a session that is still logged in.
+fn authenticate(headers: &hyper::HeaderMap, sessions: &BTreeSet<String>) -> Result<(), ApiError> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    let logged_in = session_cookie(headers).is_some_and(|session| sessions.contains(&session));
+    if token.trim().is_empty() && !logged_in {
+        Err(ApiError::unauthorized())
+    } else {
+        Ok(())
+    }
+}
+
+/// The session id in the `Cookie` header, if there is one.
+fn session_cookie(headers: &hyper::HeaderMap) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, value)| value.to_string())
+}
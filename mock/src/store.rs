@@ -0,0 +1,129 @@
+//! The mock's in-memory state.
+//!
+//! Resources are kept as JSON, in collections keyed by the path they are
+//! listed at, e.g. `/organizations/acme/projects`. Within a collection they
+//! are keyed by name, so listing them in name order is free. Resources that
+//! are not in a collection, such as policies, are kept by their own path.
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+#[derive(Debug, Default)]
+pub(crate) struct Store {
+    collections: BTreeMap<String, BTreeMap<String, Value>>,
+    singletons: BTreeMap<String, Value>,
+}
+
+impl Store {
+    pub(crate) fn list(&self, collection: &str) -> Vec<&Value> {
+        self.collections
+            .get(collection)
+            .map(|items| items.values().collect())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn get(&self, collection: &str, name: &str) -> Option<&Value> {
+        self.collections.get(collection)?.get(name)
+    }
+
+    pub(crate) fn get_mut(&mut self, collection: &str, name: &str) -> Option<&mut Value> {
+        self.collections.get_mut(collection)?.get_mut(name)
+    }
+
+    pub(crate) fn insert(&mut self, collection: &str, name: &str, value: Value) {
+        self.collections
+            .entry(collection.to_string())
+            .or_default()
+            .insert(name.to_string(), value);
+    }
+
+    /// Remove an item along with everything beneath it.
+    pub(crate) fn remove(&mut self, collection: &str, name: &str) -> Option<Value> {
+        let value = self.collections.get_mut(collection)?.remove(name)?;
+        let prefix = format!("{}/{}/", collection, name);
+        self.collections
+            .retain(|path, _| !path.starts_with(&prefix));
+        self.singletons.retain(|path, _| !path.starts_with(&prefix));
+        Some(value)
+    }
+
+    /// Rename an item, moving everything beneath it to its new path.
+    pub(crate) fn rename(&mut self, collection: &str, name: &str, new_name: &str) {
+        let items = match self.collections.get_mut(collection) {
+            Some(items) => items,
+            None => return,
+        };
+        let mut value = match items.remove(name) {
+            Some(value) => value,
+            None => return,
+        };
+        value["name"] = Value::String(new_name.to_string());
+        items.insert(new_name.to_string(), value);
+
+        let old = format!("{}/{}/", collection, name);
+        let new = format!("{}/{}/", collection, new_name);
+        let moved: Vec<String> = self
+            .collections
+            .keys()
+            .filter(|path| path.starts_with(&old))
+            .cloned()
+            .collect();
+        for path in moved {
+            if let Some(items) = self.collections.remove(&path) {
+                self.collections.insert(path.replacen(&old, &new, 1), items);
+            }
+        }
+        let moved: Vec<String> = self
+            .singletons
+            .keys()
+            .filter(|path| path.starts_with(&old))
+            .cloned()
+            .collect();
+        for path in moved {
+            if let Some(value) = self.singletons.remove(&path) {
+                self.singletons.insert(path.replacen(&old, &new, 1), value);
+            }
+        }
+    }
+
+    /// The non-empty collections directly beneath an item, by the last
+    /// segment of their path.
+    pub(crate) fn children(&self, collection: &str, name: &str) -> Vec<&str> {
+        let prefix = format!("{}/{}/", collection, name);
+        self.collections
+            .iter()
+            .filter(|(path, items)| !items.is_empty() && path.starts_with(&prefix))
+            .filter_map(|(path, _)| {
+                let rest = &path[prefix.len()..];
+                if rest.contains('/') {
+                    None
+                } else {
+                    Some(rest)
+                }
+            })
+            .collect()
+    }
+
+    /// The items in every collection whose path starts with `prefix` and
+    /// ends with `/kind`.
+    pub(crate) fn all(&self, prefix: &str, kind: &str) -> impl Iterator<Item = &Value> {
+        let prefix = prefix.to_string();
+        let suffix = format!("/{}", kind);
+        self.collections
+            .iter()
+            .filter(move |(path, _)| path.starts_with(&prefix) && path.ends_with(&suffix))
+            .flat_map(|(_, items)| items.values())
+    }
+
+    pub(crate) fn find_by_id(&self, prefix: &str, kind: &str, id: &str) -> Option<&Value> {
+        self.all(prefix, kind).find(|item| item["id"] == id)
+    }
+
+    pub(crate) fn singleton(&self, path: &str) -> Option<&Value> {
+        self.singletons.get(path)
+    }
+
+    pub(crate) fn set_singleton(&mut self, path: &str, value: Value) {
+        self.singletons.insert(path.to_string(), value);
+    }
+}
@@ -0,0 +1,896 @@
+use std::time::{Duration, Instant};
+
+use oxide_api::{
+    types::{
+        Error, InstanceCreate, InstanceState, NameOrIdSortMode, NameSortMode, OrganizationCreate,
+        ProjectCreate, ProjectUpdate, VpcCreate, VpcRouterKind,
+    },
+    Client,
+};
+use pretty_assertions::assert_eq;
+
+use crate::{Fault, MockServer};
+
+fn api_error(e: anyhow::Error) -> Error {
+    e.downcast::<Error>().expect("an API error")
+}
+
+async fn project(client: &Client) {
+    client
+        .organizations()
+        .post(&OrganizationCreate {
+            name: "acme".to_string(),
+            description: "".to_string(),
+        })
+        .await
+        .unwrap();
+    client
+        .projects()
+        .post(
+            "acme",
+            &ProjectCreate {
+                name: "web".to_string(),
+                description: "".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+}
+
+fn instance(name: &str) -> InstanceCreate {
+    InstanceCreate {
+        name: name.to_string(),
+        description: "".to_string(),
+        disks: Vec::new(),
+        hostname: name.to_string(),
+        memory: 1 << 30,
+        ncpus: 1,
+        network_interfaces: None,
+        user_data: "".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_mock_resources() {
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+    project(&client).await;
+
+    let e = client
+        .projects()
+        .post(
+            "acme",
+            &ProjectCreate {
+                name: "web".to_string(),
+                description: "".to_string(),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(oxide_api::utils::is_already_exists(&e));
+    let e = client.projects().get("acme", "api").await.unwrap_err();
+    assert_eq!(
+        api_error(e),
+        Error::ObjectNotFound {
+            message: "not found: project with name \"api\"".to_string()
+        }
+    );
+    let e = client
+        .instances()
+        .post("acme", "web", &instance("Bad_name"))
+        .await
+        .unwrap_err();
+    assert!(e.to_string().contains("unable to parse body"));
+
+    // Listing pages through everything, in order.
+    for name in ["c", "a", "b"] {
+        client
+            .instances()
+            .post("acme", "web", &instance(name))
+            .await
+            .unwrap();
+    }
+    let page = client
+        .instances()
+        .get_page(2, "acme", "", "web", NameSortMode::NameAscending)
+        .await
+        .unwrap();
+    assert_eq!(page.len(), 2);
+    let names: Vec<String> = client
+        .instances()
+        .get_all("acme", "web", NameSortMode::NameAscending)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|i| i.name)
+        .collect();
+    assert_eq!(names, vec!["a", "b", "c"]);
+
+    // Instances must be stopped to be deleted, and projects emptied.
+    let e = client
+        .instances()
+        .delete("a", "acme", "web")
+        .await
+        .unwrap_err();
+    assert!(matches!(api_error(e), Error::InvalidRequest { .. }));
+    let stopped = client.instances().stop("a", "acme", "web").await.unwrap();
+    assert_eq!(stopped.run_state, InstanceState::Stopped);
+    client.instances().delete("a", "acme", "web").await.unwrap();
+    let e = client.projects().delete("acme", "web").await.unwrap_err();
+    assert!(matches!(api_error(e), Error::InvalidRequest { .. }));
+
+    // Renaming a project moves what is in it.
+    client
+        .projects()
+        .put(
+            "acme",
+            "web",
+            &ProjectUpdate {
+                name: "www".to_string(),
+                description: "".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    let instance = client.instances().get("b", "acme", "www").await.unwrap();
+    assert_eq!(instance.run_state, InstanceState::Running);
+    assert_eq!(
+        client
+            .organizations()
+            .get_all(NameOrIdSortMode::IdAscending)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_mock_vpc() {
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+    project(&client).await;
+
+    let vpc = client
+        .vpcs()
+        .post(
+            "acme",
+            "web",
+            &VpcCreate {
+                name: "default".to_string(),
+                description: "".to_string(),
+                dns_name: "default".to_string(),
+                ipv6_prefix: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(vpc.ipv6_prefix.is_vpc_prefix());
+
+    let routers = client
+        .routers()
+        .get_all("acme", "web", NameSortMode::NameAscending, "default")
+        .await
+        .unwrap();
+    assert_eq!(routers.len(), 1);
+    assert_eq!(routers[0].kind, VpcRouterKind::System);
+    assert_eq!(routers[0].id, vpc.system_router_id);
+    let subnets = client
+        .subnets()
+        .get_all("acme", "web", NameSortMode::NameAscending, "default")
+        .await
+        .unwrap();
+    assert_eq!(subnets.len(), 1);
+    assert!(subnets[0].ipv6_block.is_vpc_subnet(&vpc.ipv6_prefix));
+    let rules = client
+        .firewall()
+        .rules_get("acme", "web", "default")
+        .await
+        .unwrap();
+    assert_eq!(rules.rules.len(), 4);
+
+    // New instances get an interface in the default subnet.
+    client
+        .instances()
+        .post("acme", "web", &instance("db"))
+        .await
+        .unwrap();
+    let interfaces = client
+        .subnets()
+        .network_interfaces_get_all(
+            "acme",
+            "web",
+            NameSortMode::NameAscending,
+            "default",
+            "default",
+        )
+        .await
+        .unwrap();
+    assert_eq!(interfaces.len(), 1);
+    assert_eq!(interfaces[0].ip.to_string(), "172.30.0.5");
+
+    let e = client
+        .routers()
+        .delete("acme", "web", "system", "default")
+        .await
+        .unwrap_err();
+    assert!(matches!(api_error(e), Error::InvalidRequest { .. }));
+    let e = client
+        .subnets()
+        .delete("acme", "web", "default", "default")
+        .await
+        .unwrap_err();
+    assert!(matches!(api_error(e), Error::InvalidRequest { .. }));
+}
+
+#[tokio::test]
+async fn test_mock_faults() {
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+
+    let e = Client::new("", mock.url())
+        .organizations()
+        .get_all(NameOrIdSortMode::NameAscending)
+        .await
+        .unwrap_err();
+    assert!(matches!(api_error(e), Error::Unauthenticated { .. }));
+
+    mock.inject(Fault::unavailable().path("/organizations").times(1));
+    let e = client
+        .organizations()
+        .get_all(NameOrIdSortMode::NameAscending)
+        .await
+        .unwrap_err();
+    assert!(api_error(e).retryable());
+    assert!(client
+        .organizations()
+        .get_all(NameOrIdSortMode::NameAscending)
+        .await
+        .unwrap()
+        .is_empty());
+
+    mock.inject(Fault::latency(Duration::from_millis(200)).method(hyper::Method::GET));
+    let start = Instant::now();
+    client
+        .organizations()
+        .get_all(NameOrIdSortMode::NameAscending)
+        .await
+        .unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(200));
+
+    mock.clear_faults();
+    mock.inject(Fault::status(
+        hyper::StatusCode::FORBIDDEN,
+        "Forbidden",
+        "Forbidden",
+    ));
+    let e = client.organizations().get("acme").await.unwrap_err();
+    assert_eq!(api_error(e), Error::Forbidden);
+}
+
+#[tokio::test]
+async fn test_mock_cassette() {
+    use oxide_api::cassette::{Cassette, REDACTED};
+
+    let path = std::env::temp_dir().join(format!("cassette-{}.json", uuid::Uuid::new_v4()));
+
+    let mock = MockServer::start().await.unwrap();
+    let url = mock.url();
+    let client = Client::new("secret-token", &url).with_cassette(Cassette::record(&path));
+    project(&client).await;
+    let recorded = client.projects().get("acme", "web").await.unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(!contents.contains("secret-token"));
+    let tape = Cassette::replay(&path).unwrap().tape();
+    assert_eq!(tape.interactions.len(), 3);
+    assert_eq!(
+        tape.interactions[2].request.to_string(),
+        "GET /organizations/acme/projects/web"
+    );
+    assert_eq!(
+        tape.interactions[2].request.headers["authorization"],
+        vec![REDACTED]
+    );
+
+    // With the server gone, the same calls are answered from the cassette.
+    drop(mock);
+    let client = Client::new("other-token", &url).with_cassette(Cassette::replay(&path).unwrap());
+    project(&client).await;
+    assert_eq!(
+        client.projects().get("acme", "web").await.unwrap(),
+        recorded
+    );
+    assert!(client.projects().get("acme", "api").await.is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_mock_session() {
+    use oxide_api::cookies::Session;
+
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("", mock.url());
+
+    let e = client.hidden().session_me().await.unwrap_err();
+    assert!(matches!(api_error(e), Error::Unauthenticated { .. }));
+
+    client.login("privileged").await.unwrap();
+    let me = client.hidden().session_me().await.unwrap();
+    assert_eq!(client.session().cookies.len(), 1);
+
+    // The session works in a client that did not log in itself.
+    let path = std::env::temp_dir().join(format!("session-{}.json", uuid::Uuid::new_v4()));
+    client.session().save(&path).unwrap();
+    let other = Client::new("", mock.url()).with_session(Session::load(&path).unwrap());
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(other.hidden().session_me().await.unwrap(), me);
+
+    client.logout().await.unwrap();
+    assert!(client.session().is_empty());
+    let e = other.hidden().session_me().await.unwrap_err();
+    assert!(matches!(api_error(e), Error::Unauthenticated { .. }));
+}
+
+#[tokio::test]
+async fn test_mock_declarative_apply() {
+    use oxide_api::{
+        declarative::{DiskSpec, Manifest, OrganizationSpec, ProjectSpec},
+        types::DiskSource,
+    };
+
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+    project(&client).await;
+
+    let manifest = |disks: Vec<DiskSpec>| Manifest {
+        organizations: vec![OrganizationSpec {
+            name: "acme".to_string(),
+            description: "".to_string(),
+            projects: vec![ProjectSpec {
+                name: "web".to_string(),
+                disks,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+
+    let desired = manifest(vec![DiskSpec {
+        name: "data".to_string(),
+        description: "".to_string(),
+        size: 1 << 30,
+        disk_source: DiskSource::Blank { block_size: 512 },
+        source_name: None,
+    }]);
+    let report = desired.plan(&client).await.unwrap().apply(&client).await;
+    assert!(report.is_success(), "{}", report.to_table());
+    assert!(desired.plan(&client).await.unwrap().is_empty());
+
+    // A disk that is no longer declared is deleted.
+    let desired = manifest(Vec::new());
+    let report = desired.plan(&client).await.unwrap().apply(&client).await;
+    assert!(report.is_success(), "{}", report.to_table());
+    let disks = client
+        .disks()
+        .get_all("acme", "web", NameSortMode::NameAscending)
+        .await
+        .unwrap();
+    assert!(disks.is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_declarative_vpc() {
+    use oxide_api::declarative::{Manifest, OrganizationSpec, ProjectSpec, SubnetSpec, VpcSpec};
+
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+    project(&client).await;
+
+    let desired = Manifest {
+        organizations: vec![OrganizationSpec {
+            name: "acme".to_string(),
+            description: "".to_string(),
+            projects: vec![ProjectSpec {
+                name: "web".to_string(),
+                vpcs: vec![VpcSpec {
+                    name: "main".to_string(),
+                    dns_name: "main".to_string(),
+                    subnets: vec![SubnetSpec {
+                        name: "app".to_string(),
+                        description: "".to_string(),
+                        ipv4_block: "10.1.0.0/24".parse().unwrap(),
+                        ipv6_block: None,
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+    let report = desired.plan(&client).await.unwrap().apply(&client).await;
+    assert!(report.is_success(), "{}", report.to_table());
+
+    // Applying again leaves the subnet and firewall rules that came with the
+    // VPC alone.
+    let plan = desired.plan(&client).await.unwrap();
+    assert!(plan.is_empty(), "{}", plan.to_json().unwrap());
+    let subnets: Vec<String> = client
+        .subnets()
+        .get_all("acme", "web", NameSortMode::NameAscending, "main")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(subnets, vec!["app", "default"]);
+}
+
+#[tokio::test]
+async fn test_mock_project_export() {
+    use oxide_api::types::{DiskCreate, DiskSource, SnapshotCreate};
+
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+    project(&client).await;
+    client
+        .disks()
+        .post(
+            "acme",
+            "web",
+            &DiskCreate {
+                name: "data".to_string(),
+                description: "".to_string(),
+                disk_source: DiskSource::Blank { block_size: 512 },
+                size: 1 << 30,
+            },
+        )
+        .await
+        .unwrap();
+
+    // Applying the export as another project leaves the original alone.
+    let mut manifest = client.projects().export("acme", "web").await.unwrap();
+    manifest.organizations[0].projects[0].name = "copy".to_string();
+    let plan = manifest.plan(&client).await.unwrap();
+    assert!(plan
+        .changes
+        .iter()
+        .all(|c| c.action == oxide_api::declarative::Action::Create));
+    let report = plan.apply(&client).await;
+    assert!(report.is_success(), "{}", report.to_table());
+    let projects: Vec<String> = client
+        .projects()
+        .get_all("acme", NameOrIdSortMode::NameAscending)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|p| p.name)
+        .collect();
+    assert_eq!(projects, vec!["copy", "web"]);
+
+    let snapshot = client
+        .snapshots()
+        .post(
+            "acme",
+            "web",
+            &SnapshotCreate {
+                name: "nightly".to_string(),
+                description: "".to_string(),
+                disk: "data".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    client
+        .disks()
+        .post(
+            "acme",
+            "web",
+            &DiskCreate {
+                name: "restored".to_string(),
+                description: "".to_string(),
+                disk_source: DiskSource::Snapshot {
+                    snapshot_id: snapshot.id.to_string(),
+                },
+                size: 1 << 30,
+            },
+        )
+        .await
+        .unwrap();
+
+    // Disks restored from a snapshot are exported with the snapshot's name,
+    // which is resolved again in the project the manifest is applied to.
+    let mut manifest = client.projects().export("acme", "web").await.unwrap();
+    let spec = &mut manifest.organizations[0].projects[0];
+    let restored = spec.disks.iter().find(|d| d.name == "restored").unwrap();
+    assert_eq!(
+        restored.disk_source,
+        DiskSource::Snapshot {
+            snapshot_id: snapshot.id
+        }
+    );
+    assert_eq!(restored.source_name.as_deref(), Some("nightly"));
+    spec.name = "restore".to_string();
+    let report = manifest.plan(&client).await.unwrap().apply(&client).await;
+    assert!(report.is_success(), "{}", report.to_table());
+    let nightly = client
+        .snapshots()
+        .get("acme", "restore", "nightly")
+        .await
+        .unwrap();
+    let restored = client
+        .disks()
+        .get("restored", "acme", "restore")
+        .await
+        .unwrap();
+    assert_eq!(restored.snapshot_id, nightly.id);
+}
+
+#[tokio::test]
+async fn test_mock_teardown() {
+    use oxide_api::{
+        teardown::TeardownOptions,
+        types::{DiskCreate, DiskSource},
+    };
+
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+    project(&client).await;
+    client
+        .vpcs()
+        .post(
+            "acme",
+            "web",
+            &VpcCreate {
+                name: "main".to_string(),
+                description: "".to_string(),
+                dns_name: "main".to_string(),
+                ipv6_prefix: None,
+            },
+        )
+        .await
+        .unwrap();
+    client
+        .instances()
+        .post("acme", "web", &instance("web-1"))
+        .await
+        .unwrap();
+    client
+        .disks()
+        .post(
+            "acme",
+            "web",
+            &DiskCreate {
+                name: "data".to_string(),
+                description: "".to_string(),
+                disk_source: DiskSource::Blank { block_size: 512 },
+                size: 1 << 30,
+            },
+        )
+        .await
+        .unwrap();
+
+    let report = client
+        .projects()
+        .teardown("acme", "web", &TeardownOptions::default())
+        .await
+        .unwrap();
+    assert!(report.is_complete(), "{}", report.to_table());
+    let e = client.projects().get("acme", "web").await.unwrap_err();
+    assert!(matches!(api_error(e), Error::ObjectNotFound { .. }));
+}
+
+#[tokio::test]
+async fn test_mock_clone_project() {
+    use oxide_api::{
+        clone::CloneOptions,
+        types::{DiskCreate, DiskSource, VpcSubnetCreate},
+    };
+
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+    project(&client).await;
+    client
+        .vpcs()
+        .post(
+            "acme",
+            "web",
+            &VpcCreate {
+                name: "main".to_string(),
+                description: "".to_string(),
+                dns_name: "main".to_string(),
+                ipv6_prefix: None,
+            },
+        )
+        .await
+        .unwrap();
+    client
+        .subnets()
+        .post(
+            "acme",
+            "web",
+            "main",
+            &VpcSubnetCreate {
+                name: "app".to_string(),
+                description: "".to_string(),
+                ipv4_block: "10.1.0.0/24".parse().unwrap(),
+                ipv6_block: None,
+            },
+        )
+        .await
+        .unwrap();
+    client
+        .disks()
+        .post(
+            "acme",
+            "web",
+            &DiskCreate {
+                name: "data".to_string(),
+                description: "".to_string(),
+                disk_source: DiskSource::Blank { block_size: 512 },
+                size: 1 << 30,
+            },
+        )
+        .await
+        .unwrap();
+
+    // The clone's VPC comes with its own default subnet and firewall rules,
+    // which must not be created a second time.
+    let report = client
+        .projects()
+        .clone_project("acme", "web", "acme", "copy", &CloneOptions::default())
+        .await
+        .unwrap();
+    assert!(report.is_success(), "{}", report.to_table());
+    let subnets: Vec<String> = client
+        .subnets()
+        .get_all("acme", "copy", NameSortMode::NameAscending, "main")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(subnets, vec!["app", "default"]);
+    let rules = client
+        .firewall()
+        .rules_get("acme", "copy", "main")
+        .await
+        .unwrap();
+    assert_eq!(rules.rules.len(), 4);
+    let disk = client.disks().get("data", "acme", "copy").await.unwrap();
+    assert_eq!(disk.size, 1 << 30);
+}
+
+#[tokio::test]
+async fn test_mock_orphans() {
+    use oxide_api::{
+        orphans::{FindingKind, OrphanOptions, OrphanReport},
+        types::{DiskCreate, DiskSource},
+    };
+
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+    project(&client).await;
+    client
+        .instances()
+        .post("acme", "web", &instance("web-1"))
+        .await
+        .unwrap();
+    client
+        .disks()
+        .post(
+            "acme",
+            "web",
+            &DiskCreate {
+                name: "data".to_string(),
+                description: "".to_string(),
+                disk_source: DiskSource::Blank { block_size: 512 },
+                size: 1 << 30,
+            },
+        )
+        .await
+        .unwrap();
+
+    let options = OrphanOptions {
+        detached_disk_age: Duration::ZERO,
+        stopped_instance_age: Duration::ZERO,
+        min_age: Duration::ZERO,
+    };
+    let report = OrphanReport::scan(&client, &options).await.unwrap();
+    assert_eq!(report.findings.len(), 1, "{}", report.to_table());
+    assert_eq!(report.findings[0].kind, FindingKind::DetachedDisk);
+
+    let remediation = report.remediate(&client, |_| true).await;
+    assert!(remediation.is_success(), "{}", remediation.to_table());
+    let disks = client
+        .disks()
+        .get_all("acme", "web", NameSortMode::NameAscending)
+        .await
+        .unwrap();
+    assert!(disks.is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_resource_index() {
+    use oxide_api::resolver::{ResourceIndex, ResourcePath};
+
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+    project(&client).await;
+    let mut index = ResourceIndex::build(&client).await.unwrap();
+    let built = index.refreshed().unwrap();
+
+    let organization = client
+        .organizations()
+        .post(&OrganizationCreate {
+            name: "later".to_string(),
+            description: "".to_string(),
+        })
+        .await
+        .unwrap();
+
+    // An unknown id does not refresh the index again straight away.
+    let resolved = index
+        .resolve_or_refresh(&client, &organization.id)
+        .await
+        .unwrap();
+    assert_eq!(resolved, None);
+    assert_eq!(index.refreshed(), Some(built));
+
+    let mut index = index.with_min_refresh_interval(chrono::Duration::zero());
+    let resolved = index
+        .resolve_or_refresh(&client, &organization.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        resolved,
+        Some(&ResourcePath::Organization {
+            organization: "later".to_string()
+        })
+    );
+    assert!(index.refreshed().unwrap() > built);
+}
+
+#[tokio::test]
+async fn test_mock_search() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use futures::StreamExt;
+    use hyper::{body::Bytes, Request, Response};
+    use oxide_api::{
+        declarative::ResourceKind,
+        middleware::{Middleware, Next},
+        search::{search, Query, SearchOptions},
+        transport::BoxFuture,
+        types::{DiskCreate, DiskSource},
+    };
+
+    /// Tracks the most requests in flight at once.
+    #[derive(Default)]
+    struct InFlight {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    impl Middleware for InFlight {
+        fn handle<'a>(
+            &'a self,
+            request: Request<Bytes>,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, anyhow::Result<Response<Bytes>>> {
+            Box::pin(async move {
+                let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max.fetch_max(current, Ordering::SeqCst);
+                let response = next.run(request).await;
+                self.current.fetch_sub(1, Ordering::SeqCst);
+                response
+            })
+        }
+    }
+
+    let mock = MockServer::start().await.unwrap();
+    let client = Client::new("token", mock.url());
+    project(&client).await;
+    client
+        .instances()
+        .post("acme", "web", &instance("web-1"))
+        .await
+        .unwrap();
+    client
+        .organizations()
+        .post(&OrganizationCreate {
+            name: "globex".to_string(),
+            description: "".to_string(),
+        })
+        .await
+        .unwrap();
+    for name in ["api", "website"] {
+        client
+            .projects()
+            .post(
+                "globex",
+                &ProjectCreate {
+                    name: name.to_string(),
+                    description: "".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+    }
+    client
+        .disks()
+        .post(
+            "globex",
+            "api",
+            &DiskCreate {
+                name: "web-data".to_string(),
+                description: "".to_string(),
+                disk_source: DiskSource::Blank { block_size: 512 },
+                size: 1 << 30,
+            },
+        )
+        .await
+        .unwrap();
+
+    let matches = |client: Client, options: SearchOptions| async move {
+        let query = Query::Prefix("web".to_string());
+        let mut found: Vec<String> = search(&client, &query, &options)
+            .map(|m| m.unwrap().path.to_string())
+            .collect()
+            .await;
+        found.sort();
+        found
+    };
+
+    // Every organization and project is searched.
+    assert_eq!(
+        matches(client.clone(), SearchOptions::default()).await,
+        vec![
+            "acme/web",
+            "acme/web/instances/web-1",
+            "globex/api/disks/web-data",
+            "globex/website",
+        ]
+    );
+    assert_eq!(
+        matches(
+            client.clone(),
+            SearchOptions {
+                kinds: vec![ResourceKind::Disk],
+                ..Default::default()
+            }
+        )
+        .await,
+        vec!["globex/api/disks/web-data"]
+    );
+
+    // With a concurrency of one, a project listing and a resource listing
+    // are the most that are ever in flight together.
+    mock.inject(Fault::latency(Duration::from_millis(10)));
+    let in_flight = Arc::new(InFlight::default());
+    let found = matches(
+        client.clone().with_middleware(in_flight.clone()),
+        SearchOptions {
+            concurrency: 1,
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(found.len(), 4);
+    assert!(in_flight.max.load(Ordering::SeqCst) <= 2);
+
+    // By default the listings run side by side.
+    let in_flight = Arc::new(InFlight::default());
+    let found = matches(
+        client.clone().with_middleware(in_flight.clone()),
+        SearchOptions::default(),
+    )
+    .await;
+    assert_eq!(found.len(), 4);
+    assert!(in_flight.max.load(Ordering::SeqCst) > 2);
+}
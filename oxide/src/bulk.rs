@@ -0,0 +1,258 @@
+//! Start, stop, reboot or delete many instances at once.
+use std::{fmt, path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, bail, Result};
+use futures::{stream, StreamExt};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tabled::Tabled;
+
+use crate::{
+    declarative::Outcome,
+    types::{Instance, NameSortMode},
+};
+
+/// What to do to each instance.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkAction {
+    Start,
+    Stop,
+    Reboot,
+    Delete,
+}
+
+impl fmt::Display for BulkAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BulkAction::Start => "start",
+            BulkAction::Stop => "stop",
+            BulkAction::Reboot => "reboot",
+            BulkAction::Delete => "delete",
+        })
+    }
+}
+
+/// Identifies an instance by the names that address it.
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, JsonSchema)]
+pub struct InstanceSelector {
+    pub organization_name: String,
+    pub project_name: String,
+    pub instance_name: String,
+}
+
+impl InstanceSelector {
+    pub fn new(organization_name: &str, project_name: &str, instance_name: &str) -> Self {
+        InstanceSelector {
+            organization_name: organization_name.to_string(),
+            project_name: project_name.to_string(),
+            instance_name: instance_name.to_string(),
+        }
+    }
+}
+
+/// Formats the selector as in `acme/web/instances/db-1`.
+impl fmt::Display for InstanceSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}/instances/{}",
+            self.organization_name, self.project_name, self.instance_name
+        )
+    }
+}
+
+/// Parses `acme/web/db-1` or `acme/web/instances/db-1`.
+impl FromStr for InstanceSelector {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split('/').collect::<Vec<_>>()[..] {
+            [o, p, i] | [o, p, "instances", i]
+                if !o.is_empty() && !p.is_empty() && !i.is_empty() =>
+            {
+                Ok(InstanceSelector::new(o, p, i))
+            }
+            _ => Err(anyhow!(
+                "invalid instance `{}`, expected organization/project/instance",
+                s
+            )),
+        }
+    }
+}
+
+/// Options for a bulk operation.
+#[derive(Debug, Clone)]
+pub struct BulkOptions {
+    /// The maximum number of instances to act on at once.
+    pub concurrency: usize,
+    /// A file to record progress in after each instance. If it already
+    /// exists, instances it records as done are not acted on again, so an
+    /// interrupted run can be resumed by running it again.
+    pub checkpoint: Option<PathBuf>,
+}
+
+impl Default for BulkOptions {
+    fn default() -> Self {
+        BulkOptions {
+            concurrency: 8,
+            checkpoint: None,
+        }
+    }
+}
+
+/// The outcome of acting on a single instance.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Tabled)]
+pub struct BulkResult {
+    pub instance: InstanceSelector,
+    pub outcome: Outcome,
+}
+
+/// The outcome of a bulk operation. This is also what is written to the
+/// checkpoint file.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct BulkReport {
+    pub action: BulkAction,
+    pub results: Vec<BulkResult>,
+}
+
+impl BulkReport {
+    /// Returns whether the action succeeded for every instance.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|r| r.outcome == Outcome::Applied)
+    }
+
+    /// The instances the action failed for.
+    pub fn failures(&self) -> Vec<&BulkResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, Outcome::Failed { .. }))
+            .collect()
+    }
+
+    /// Render the report as a table.
+    pub fn to_table(&self) -> String {
+        tabled::Table::new(&self.results).to_string()
+    }
+
+    /// Render the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Read a report written by `save`, if there is one.
+    fn load(path: &std::path::Path) -> Result<Option<Self>> {
+        let contents = match std::fs::read(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => bail!("reading checkpoint {}: {}", path.display(), e),
+        };
+        serde_json::from_slice(&contents)
+            .map(Some)
+            .map_err(|e| anyhow!("reading checkpoint {}: {}", path.display(), e))
+    }
+
+    /// Write the report to `path`, replacing its contents atomically.
+    fn save(&self, path: &std::path::Path) -> Result<()> {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)
+            .and_then(|()| std::fs::rename(&tmp, path))
+            .map_err(|e| anyhow!("writing checkpoint {}: {}", path.display(), e))
+    }
+}
+
+impl crate::instances::Instances {
+    /// Select the instances in a project for which `filter` returns true.
+    pub async fn select<F>(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        filter: F,
+    ) -> Result<Vec<InstanceSelector>>
+    where
+        F: Fn(&Instance) -> bool,
+    {
+        Ok(self
+            .get_all(organization_name, project_name, NameSortMode::NameAscending)
+            .await?
+            .iter()
+            .filter(|i| filter(i))
+            .map(|i| InstanceSelector::new(organization_name, project_name, &i.name))
+            .collect())
+    }
+
+    /// Apply `action` to every instance in `instances`, with at most
+    /// `options.concurrency` requests in flight.
+    ///
+    /// A failure for one instance does not stop the others; the outcome for
+    /// each is recorded in the report. Only reading or writing the checkpoint
+    /// can fail the operation as a whole.
+    pub async fn bulk(
+        &self,
+        action: BulkAction,
+        instances: &[InstanceSelector],
+        options: &BulkOptions,
+    ) -> Result<BulkReport> {
+        let mut report = BulkReport {
+            action,
+            results: Vec::new(),
+        };
+        if let Some(path) = &options.checkpoint {
+            if let Some(checkpoint) = BulkReport::load(path)? {
+                if checkpoint.action != action {
+                    bail!(
+                        "checkpoint {} is for `{}`, not `{}`",
+                        path.display(),
+                        checkpoint.action,
+                        action
+                    );
+                }
+                // Anything that did not succeed is tried again.
+                report.results = checkpoint
+                    .results
+                    .into_iter()
+                    .filter(|r| r.outcome == Outcome::Applied)
+                    .collect();
+            }
+        }
+
+        let pending: Vec<&InstanceSelector> = instances
+            .iter()
+            .filter(|i| !report.results.iter().any(|r| &r.instance == *i))
+            .collect();
+        let mut outcomes = stream::iter(pending)
+            .map(|instance| async move {
+                let (i, o, p) = (
+                    instance.instance_name.as_str(),
+                    instance.organization_name.as_str(),
+                    instance.project_name.as_str(),
+                );
+                let result = match action {
+                    BulkAction::Start => self.start(i, o, p).await.map(|_| ()),
+                    BulkAction::Stop => self.stop(i, o, p).await.map(|_| ()),
+                    BulkAction::Reboot => self.reboot(i, o, p).await.map(|_| ()),
+                    BulkAction::Delete => self.delete(i, o, p).await,
+                };
+                BulkResult {
+                    instance: instance.clone(),
+                    outcome: match result {
+                        Ok(()) => Outcome::Applied,
+                        Err(e) => Outcome::Failed {
+                            error: format!("{:#}", e),
+                        },
+                    },
+                }
+            })
+            .buffer_unordered(options.concurrency.max(1));
+
+        while let Some(result) = outcomes.next().await {
+            report.results.push(result);
+            if let Some(path) = &options.checkpoint {
+                report.save(path)?;
+            }
+        }
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,351 @@
+//! Record requests and responses to a cassette file, and replay them later
+//! without a server.
+//!
+//! A client given a [`Cassette`] in record mode sends every request through
+//! its transport as usual and appends the request and its response to the
+//! cassette file. In replay mode nothing is sent: each request is answered
+//! with the first recorded response to a matching request that has not
+//! already been replayed.
+//! Requests match if they have the same method, path, query and body.
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! use oxide_api::{cassette::Cassette, Client};
+//!
+//! let client = Client::new("token", "http://127.0.0.1:12220")
+//!     .with_cassette(Cassette::replay("tests/cassettes/organizations.json")?);
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+pub use crate::redact::REDACTED;
+use crate::{
+    redact::SENSITIVE_HEADERS,
+    transport::{BoxFuture, Transport},
+};
+
+/// A request as written to a cassette.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// The values of each header, in the order they were sent.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl RecordedRequest {
+    fn from_request(request: &http::Request<Bytes>) -> Self {
+        RecordedRequest {
+            method: request.method().to_string(),
+            path: request.uri().path().to_string(),
+            query: request.uri().query().map(str::to_string),
+            headers: recorded_headers(request.headers()),
+            body: Some(request.body())
+                .filter(|body| !body.is_empty())
+                .map(|body| String::from_utf8_lossy(body).to_string()),
+        }
+    }
+
+    /// The query parameters, sorted, so that the order they were added in
+    /// does not affect matching.
+    fn query_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs: Vec<(String, String)> =
+            serde_urlencoded::from_str(self.query.as_deref().unwrap_or_default())
+                .unwrap_or_default();
+        pairs.sort();
+        pairs
+    }
+
+    /// Bodies that are both JSON are compared as JSON, so that whitespace and
+    /// key order do not affect matching.
+    fn body_matches(&self, other: &RecordedRequest) -> bool {
+        let body = self.body.as_deref().unwrap_or_default();
+        let other = other.body.as_deref().unwrap_or_default();
+        match (
+            serde_json::from_str::<serde_json::Value>(body),
+            serde_json::from_str::<serde_json::Value>(other),
+        ) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => body == other,
+        }
+    }
+
+    /// How this request differs from `other`, if it does. Headers are not
+    /// compared.
+    fn mismatch(&self, other: &RecordedRequest) -> Option<&'static str> {
+        if self.method != other.method {
+            Some("method")
+        } else if self.path != other.path {
+            Some("path")
+        } else if self.query_pairs() != other.query_pairs() {
+            Some("query")
+        } else if !self.body_matches(other) {
+            Some("body")
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for RecordedRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.path)?;
+        if let Some(query) = &self.query {
+            write!(f, "?{}", query)?;
+        }
+        Ok(())
+    }
+}
+
+fn recorded_headers(headers: &http::HeaderMap) -> BTreeMap<String, Vec<String>> {
+    let mut recorded: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (name, value) in headers {
+        // Credentials are never written to a cassette.
+        let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+            REDACTED.to_string()
+        } else {
+            String::from_utf8_lossy(value.as_bytes()).to_string()
+        };
+        recorded.entry(name.to_string()).or_default().push(value);
+    }
+    recorded
+}
+
+/// A response as written to a cassette.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct RecordedResponse {
+    pub status: u16,
+    /// The values of each header, in the order they were sent.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub body: String,
+}
+
+impl RecordedResponse {
+    fn to_response(&self) -> Result<http::Response<Bytes>> {
+        let mut builder = http::Response::builder().status(self.status);
+        for (name, values) in &self.headers {
+            for value in values {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+        }
+        Ok(builder.body(Bytes::from(self.body.clone()))?)
+    }
+}
+
+/// A request and the response it got.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// The contents of a cassette file.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct Tape {
+    pub interactions: Vec<Interaction>,
+}
+
+/// Whether a cassette is being recorded or replayed.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Mode {
+    Record,
+    Replay,
+}
+
+/// Returned, wrapped in an `anyhow::Error`, when a request is replayed that
+/// has no unused match in the cassette.
+#[derive(Debug, Clone)]
+pub struct NoMatch {
+    pub cassette: PathBuf,
+    pub request: RecordedRequest,
+    /// Recorded requests to the same path, by index, with how each differs
+    /// from the request or why it could not be used.
+    pub candidates: Vec<(usize, RecordedRequest, String)>,
+}
+
+impl fmt::Display for NoMatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "no interaction in cassette {} matches {}",
+            self.cassette.display(),
+            self.request
+        )?;
+        if self.candidates.is_empty() {
+            write!(f, "; nothing was recorded for this path")?;
+        }
+        for (index, request, reason) in &self.candidates {
+            write!(f, "\n  #{} {}: {}", index, request, reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for NoMatch {}
+
+/// A cassette file that a [`Client`](crate::Client) records to or replays
+/// from.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+    tape: Mutex<Tape>,
+    /// Which interactions have been replayed.
+    played: Mutex<Vec<bool>>,
+}
+
+impl Cassette {
+    /// Record to `path`, replacing anything recorded there before.
+    pub fn record<P: AsRef<Path>>(path: P) -> Self {
+        Cassette {
+            path: path.as_ref().to_path_buf(),
+            mode: Mode::Record,
+            tape: Mutex::new(Tape::default()),
+            played: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Replay what was recorded to `path`.
+    pub fn replay<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("reading cassette {} failed: {}", path.display(), e))?;
+        let tape: Tape = serde_json::from_str(&contents)
+            .map_err(|e| anyhow::anyhow!("parsing cassette {} failed: {}", path.display(), e))?;
+        Ok(Cassette::from_tape(path, tape))
+    }
+
+    /// Replay `tape`, reporting `path` as its source in diagnostics.
+    pub fn from_tape<P: AsRef<Path>>(path: P, tape: Tape) -> Self {
+        let played = vec![false; tape.interactions.len()];
+        Cassette {
+            path: path.as_ref().to_path_buf(),
+            mode: Mode::Replay,
+            tape: Mutex::new(tape),
+            played: Mutex::new(played),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The interactions recorded so far, or loaded for replay.
+    pub fn tape(&self) -> Tape {
+        self.tape.lock().unwrap().clone()
+    }
+
+    /// Whether every recorded interaction has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.played.lock().unwrap().iter().all(|played| *played)
+    }
+
+    async fn record_one(
+        &self,
+        inner: &dyn Transport,
+        request: http::Request<Bytes>,
+    ) -> Result<http::Response<Bytes>> {
+        let recorded = RecordedRequest::from_request(&request);
+        let response = inner.send(request).await?;
+
+        let interaction = Interaction {
+            request: recorded,
+            response: RecordedResponse {
+                status: response.status().as_u16(),
+                headers: recorded_headers(response.headers()),
+                body: String::from_utf8_lossy(response.body()).to_string(),
+            },
+        };
+
+        // Write the whole cassette after every request, so that a test that
+        // fails partway still leaves behind what it recorded.
+        let contents = {
+            let mut tape = self.tape.lock().unwrap();
+            tape.interactions.push(interaction);
+            serde_json::to_string_pretty(&*tape)?
+        };
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, contents)?;
+
+        Ok(response)
+    }
+
+    fn replay_one(&self, request: &http::Request<Bytes>) -> Result<http::Response<Bytes>> {
+        let wanted = RecordedRequest::from_request(request);
+        let tape = self.tape.lock().unwrap();
+        let mut played = self.played.lock().unwrap();
+
+        let mut candidates = Vec::new();
+        for (index, interaction) in tape.interactions.iter().enumerate() {
+            let recorded = &interaction.request;
+            if recorded.path != wanted.path {
+                continue;
+            }
+            match recorded.mismatch(&wanted) {
+                Some(field) => {
+                    candidates.push((index, recorded.clone(), format!("{} differs", field)))
+                }
+                None if played[index] => {
+                    candidates.push((index, recorded.clone(), "already replayed".to_string()))
+                }
+                None => {
+                    played[index] = true;
+                    log::debug!(
+                        "replaying #{} from {} for {}",
+                        index,
+                        self.path.display(),
+                        wanted
+                    );
+                    return interaction.response.to_response();
+                }
+            }
+        }
+
+        Err(NoMatch {
+            cassette: self.path.clone(),
+            request: wanted,
+            candidates,
+        }
+        .into())
+    }
+}
+
+/// A cassette in front of the transport that recordings are made with.
+pub(crate) struct CassetteTransport {
+    pub(crate) cassette: Cassette,
+    pub(crate) inner: Arc<dyn Transport>,
+}
+
+impl Transport for CassetteTransport {
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Bytes>>> {
+        Box::pin(async move {
+            match self.cassette.mode {
+                Mode::Record => self.cassette.record_one(&*self.inner, request).await,
+                Mode::Replay => self.cassette.replay_one(&request),
+            }
+        })
+    }
+}
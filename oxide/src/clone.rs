@@ -0,0 +1,222 @@
+//! Copy the layout of a project, and optionally its data, into a new project.
+use anyhow::{bail, Result};
+
+use crate::{
+    declarative::{live_project, ApplyReport, Plan, ProjectSpec, ResourceKind, VpcSpec},
+    retention::MAX_SNAPSHOT_PREFIX_LEN,
+    types::{
+        DiskSource, NameOrIdSortMode, RouteDestination, RouteTarget, SnapshotCreate,
+        VpcFirewallRuleHostFilter, VpcFirewallRuleTarget,
+    },
+};
+
+/// Maps the name of a resource in the source project to its name in the clone.
+pub type RenameFn = dyn Fn(ResourceKind, &str) -> String + Send + Sync;
+
+/// Options for cloning a project.
+#[derive(Default)]
+pub struct CloneOptions {
+    /// Snapshot each disk and create the new disks from those snapshots,
+    /// rather than from the source the original disks were created from.
+    pub snapshot_disks: bool,
+    /// Renames resources in the clone. Names are kept as they are if unset.
+    pub rename: Option<Box<RenameFn>>,
+}
+
+/// Copy the layout of `source` into a new project named `project_name`,
+/// renaming every resource and every reference to it with `rename`.
+///
+/// Snapshots and images are not copied. IPv6 prefixes are left for the
+/// system to assign, since they must be unique.
+pub fn clone_spec(source: &ProjectSpec, project_name: &str, rename: &RenameFn) -> ProjectSpec {
+    let mut spec = ProjectSpec {
+        name: project_name.to_string(),
+        description: source.description.to_string(),
+        vpcs: source.vpcs.clone(),
+        disks: source.disks.clone(),
+        snapshots: Vec::new(),
+        images: Vec::new(),
+        instances: source.instances.clone(),
+    };
+
+    for vpc in &mut spec.vpcs {
+        vpc.name = rename(ResourceKind::Vpc, &vpc.name);
+        vpc.ipv6_prefix = None;
+        for subnet in &mut vpc.subnets {
+            subnet.name = rename(ResourceKind::Subnet, &subnet.name);
+            subnet.ipv6_block = None;
+        }
+        for router in &mut vpc.routers {
+            router.name = rename(ResourceKind::Router, &router.name);
+            for route in &mut router.routes {
+                route.name = rename(ResourceKind::Route, &route.name);
+                route.destination = match &route.destination {
+                    RouteDestination::Vpc(n) => RouteDestination::Vpc(rename(ResourceKind::Vpc, n)),
+                    RouteDestination::Subnet(n) => {
+                        RouteDestination::Subnet(rename(ResourceKind::Subnet, n))
+                    }
+                    other => other.clone(),
+                };
+                route.target = match &route.target {
+                    RouteTarget::Vpc(n) => RouteTarget::Vpc(rename(ResourceKind::Vpc, n)),
+                    RouteTarget::Subnet(n) => RouteTarget::Subnet(rename(ResourceKind::Subnet, n)),
+                    RouteTarget::Instance(n) => {
+                        RouteTarget::Instance(rename(ResourceKind::Instance, n))
+                    }
+                    other => other.clone(),
+                };
+            }
+        }
+        for rule in vpc.firewall_rules.iter_mut().flatten() {
+            for target in &mut rule.targets {
+                *target = match &*target {
+                    VpcFirewallRuleTarget::Vpc(n) => {
+                        VpcFirewallRuleTarget::Vpc(rename(ResourceKind::Vpc, n))
+                    }
+                    VpcFirewallRuleTarget::Subnet(n) => {
+                        VpcFirewallRuleTarget::Subnet(rename(ResourceKind::Subnet, n))
+                    }
+                    VpcFirewallRuleTarget::Instance(n) => {
+                        VpcFirewallRuleTarget::Instance(rename(ResourceKind::Instance, n))
+                    }
+                    other => other.clone(),
+                };
+            }
+            for host in &mut rule.filters.hosts {
+                *host = match &*host {
+                    VpcFirewallRuleHostFilter::Vpc(n) => {
+                        VpcFirewallRuleHostFilter::Vpc(rename(ResourceKind::Vpc, n))
+                    }
+                    VpcFirewallRuleHostFilter::Subnet(n) => {
+                        VpcFirewallRuleHostFilter::Subnet(rename(ResourceKind::Subnet, n))
+                    }
+                    VpcFirewallRuleHostFilter::Instance(n) => {
+                        VpcFirewallRuleHostFilter::Instance(rename(ResourceKind::Instance, n))
+                    }
+                    other => other.clone(),
+                };
+            }
+        }
+    }
+
+    for disk in &mut spec.disks {
+        disk.name = rename(ResourceKind::Disk, &disk.name);
+        // Snapshots and images stay in the source project, where the ids
+        // still refer to them.
+        disk.source_name = None;
+    }
+    for instance in &mut spec.instances {
+        instance.name = rename(ResourceKind::Instance, &instance.name);
+        for disk in &mut instance.disks {
+            *disk = rename(ResourceKind::Disk, disk);
+        }
+    }
+
+    spec
+}
+
+impl crate::projects::Projects {
+    /// Clone a project into a new project, which may be in another
+    /// organization.
+    ///
+    /// The VPCs, subnets, routers, routes and firewall rules are copied, and
+    /// the disks and instances recreated with the same sizing. With
+    /// `options.snapshot_disks` set, each disk is snapshotted first and the new
+    /// disk created from the snapshot, so that it has the same contents.
+    pub async fn clone_project(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        target_organization_name: &str,
+        target_project_name: &str,
+        options: &CloneOptions,
+    ) -> Result<ApplyReport> {
+        if self
+            .get_all(target_organization_name, NameOrIdSortMode::NameAscending)
+            .await?
+            .iter()
+            .any(|p| p.name == target_project_name)
+        {
+            bail!(
+                "project `{}/{}` already exists",
+                target_organization_name,
+                target_project_name
+            );
+        }
+
+        let manifest = self.export(organization_name, project_name).await?;
+        let source = &manifest.organizations[0].projects[0];
+
+        let keep = |_: ResourceKind, name: &str| name.to_string();
+        let rename: &RenameFn = match &options.rename {
+            Some(rename) => rename.as_ref(),
+            None => &keep,
+        };
+        let mut spec = clone_spec(source, target_project_name, rename);
+
+        if options.snapshot_disks {
+            let stamp = chrono::Utc::now().format("%Y%m%d%H%M%S");
+            for (disk, clone) in source.disks.iter().zip(spec.disks.iter_mut()) {
+                let prefix = &disk.name[..disk.name.len().min(MAX_SNAPSHOT_PREFIX_LEN)];
+                let snapshot = self
+                    .client
+                    .snapshots()
+                    .post(
+                        organization_name,
+                        project_name,
+                        &SnapshotCreate {
+                            name: format!("{}-clone-{}", prefix, stamp),
+                            description: format!(
+                                "Snapshot of {} for {}/{}",
+                                disk.name, target_organization_name, target_project_name
+                            ),
+                            disk: disk.name.to_string(),
+                        },
+                    )
+                    .await?;
+                clone.disk_source = DiskSource::Snapshot {
+                    snapshot_id: snapshot.id,
+                };
+            }
+        }
+
+        // Creating a VPC also creates its `default` subnet and firewall rules,
+        // so the VPCs are created on their own first, and everything else is
+        // planned against what is then in the new project.
+        let vpcs = ProjectSpec {
+            name: spec.name.to_string(),
+            description: spec.description.to_string(),
+            vpcs: spec
+                .vpcs
+                .iter()
+                .map(|vpc| VpcSpec {
+                    name: vpc.name.to_string(),
+                    description: vpc.description.to_string(),
+                    dns_name: vpc.dns_name.to_string(),
+                    ipv6_prefix: vpc.ipv6_prefix,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let mut report = Plan::for_project(target_organization_name, &vpcs, None)
+            .apply(&self.client)
+            .await;
+        if !report.is_success() {
+            return Ok(report);
+        }
+
+        let project = self
+            .get(target_organization_name, target_project_name)
+            .await?;
+        let live = live_project(&self.client, target_organization_name, project).await?;
+        report.results.extend(
+            Plan::for_project(target_organization_name, &spec, Some(&live))
+                .apply(&self.client)
+                .await
+                .results,
+        );
+
+        Ok(report)
+    }
+}
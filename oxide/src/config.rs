@@ -0,0 +1,275 @@
+//! Named connection profiles, read from `~/.config/oxide/config.toml`.
+//!
+//! ```toml
+//! default_profile = "lab"
+//!
+//! [profiles.lab]
+//! host = "https://oxide.lab.example.com"
+//! token_file = "lab-token"
+//! ca_bundle = "/etc/ssl/certs/lab-ca.pem"
+//! timeout = 30
+//! connect_timeout = 5
+//! organization = "acme"
+//! project = "web"
+//! ```
+//!
+//! Relative paths are relative to the directory the file is in, and a leading
+//! `~/` is the home directory. Each setting can be overridden by an
+//! environment variable, which takes precedence over the profile: `OXIDE_HOST`,
+//! `OXIDE_TOKEN`, `OXIDE_TOKEN_FILE`, `OXIDE_CA_BUNDLE`, `OXIDE_TIMEOUT`,
+//! `OXIDE_CONNECT_TIMEOUT`, `OXIDE_ORGANIZATION` and `OXIDE_PROJECT`. A
+//! different file can be read by setting `OXIDE_CONFIG`.
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// The name of the profile used when none is given.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The contents of a configuration file.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct Config {
+    /// The profile used when none is given. `default`, if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    /// Where the file was read from. Relative paths in it are resolved
+    /// against its directory.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// How to reach and authenticate with one Oxide deployment.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Profile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// The token itself. Takes precedence over `token_file`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// A file containing the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_file: Option<PathBuf>,
+    /// PEM certificates to trust in addition to the bundled Mozilla roots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<PathBuf>,
+    /// Seconds to wait for a whole request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// Seconds to wait for a connection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout: Option<u64>,
+    /// The organization to use when a command does not name one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    /// The project to use when a command does not name one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+impl Config {
+    /// Where the configuration file is: `$OXIDE_CONFIG` if set, otherwise
+    /// `oxide/config.toml` in `$XDG_CONFIG_HOME` or `~/.config`.
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("OXIDE_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let dir = match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => home_dir()?.join(".config"),
+        };
+        Some(dir.join("oxide").join("config.toml"))
+    }
+
+    /// Read the configuration file. A missing file is the same as an empty
+    /// one.
+    pub fn load() -> Result<Self> {
+        match Config::path() {
+            Some(path) if path.exists() => Config::from_path(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("reading {} failed: {}", path.display(), e))?;
+        let mut config: Config = toml::from_str(&contents)
+            .map_err(|e| anyhow!("parsing {} failed: {}", path.display(), e))?;
+        config.path = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    /// The profile called `name`, or the default profile, with any paths in
+    /// it resolved and the environment applied on top.
+    pub fn profile(&self, name: Option<&str>) -> Result<Profile> {
+        self.profile_with_env(name, &|name| env::var(name).ok())
+    }
+
+    /// Like [`profile`](Config::profile), but with environment variables
+    /// looked up by `env` rather than in the process environment.
+    pub fn profile_with_env(
+        &self,
+        name: Option<&str>,
+        env: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<Profile> {
+        let name = name
+            .or(self.default_profile.as_deref())
+            .unwrap_or(DEFAULT_PROFILE);
+        let mut profile = match self.profiles.get(name) {
+            Some(profile) => profile.clone(),
+            // Without a file, everything has to come from the environment.
+            None if self.profiles.is_empty() && name == DEFAULT_PROFILE => Profile::default(),
+            None => bail!(
+                "no profile {:?} in {}; the profiles are: {}",
+                name,
+                self.describe(),
+                self.profiles
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        profile.token_file = profile.token_file.map(|p| self.resolve(&p));
+        profile.ca_bundle = profile.ca_bundle.map(|p| self.resolve(&p));
+        profile.apply_env(env)
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        if let (Ok(rest), Some(home)) = (path.strip_prefix("~"), home_dir()) {
+            return home.join(rest);
+        }
+        match self.path.as_deref().and_then(Path::parent) {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match &self.path {
+            Some(path) => path.display().to_string(),
+            None => "the configuration".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Profile")
+            .field("host", &self.host)
+            .field(
+                "token",
+                &self.token.as_ref().map(|_| crate::redact::REDACTED),
+            )
+            .field("token_file", &self.token_file)
+            .field("ca_bundle", &self.ca_bundle)
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("organization", &self.organization)
+            .field("project", &self.project)
+            .finish()
+    }
+}
+
+impl Profile {
+    /// Override the profile with whatever is set in the environment, as
+    /// looked up by `env`. Empty variables count as unset.
+    pub fn apply_env(mut self, env: &dyn Fn(&str) -> Option<String>) -> Result<Self> {
+        let var = |name: &str| env(name).filter(|value| !value.is_empty());
+        let seconds = |name: &str| {
+            var(name)
+                .map(|value| {
+                    value.parse::<u64>().map_err(|_| {
+                        anyhow!("{} must be a number of seconds, not {:?}", name, value)
+                    })
+                })
+                .transpose()
+        };
+
+        if let Some(host) = var("OXIDE_HOST") {
+            self.host = Some(host);
+        }
+        if let Some(token) = var("OXIDE_TOKEN") {
+            self.token = Some(token);
+        } else if let Some(token_file) = var("OXIDE_TOKEN_FILE") {
+            self.token = None;
+            self.token_file = Some(token_file.into());
+        }
+        if let Some(ca_bundle) = var("OXIDE_CA_BUNDLE") {
+            self.ca_bundle = Some(ca_bundle.into());
+        }
+        if let Some(timeout) = seconds("OXIDE_TIMEOUT")? {
+            self.timeout = Some(timeout);
+        }
+        if let Some(timeout) = seconds("OXIDE_CONNECT_TIMEOUT")? {
+            self.connect_timeout = Some(timeout);
+        }
+        if let Some(organization) = var("OXIDE_ORGANIZATION") {
+            self.organization = Some(organization);
+        }
+        if let Some(project) = var("OXIDE_PROJECT") {
+            self.project = Some(project);
+        }
+        Ok(self)
+    }
+
+    /// The token, read from `token_file` if it is not set directly.
+    pub fn token(&self) -> Result<String> {
+        if let Some(token) = &self.token {
+            return Ok(token.clone());
+        }
+        match &self.token_file {
+            Some(path) => {
+                let token = std::fs::read_to_string(path)
+                    .map_err(|e| anyhow!("reading token file {} failed: {}", path.display(), e))?;
+                Ok(token.trim().to_string())
+            }
+            None => bail!("no token: set OXIDE_TOKEN, or token or token_file in the profile"),
+        }
+    }
+
+    /// A client for the deployment the profile describes.
+    pub fn client(&self) -> Result<crate::Client> {
+        let host = match &self.host {
+            Some(host) => host,
+            None => bail!("no host: set OXIDE_HOST, or host in the profile"),
+        };
+
+        let mut options = crate::transport::TransportOptions::new();
+        if let Some(path) = &self.ca_bundle {
+            let pem = std::fs::read(path)
+                .map_err(|e| anyhow!("reading CA bundle {} failed: {}", path.display(), e))?;
+            options = options.root_certificates(&pem);
+        }
+        if let Some(timeout) = self.timeout {
+            options = options.timeout(Duration::from_secs(timeout));
+        }
+        if let Some(timeout) = self.connect_timeout {
+            options = options.connect_timeout(Duration::from_secs(timeout));
+        }
+
+        let mut client =
+            crate::Client::new(self.token()?, host).with_transport_options(&options)?;
+        // A token kept in a file is read again when the file changes, so that
+        // it can be rotated under a long-running client.
+        if let (None, Some(path)) = (&self.token, &self.token_file) {
+            client = client.with_token_provider(crate::token::FileToken::new(path));
+        }
+        client.organization = self.organization.clone();
+        client.project = self.project.clone();
+        Ok(client)
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
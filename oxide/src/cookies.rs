@@ -0,0 +1,157 @@
+//! Authenticating with a session cookie rather than a token.
+//!
+//! Every [`Client`](crate::Client) keeps the cookies the server sets, and
+//! sends them back with later requests, so after
+//! [`Client::login`](crate::Client::login) the session is used for every
+//! call. A client made with an empty token sends no `Authorization` header,
+//! and so is authenticated by its session alone.
+//!
+//! The cookies can be saved and given to a client in another process:
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! use oxide_api::{cookies::Session, Client};
+//!
+//! let client = Client::new("", "http://127.0.0.1:12220");
+//! client.login("privileged").await?;
+//! client.session().save("session.json")?;
+//!
+//! let later =
+//!     Client::new("", "http://127.0.0.1:12220").with_session(Session::load("session.json")?);
+//! let me = later.hidden().session_me().await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::{collections::BTreeMap, fmt, io::Write, path::Path};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The cookies a server has set, by name.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Session {
+    #[serde(default)]
+    pub cookies: BTreeMap<String, String>,
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.cookies
+                    .keys()
+                    .map(|name| (name, crate::redact::REDACTED)),
+            )
+            .finish()
+    }
+}
+
+impl Session {
+    /// Read a session saved with [`save`](Session::save).
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("reading session {} failed: {}", path.display(), e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| anyhow!("parsing session {} failed: {}", path.display(), e))
+    }
+
+    /// Write the session to `path`, readable only by its owner, since it is
+    /// as good as a password until it expires.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let contents = serde_json::to_string_pretty(self)?;
+        let error = |e: std::io::Error| anyhow!("writing session {} failed: {}", path.display(), e);
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(path).map_err(error)?;
+        // The mode only applies to a new file, so tighten an existing one
+        // before anything is written to it.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))
+                .map_err(error)?;
+        }
+        file.write_all(contents.as_bytes()).map_err(error)?;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cookies.clear();
+    }
+
+    /// The value of a `Cookie` header carrying every cookie.
+    pub(crate) fn cookie_header(&self) -> Option<String> {
+        if self.cookies.is_empty() {
+            return None;
+        }
+        Some(
+            self.cookies
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+
+    /// Keep the cookies set in `headers`. A cookie set to nothing, or that
+    /// has expired by its `Max-Age` or `Expires`, is removed, which is how
+    /// servers log out.
+    pub(crate) fn update(&mut self, headers: &http::HeaderMap) {
+        for value in headers.get_all(http::header::SET_COOKIE) {
+            let value = match value.to_str() {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            let mut parts = value.split(';').map(str::trim);
+            let (name, value) = match parts.next().and_then(|pair| pair.split_once('=')) {
+                Some((name, value)) if !name.trim().is_empty() => (name.trim(), value.trim()),
+                _ => continue,
+            };
+            let (mut max_age, mut expires) = (None, None);
+            for (key, value) in parts.filter_map(|attribute| attribute.split_once('=')) {
+                let (key, value) = (key.trim(), value.trim());
+                if key.eq_ignore_ascii_case("max-age") {
+                    max_age = value.parse::<i64>().ok();
+                } else if key.eq_ignore_ascii_case("expires") {
+                    expires = parse_http_date(value);
+                }
+            }
+            // `Max-Age` takes precedence over `Expires`.
+            let expired = match (max_age, expires) {
+                (Some(age), _) => age <= 0,
+                (None, Some(expires)) => expires <= Utc::now(),
+                (None, None) => false,
+            };
+
+            if expired || value.trim_matches('"').is_empty() {
+                self.cookies.remove(name);
+            } else {
+                self.cookies.insert(name.to_string(), value.to_string());
+            }
+        }
+    }
+}
+
+/// Parse the date in an `Expires` attribute, which is usually as in RFC 7231,
+/// `Wed, 21 Oct 2015 07:28:00 GMT`, but sometimes has dashes in the date.
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(value, "%a, %d-%b-%Y %H:%M:%S GMT")
+        .ok()
+        .map(|date| Utc.from_utc_datetime(&date))
+}
@@ -0,0 +1,1573 @@
+//! Declarative management of organizations and projects from a manifest.
+//!
+//! A [`Manifest`] describes the desired organizations, projects and the
+//! resources within them. [`Manifest::plan`] compares it against the live
+//! state and produces a [`Plan`] of changes, which [`Plan::apply`] carries out
+//! in dependency order.
+//!
+//! The same types are used to export the live state of an organization or
+//! project, via [`Organizations::export`](crate::organizations::Organizations::export)
+//! and [`Projects::export`](crate::projects::Projects::export). Exported
+//! manifests contain no server-owned fields such as ids or timestamps.
+//!
+//! Everything beneath a declared organization is managed by the manifest:
+//! projects, VPCs, subnets, routers, routes, disks, snapshots, images and
+//! instances that exist but are not declared are deleted, unless the
+//! organization is marked [`partial`](OrganizationSpec::partial).
+//! Organizations themselves are never deleted, and system routers and routes
+//! are left alone, as are the `default` subnet and firewall rules the system
+//! creates with each VPC unless the manifest declares them.
+use std::{collections::BTreeSet, fmt};
+
+use anyhow::{anyhow, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tabled::Tabled;
+
+use crate::types::{
+    Disk, DiskCreate, DiskSource, ImageCreate, ImageSource, InstanceCreate, InstanceDiskAttachment,
+    InstanceNetworkInterfaceAttachment, Ipv4Net, Ipv6Net, NameOrIdSortMode, NameSortMode,
+    Organization, OrganizationCreate, OrganizationUpdate, Project, ProjectCreate, ProjectUpdate,
+    RouteDestination, RouteTarget, RouterRouteCreateParams, RouterRouteKind,
+    RouterRouteUpdateParams, SnapshotCreate, VpcCreate, VpcFirewallRule, VpcFirewallRuleUpdate,
+    VpcFirewallRuleUpdateParams, VpcRouterCreate, VpcRouterKind, VpcRouterUpdate, VpcSubnetCreate,
+    VpcSubnetUpdate, VpcUpdate,
+};
+
+/// The desired state of a set of organizations.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+pub struct Manifest {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub organizations: Vec<OrganizationSpec>,
+}
+
+/// An organization and the projects within it.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+pub struct OrganizationSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<ProjectSpec>,
+    /// Only manage the declared projects. Projects that are not declared are
+    /// left alone, and an existing organization is not updated.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+}
+
+/// A project and the resources within it.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+pub struct ProjectSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vpcs: Vec<VpcSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disks: Vec<DiskSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snapshots: Vec<SnapshotSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImageSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instances: Vec<InstanceSpec>,
+}
+
+/// A VPC and the resources within it.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+pub struct VpcSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub dns_name: String,
+    /// The IPv6 prefix of the VPC. One is assigned by the system if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv6_prefix: Option<Ipv6Net>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subnets: Vec<SubnetSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routers: Vec<RouterSpec>,
+    /// The complete set of firewall rules for the VPC. The rules are left
+    /// unmanaged if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firewall_rules: Option<Vec<VpcFirewallRuleUpdate>>,
+}
+
+/// A subnet within a VPC.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct SubnetSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub ipv4_block: Ipv4Net,
+    /// The IPv6 block of the subnet. One is assigned by the system if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv6_block: Option<Ipv6Net>,
+}
+
+/// A custom router within a VPC.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+pub struct RouterSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<RouteSpec>,
+}
+
+/// A custom route within a router.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct RouteSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub destination: RouteDestination,
+    pub target: RouteTarget,
+}
+
+/// A disk within a project.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct DiskSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// The size of the disk in bytes.
+    pub size: u64,
+    /// The initial contents of the disk. Only used when the disk is created.
+    pub disk_source: DiskSource,
+    /// The name of the snapshot or image in the same project that
+    /// `disk_source` refers to. When set, the disk is created from the
+    /// snapshot or image with this name, since the id in `disk_source` only
+    /// means something in the project the manifest was exported from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+}
+
+/// A snapshot of a disk within a project.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+pub struct SnapshotSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// The name of the disk the snapshot was taken from.
+    pub disk: String,
+}
+
+/// An image within a project.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+pub struct ImageSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub block_size: u64,
+    /// Where the image was created from. Only used when the image is created.
+    ///
+    /// The system only reports the source of images created from a URL, so
+    /// exported images created from a snapshot leave this unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ImageSource>,
+}
+
+/// An instance within a project.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+pub struct InstanceSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub hostname: String,
+    /// The memory of the instance in bytes.
+    pub memory: u64,
+    pub ncpus: u16,
+    /// The names of the disks in the project to attach. Only used when the
+    /// instance is created.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disks: Vec<String>,
+    /// Only used when the instance is created.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub user_data: String,
+}
+
+impl Manifest {
+    /// Parse a manifest from YAML.
+    pub fn from_yaml(s: &str) -> Result<Self> {
+        Ok(serde_yaml::from_str(s)?)
+    }
+
+    /// Render the manifest as YAML.
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    /// Parse a manifest from JSON.
+    pub fn from_json(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Render the manifest as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Read the live state of the organizations declared in this manifest.
+    ///
+    /// Declared organizations that do not exist are omitted from the result.
+    pub async fn live(&self, client: &crate::Client) -> Result<Manifest> {
+        let mut live = Manifest::default();
+        for organization in client
+            .organizations()
+            .get_all(NameOrIdSortMode::NameAscending)
+            .await?
+        {
+            if !self
+                .organizations
+                .iter()
+                .any(|o| o.name == organization.name)
+            {
+                continue;
+            }
+
+            live.organizations
+                .push(live_organization(client, organization).await?);
+        }
+
+        Ok(live)
+    }
+
+    /// Compute the changes needed to bring the live state in line with this
+    /// manifest.
+    pub async fn plan(&self, client: &crate::Client) -> Result<Plan> {
+        let live = self.live(client).await?;
+        Ok(Plan::new(self, &live))
+    }
+}
+
+impl crate::organizations::Organizations {
+    /// Export the live state of an organization and all of its projects as a
+    /// manifest.
+    pub async fn export(&self, organization_name: &str) -> Result<Manifest> {
+        let organization = self.get(organization_name).await?;
+
+        Ok(Manifest {
+            organizations: vec![live_organization(&self.client, organization).await?],
+        })
+    }
+}
+
+impl crate::projects::Projects {
+    /// Export the live state of a project as a manifest.
+    ///
+    /// The project's organization is marked [`partial`](OrganizationSpec::partial),
+    /// so applying the manifest elsewhere leaves the organization's other
+    /// projects alone.
+    pub async fn export(&self, organization_name: &str, project_name: &str) -> Result<Manifest> {
+        let organization = self.client.organizations().get(organization_name).await?;
+        let project = self.get(organization_name, project_name).await?;
+
+        Ok(Manifest {
+            organizations: vec![OrganizationSpec {
+                name: organization.name,
+                description: organization.description,
+                projects: vec![live_project(&self.client, organization_name, project).await?],
+                partial: true,
+            }],
+        })
+    }
+}
+
+async fn live_organization(
+    client: &crate::Client,
+    organization: Organization,
+) -> Result<OrganizationSpec> {
+    let mut spec = OrganizationSpec {
+        name: organization.name,
+        description: organization.description,
+        ..Default::default()
+    };
+    for project in client
+        .projects()
+        .get_all(&spec.name, NameOrIdSortMode::NameAscending)
+        .await?
+    {
+        spec.projects
+            .push(live_project(client, &spec.name, project).await?);
+    }
+
+    Ok(spec)
+}
+
+pub(crate) async fn live_project(
+    client: &crate::Client,
+    organization_name: &str,
+    project: Project,
+) -> Result<ProjectSpec> {
+    let project_name = project.name.as_str();
+
+    let mut vpcs = Vec::new();
+    for vpc in client
+        .vpcs()
+        .get_all(organization_name, project_name, NameSortMode::NameAscending)
+        .await?
+    {
+        vpcs.push(
+            live_vpc(
+                client,
+                organization_name,
+                project_name,
+                VpcSpec {
+                    name: vpc.name,
+                    description: vpc.description,
+                    dns_name: vpc.dns_name,
+                    ipv6_prefix: Some(vpc.ipv6_prefix),
+                    ..Default::default()
+                },
+            )
+            .await?,
+        );
+    }
+
+    let disks = client
+        .disks()
+        .get_all(organization_name, project_name, NameSortMode::NameAscending)
+        .await?;
+
+    let live_snapshots = client
+        .snapshots()
+        .get_all(organization_name, project_name, NameSortMode::NameAscending)
+        .await?;
+    let live_images = client
+        .images()
+        .get_all(organization_name, project_name, NameSortMode::NameAscending)
+        .await?;
+
+    let disk_specs = disks
+        .iter()
+        .cloned()
+        .map(|disk| {
+            let mut spec = DiskSpec::from(disk);
+            match &spec.disk_source {
+                DiskSource::Snapshot { snapshot_id } => {
+                    spec.source_name = live_snapshots
+                        .iter()
+                        .find(|s| s.id == *snapshot_id)
+                        .map(|s| s.name.to_string());
+                }
+                DiskSource::Image { image_id } => {
+                    match live_images.iter().find(|i| i.id == *image_id) {
+                        Some(image) => spec.source_name = Some(image.name.to_string()),
+                        // Disks report the id of a global image the same way.
+                        None => {
+                            spec.disk_source = DiskSource::GlobalImage {
+                                image_id: image_id.to_string(),
+                            }
+                        }
+                    }
+                }
+                DiskSource::Blank { .. } | DiskSource::GlobalImage { .. } => {}
+            }
+            spec
+        })
+        .collect();
+
+    let snapshots = live_snapshots
+        .into_iter()
+        .map(|s| {
+            // Fall back to the id if the disk has since been deleted.
+            let disk = disks
+                .iter()
+                .find(|d| d.id == s.disk_id)
+                .map(|d| d.name.to_string())
+                .unwrap_or(s.disk_id);
+            SnapshotSpec {
+                name: s.name,
+                description: s.description,
+                disk,
+            }
+        })
+        .collect();
+
+    let images = live_images
+        .into_iter()
+        .map(|i| ImageSpec {
+            name: i.name,
+            description: i.description,
+            block_size: i.block_size,
+            source: if i.url.is_empty() {
+                None
+            } else {
+                Some(ImageSource::Url(i.url))
+            },
+        })
+        .collect();
+
+    let mut instances = Vec::new();
+    for instance in client
+        .instances()
+        .get_all(organization_name, project_name, NameSortMode::NameAscending)
+        .await?
+    {
+        let disks = client
+            .instances()
+            .disks_get_all(
+                &instance.name,
+                organization_name,
+                project_name,
+                NameSortMode::NameAscending,
+            )
+            .await?;
+        instances.push(InstanceSpec {
+            name: instance.name,
+            description: instance.description,
+            hostname: instance.hostname,
+            memory: instance.memory,
+            ncpus: instance.ncpus,
+            disks: disks.into_iter().map(|d| d.name).collect(),
+            user_data: String::new(),
+        });
+    }
+
+    Ok(ProjectSpec {
+        name: project.name.to_string(),
+        description: project.description,
+        vpcs,
+        disks: disk_specs,
+        snapshots,
+        images,
+        instances,
+    })
+}
+
+async fn live_vpc(
+    client: &crate::Client,
+    organization_name: &str,
+    project_name: &str,
+    mut spec: VpcSpec,
+) -> Result<VpcSpec> {
+    let vpc_name = spec.name.as_str();
+
+    spec.subnets = client
+        .subnets()
+        .get_all(
+            organization_name,
+            project_name,
+            NameSortMode::NameAscending,
+            vpc_name,
+        )
+        .await?
+        .into_iter()
+        .map(|s| SubnetSpec {
+            name: s.name,
+            description: s.description,
+            ipv4_block: s.ipv4_block,
+            ipv6_block: Some(s.ipv6_block),
+        })
+        .collect();
+
+    let mut routers = Vec::new();
+    for router in client
+        .routers()
+        .get_all(
+            organization_name,
+            project_name,
+            NameSortMode::NameAscending,
+            vpc_name,
+        )
+        .await?
+    {
+        if router.kind == VpcRouterKind::System {
+            continue;
+        }
+
+        let routes = client
+            .routes()
+            .get_all(
+                organization_name,
+                project_name,
+                &router.name,
+                NameSortMode::NameAscending,
+                vpc_name,
+            )
+            .await?
+            .into_iter()
+            .filter(|r| r.kind == RouterRouteKind::Custom)
+            .map(|r| RouteSpec {
+                name: r.name,
+                description: r.description,
+                destination: r.destination,
+                target: r.target,
+            })
+            .collect();
+        routers.push(RouterSpec {
+            name: router.name,
+            description: router.description,
+            routes,
+        });
+    }
+    spec.routers = routers;
+
+    let rules = client
+        .firewall()
+        .rules_get(organization_name, project_name, vpc_name)
+        .await?
+        .rules;
+    let mut rules: Vec<VpcFirewallRuleUpdate> =
+        rules.into_iter().map(VpcFirewallRuleUpdate::from).collect();
+    rules.sort_by(|a, b| a.name.cmp(&b.name));
+    spec.firewall_rules = Some(rules);
+
+    Ok(spec)
+}
+
+impl From<Disk> for DiskSpec {
+    fn from(disk: Disk) -> Self {
+        let disk_source = if !disk.snapshot_id.is_empty() {
+            DiskSource::Snapshot {
+                snapshot_id: disk.snapshot_id,
+            }
+        } else if !disk.image_id.is_empty() {
+            DiskSource::Image {
+                image_id: disk.image_id,
+            }
+        } else {
+            DiskSource::Blank {
+                block_size: disk.block_size as i64,
+            }
+        };
+
+        DiskSpec {
+            name: disk.name,
+            description: disk.description,
+            size: disk.size,
+            disk_source,
+            source_name: None,
+        }
+    }
+}
+
+impl From<VpcFirewallRule> for VpcFirewallRuleUpdate {
+    fn from(rule: VpcFirewallRule) -> Self {
+        VpcFirewallRuleUpdate {
+            name: rule.name,
+            description: rule.description,
+            action: rule.action,
+            direction: rule.direction,
+            filters: rule.filters,
+            priority: rule.priority,
+            status: rule.status,
+            targets: rule.targets,
+        }
+    }
+}
+
+/// The kind of resource a change applies to, in dependency order.
+#[derive(
+    Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    Organization,
+    Project,
+    Vpc,
+    Subnet,
+    Router,
+    Route,
+    FirewallRules,
+    Disk,
+    Snapshot,
+    Image,
+    Instance,
+}
+
+impl ResourceKind {
+    fn has_children(&self) -> bool {
+        matches!(
+            self,
+            ResourceKind::Organization
+                | ResourceKind::Project
+                | ResourceKind::Vpc
+                | ResourceKind::Router
+        )
+    }
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ResourceKind::Organization => "organization",
+            ResourceKind::Project => "project",
+            ResourceKind::Vpc => "vpc",
+            ResourceKind::Subnet => "subnet",
+            ResourceKind::Router => "router",
+            ResourceKind::Route => "route",
+            ResourceKind::FirewallRules => "firewall rules",
+            ResourceKind::Disk => "disk",
+            ResourceKind::Snapshot => "snapshot",
+            ResourceKind::Image => "image",
+            ResourceKind::Instance => "instance",
+        })
+    }
+}
+
+/// What a change does to a resource.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Create,
+    Update,
+    Delete,
+}
+
+impl Action {
+    fn symbol(&self) -> char {
+        match self {
+            Action::Create => '+',
+            Action::Update => '~',
+            Action::Delete => '-',
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Action::Create => "create",
+            Action::Update => "update",
+            Action::Delete => "delete",
+        })
+    }
+}
+
+/// A field that differs between the live and desired state.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct FieldDiff {
+    pub field: String,
+    /// The live value, if the field is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<serde_json::Value>,
+    /// The desired value, if the field is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<serde_json::Value>,
+}
+
+/// A single planned change.
+#[derive(Serialize, Debug, Clone)]
+pub struct Change {
+    pub kind: ResourceKind,
+    pub action: Action,
+    /// The names of the resource and its parents, separated by `/`.
+    pub path: String,
+    pub diff: Vec<FieldDiff>,
+    /// Why the change cannot be applied, if it cannot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsupported: Option<String>,
+    #[serde(skip)]
+    scope: Vec<String>,
+    #[serde(skip)]
+    operation: Option<Operation>,
+}
+
+#[derive(Debug, Clone)]
+enum Operation {
+    CreateOrganization(OrganizationCreate),
+    UpdateOrganization(OrganizationUpdate),
+    CreateProject(ProjectCreate),
+    UpdateProject(ProjectUpdate),
+    DeleteProject,
+    CreateVpc(VpcCreate),
+    UpdateVpc(VpcUpdate),
+    DeleteVpc,
+    CreateSubnet(VpcSubnetCreate),
+    UpdateSubnet(VpcSubnetUpdate),
+    DeleteSubnet,
+    CreateRouter(VpcRouterCreate),
+    UpdateRouter(VpcRouterUpdate),
+    DeleteRouter,
+    CreateRoute(RouterRouteCreateParams),
+    UpdateRoute(RouterRouteUpdateParams),
+    DeleteRoute,
+    PutFirewallRules(VpcFirewallRuleUpdateParams),
+    /// With the name of the snapshot or image to create the disk from.
+    CreateDisk(DiskCreate, Option<String>),
+    DeleteDisk,
+    CreateSnapshot(SnapshotCreate),
+    DeleteSnapshot,
+    CreateImage(ImageCreate),
+    DeleteImage,
+    CreateInstance(InstanceCreate),
+    DeleteInstance,
+}
+
+impl Change {
+    /// Returns whether `other` is a parent of the resource this change applies to.
+    fn is_child_of(&self, other: &Change) -> bool {
+        if self.kind == ResourceKind::FirewallRules && other.kind == ResourceKind::Vpc {
+            return self.scope == other.scope;
+        }
+
+        other.kind.has_children()
+            && self.scope.len() > other.scope.len()
+            && self.scope.starts_with(&other.scope)
+    }
+
+    /// Returns whether this change cannot succeed once `failed` has failed.
+    fn is_blocked_by(&self, failed: &Change) -> bool {
+        self.is_child_of(failed) || (self.action == Action::Delete && failed.is_child_of(self))
+    }
+
+    async fn execute(&self, client: &crate::Client) -> Result<()> {
+        let s = &self.scope;
+        let operation = match &self.operation {
+            Some(operation) => operation,
+            None => {
+                return Err(anyhow!(
+                    "{}",
+                    self.unsupported
+                        .as_deref()
+                        .unwrap_or("the change cannot be applied")
+                ))
+            }
+        };
+
+        match operation {
+            Operation::CreateOrganization(body) => {
+                client.organizations().post(body).await?;
+            }
+            Operation::UpdateOrganization(body) => {
+                client.organizations().put(&s[0], body).await?;
+            }
+            Operation::CreateProject(body) => {
+                client.projects().post(&s[0], body).await?;
+            }
+            Operation::UpdateProject(body) => {
+                client.projects().put(&s[0], &s[1], body).await?;
+            }
+            Operation::DeleteProject => client.projects().delete(&s[0], &s[1]).await?,
+            Operation::CreateVpc(body) => {
+                client.vpcs().post(&s[0], &s[1], body).await?;
+            }
+            Operation::UpdateVpc(body) => {
+                client.vpcs().put(&s[0], &s[1], &s[2], body).await?;
+            }
+            Operation::DeleteVpc => client.vpcs().delete(&s[0], &s[1], &s[2]).await?,
+            Operation::CreateSubnet(body) => {
+                client.subnets().post(&s[0], &s[1], &s[2], body).await?;
+            }
+            Operation::UpdateSubnet(body) => {
+                client
+                    .subnets()
+                    .put(&s[0], &s[1], &s[3], &s[2], body)
+                    .await?;
+            }
+            Operation::DeleteSubnet => client.subnets().delete(&s[0], &s[1], &s[3], &s[2]).await?,
+            Operation::CreateRouter(body) => {
+                client.routers().post(&s[0], &s[1], &s[2], body).await?;
+            }
+            Operation::UpdateRouter(body) => {
+                client
+                    .routers()
+                    .put(&s[0], &s[1], &s[3], &s[2], body)
+                    .await?;
+            }
+            Operation::DeleteRouter => client.routers().delete(&s[0], &s[1], &s[3], &s[2]).await?,
+            Operation::CreateRoute(body) => {
+                client
+                    .routes()
+                    .post(&s[0], &s[1], &s[3], &s[2], body)
+                    .await?;
+            }
+            Operation::UpdateRoute(body) => {
+                client
+                    .routes()
+                    .put(&s[0], &s[1], &s[4], &s[3], &s[2], body)
+                    .await?;
+            }
+            Operation::DeleteRoute => {
+                client
+                    .routes()
+                    .delete(&s[0], &s[1], &s[4], &s[3], &s[2])
+                    .await?
+            }
+            Operation::PutFirewallRules(body) => {
+                client
+                    .firewall()
+                    .rules_put(&s[0], &s[1], &s[2], body)
+                    .await?;
+            }
+            Operation::CreateDisk(body, None) => {
+                client.disks().post(&s[0], &s[1], body).await?;
+            }
+            Operation::CreateDisk(body, Some(source_name)) => {
+                let disk_source = match &body.disk_source {
+                    DiskSource::Snapshot { .. } => DiskSource::Snapshot {
+                        snapshot_id: client.snapshots().get(&s[0], &s[1], source_name).await?.id,
+                    },
+                    DiskSource::Image { .. } => DiskSource::Image {
+                        image_id: client.images().get(source_name, &s[0], &s[1]).await?.id,
+                    },
+                    other => other.clone(),
+                };
+                let body = DiskCreate {
+                    disk_source,
+                    ..body.clone()
+                };
+                client.disks().post(&s[0], &s[1], &body).await?;
+            }
+            Operation::DeleteDisk => client.disks().delete(&s[2], &s[0], &s[1]).await?,
+            Operation::CreateSnapshot(body) => {
+                client.snapshots().post(&s[0], &s[1], body).await?;
+            }
+            Operation::DeleteSnapshot => client.snapshots().delete(&s[0], &s[1], &s[2]).await?,
+            Operation::CreateImage(body) => {
+                client.images().post(&s[0], &s[1], body).await?;
+            }
+            Operation::DeleteImage => client.images().delete(&s[2], &s[0], &s[1]).await?,
+            Operation::CreateInstance(body) => {
+                client.instances().post(&s[0], &s[1], body).await?;
+            }
+            Operation::DeleteInstance => client.instances().delete(&s[2], &s[0], &s[1]).await?,
+        }
+
+        Ok(())
+    }
+}
+
+/// A resource that can be declared in a manifest.
+trait Resource: Serialize {
+    const KIND: ResourceKind;
+    /// Fields that hold child resources or are only used at creation, and so
+    /// are never compared.
+    const IGNORED: &'static [&'static str] = &[];
+    /// Fields assigned by the system when the manifest leaves them unset.
+    const DEFAULTED: &'static [&'static str] = &[];
+    /// Fields that can be changed in place.
+    const MUTABLE: &'static [&'static str] = &[];
+
+    fn name(&self) -> &str;
+    fn create(&self) -> Result<Operation, String>;
+    fn update(&self) -> Option<Operation> {
+        None
+    }
+    fn delete() -> Option<Operation>;
+}
+
+impl Resource for OrganizationSpec {
+    const KIND: ResourceKind = ResourceKind::Organization;
+    const IGNORED: &'static [&'static str] = &["projects", "partial"];
+    const MUTABLE: &'static [&'static str] = &["description"];
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create(&self) -> Result<Operation, String> {
+        Ok(Operation::CreateOrganization(OrganizationCreate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+        }))
+    }
+
+    fn update(&self) -> Option<Operation> {
+        Some(Operation::UpdateOrganization(OrganizationUpdate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+        }))
+    }
+
+    fn delete() -> Option<Operation> {
+        None
+    }
+}
+
+impl Resource for ProjectSpec {
+    const KIND: ResourceKind = ResourceKind::Project;
+    const IGNORED: &'static [&'static str] = &["vpcs", "disks", "snapshots", "images", "instances"];
+    const MUTABLE: &'static [&'static str] = &["description"];
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create(&self) -> Result<Operation, String> {
+        Ok(Operation::CreateProject(ProjectCreate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+        }))
+    }
+
+    fn update(&self) -> Option<Operation> {
+        Some(Operation::UpdateProject(ProjectUpdate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+        }))
+    }
+
+    fn delete() -> Option<Operation> {
+        Some(Operation::DeleteProject)
+    }
+}
+
+impl Resource for VpcSpec {
+    const KIND: ResourceKind = ResourceKind::Vpc;
+    const IGNORED: &'static [&'static str] = &["subnets", "routers", "firewall_rules"];
+    const DEFAULTED: &'static [&'static str] = &["ipv6_prefix"];
+    const MUTABLE: &'static [&'static str] = &["description", "dns_name"];
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create(&self) -> Result<Operation, String> {
+        Ok(Operation::CreateVpc(VpcCreate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            dns_name: self.dns_name.to_string(),
+            ipv6_prefix: self.ipv6_prefix,
+        }))
+    }
+
+    fn update(&self) -> Option<Operation> {
+        Some(Operation::UpdateVpc(VpcUpdate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            dns_name: self.dns_name.to_string(),
+        }))
+    }
+
+    fn delete() -> Option<Operation> {
+        Some(Operation::DeleteVpc)
+    }
+}
+
+impl Resource for SubnetSpec {
+    const KIND: ResourceKind = ResourceKind::Subnet;
+    const DEFAULTED: &'static [&'static str] = &["ipv6_block"];
+    const MUTABLE: &'static [&'static str] = &["description"];
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create(&self) -> Result<Operation, String> {
+        Ok(Operation::CreateSubnet(VpcSubnetCreate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            ipv4_block: self.ipv4_block,
+            ipv6_block: self.ipv6_block,
+        }))
+    }
+
+    fn update(&self) -> Option<Operation> {
+        Some(Operation::UpdateSubnet(VpcSubnetUpdate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+        }))
+    }
+
+    fn delete() -> Option<Operation> {
+        Some(Operation::DeleteSubnet)
+    }
+}
+
+impl Resource for RouterSpec {
+    const KIND: ResourceKind = ResourceKind::Router;
+    const IGNORED: &'static [&'static str] = &["routes"];
+    const MUTABLE: &'static [&'static str] = &["description"];
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create(&self) -> Result<Operation, String> {
+        Ok(Operation::CreateRouter(VpcRouterCreate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+        }))
+    }
+
+    fn update(&self) -> Option<Operation> {
+        Some(Operation::UpdateRouter(VpcRouterUpdate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+        }))
+    }
+
+    fn delete() -> Option<Operation> {
+        Some(Operation::DeleteRouter)
+    }
+}
+
+impl Resource for RouteSpec {
+    const KIND: ResourceKind = ResourceKind::Route;
+    const MUTABLE: &'static [&'static str] = &["description", "destination", "target"];
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create(&self) -> Result<Operation, String> {
+        Ok(Operation::CreateRoute(RouterRouteCreateParams {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            destination: self.destination.clone(),
+            target: self.target.clone(),
+        }))
+    }
+
+    fn update(&self) -> Option<Operation> {
+        Some(Operation::UpdateRoute(RouterRouteUpdateParams {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            destination: self.destination.clone(),
+            target: self.target.clone(),
+        }))
+    }
+
+    fn delete() -> Option<Operation> {
+        Some(Operation::DeleteRoute)
+    }
+}
+
+impl Resource for DiskSpec {
+    const KIND: ResourceKind = ResourceKind::Disk;
+    const IGNORED: &'static [&'static str] = &["disk_source", "source_name"];
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create(&self) -> Result<Operation, String> {
+        Ok(Operation::CreateDisk(
+            DiskCreate {
+                name: self.name.to_string(),
+                description: self.description.to_string(),
+                disk_source: self.disk_source.clone(),
+                size: self.size,
+            },
+            self.source_name.clone(),
+        ))
+    }
+
+    fn delete() -> Option<Operation> {
+        Some(Operation::DeleteDisk)
+    }
+}
+
+impl Resource for SnapshotSpec {
+    const KIND: ResourceKind = ResourceKind::Snapshot;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create(&self) -> Result<Operation, String> {
+        Ok(Operation::CreateSnapshot(SnapshotCreate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            disk: self.disk.to_string(),
+        }))
+    }
+
+    fn delete() -> Option<Operation> {
+        Some(Operation::DeleteSnapshot)
+    }
+}
+
+impl Resource for ImageSpec {
+    const KIND: ResourceKind = ResourceKind::Image;
+    const IGNORED: &'static [&'static str] = &["source"];
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create(&self) -> Result<Operation, String> {
+        let source = self
+            .source
+            .clone()
+            .ok_or_else(|| "image cannot be created without a source".to_string())?;
+
+        Ok(Operation::CreateImage(ImageCreate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            block_size: self.block_size as i64,
+            source,
+        }))
+    }
+
+    fn delete() -> Option<Operation> {
+        Some(Operation::DeleteImage)
+    }
+}
+
+impl Resource for InstanceSpec {
+    const KIND: ResourceKind = ResourceKind::Instance;
+    const IGNORED: &'static [&'static str] = &["disks", "user_data"];
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create(&self) -> Result<Operation, String> {
+        Ok(Operation::CreateInstance(InstanceCreate {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            disks: self
+                .disks
+                .iter()
+                .map(|d| InstanceDiskAttachment::Attach(d.to_string()))
+                .collect(),
+            hostname: self.hostname.to_string(),
+            memory: self.memory,
+            ncpus: self.ncpus,
+            network_interfaces: Some(InstanceNetworkInterfaceAttachment::Default),
+            user_data: self.user_data.to_string(),
+        }))
+    }
+
+    fn delete() -> Option<Operation> {
+        Some(Operation::DeleteInstance)
+    }
+}
+
+/// The changes needed to bring the live state in line with a manifest.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct Plan {
+    /// The changes, in the order they will be applied.
+    pub changes: Vec<Change>,
+}
+
+impl Plan {
+    /// Compute the changes needed to turn `live` into `desired`.
+    ///
+    /// Only the organizations declared in `desired` are considered.
+    pub fn new(desired: &Manifest, live: &Manifest) -> Self {
+        let mut changes = Vec::new();
+        for organization in &desired.organizations {
+            let live = live
+                .organizations
+                .iter()
+                .find(|o| o.name == organization.name);
+            let scope = match live {
+                Some(_) if organization.partial => vec![organization.name.to_string()],
+                _ => diff_resource(&mut changes, &[], Some(organization), live),
+            };
+            for (d, l) in pair(
+                &organization.projects,
+                live.map(|o| o.projects.as_slice()).unwrap_or_default(),
+            ) {
+                if d.is_none() && organization.partial {
+                    continue;
+                }
+                diff_project(&mut changes, &scope, d, l);
+            }
+        }
+
+        Plan::sorted(changes)
+    }
+
+    /// Compute the changes needed to turn `live` into `desired` for a single
+    /// project in an existing organization, leaving the organization's other
+    /// projects alone.
+    pub fn for_project(
+        organization_name: &str,
+        desired: &ProjectSpec,
+        live: Option<&ProjectSpec>,
+    ) -> Self {
+        let mut changes = Vec::new();
+        diff_project(
+            &mut changes,
+            &[organization_name.to_string()],
+            Some(desired),
+            live,
+        );
+
+        Plan::sorted(changes)
+    }
+
+    fn sorted(mut changes: Vec<Change>) -> Self {
+        // Deletes run first, children before parents, so that names are free
+        // to be reused by the creates that follow. Disks created from a
+        // snapshot or image by name wait for the snapshots and images.
+        changes.sort_by_key(|c| match (c.action, &c.operation) {
+            (Action::Delete, _) => (0, -(c.kind as i8), 0),
+            (_, Some(Operation::CreateDisk(_, Some(_)))) => (1, ResourceKind::Image as i8, 1),
+            (Action::Create | Action::Update, _) => (1, c.kind as i8, 0),
+        });
+
+        Plan { changes }
+    }
+
+    /// Returns whether the live state already matches the manifest.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Render the plan as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Apply the changes in order.
+    ///
+    /// A failed change does not stop the others, but changes that depend on
+    /// it are skipped.
+    pub async fn apply(&self, client: &crate::Client) -> ApplyReport {
+        let mut failed: Vec<&Change> = Vec::new();
+        let mut results = Vec::with_capacity(self.changes.len());
+        for change in &self.changes {
+            let outcome = if let Some(f) = failed.iter().copied().find(|f| change.is_blocked_by(f))
+            {
+                failed.push(change);
+                Outcome::Skipped {
+                    reason: format!("{} `{}` failed", f.kind, f.path),
+                }
+            } else {
+                match change.execute(client).await {
+                    Ok(()) => Outcome::Applied,
+                    Err(e) => {
+                        failed.push(change);
+                        Outcome::Failed {
+                            error: format!("{:#}", e),
+                        }
+                    }
+                }
+            };
+
+            results.push(ChangeResult {
+                kind: change.kind,
+                action: change.action,
+                path: change.path.to_string(),
+                outcome,
+            });
+        }
+
+        ApplyReport { results }
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(
+                f,
+                "{} {} {}",
+                change.action.symbol(),
+                change.kind,
+                change.path
+            )?;
+            if change.action == Action::Delete {
+                continue;
+            }
+            for d in &change.diff {
+                match (&d.before, &d.after, change.action) {
+                    (_, Some(after), Action::Create) => writeln!(f, "    {}: {}", d.field, after)?,
+                    (Some(before), Some(after), _) => {
+                        writeln!(f, "    {}: {} -> {}", d.field, before, after)?
+                    }
+                    (Some(before), None, _) => {
+                        writeln!(f, "    {}: {} -> (unset)", d.field, before)?
+                    }
+                    (None, Some(after), _) => writeln!(f, "    {}: (unset) -> {}", d.field, after)?,
+                    (None, None, _) => {}
+                }
+            }
+            if let Some(reason) = &change.unsupported {
+                writeln!(f, "    ! {}", reason)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn diff_project(
+    changes: &mut Vec<Change>,
+    parents: &[String],
+    desired: Option<&ProjectSpec>,
+    live: Option<&ProjectSpec>,
+) {
+    let scope = diff_resource(changes, parents, desired, live);
+
+    for (d, l) in pair(
+        desired.map(|p| p.vpcs.as_slice()).unwrap_or_default(),
+        live.map(|p| p.vpcs.as_slice()).unwrap_or_default(),
+    ) {
+        diff_vpc(changes, &scope, d, l);
+    }
+    for (d, l) in pair(
+        desired.map(|p| p.disks.as_slice()).unwrap_or_default(),
+        live.map(|p| p.disks.as_slice()).unwrap_or_default(),
+    ) {
+        diff_resource(changes, &scope, d, l);
+    }
+    for (d, l) in pair(
+        desired.map(|p| p.snapshots.as_slice()).unwrap_or_default(),
+        live.map(|p| p.snapshots.as_slice()).unwrap_or_default(),
+    ) {
+        diff_resource(changes, &scope, d, l);
+    }
+    for (d, l) in pair(
+        desired.map(|p| p.images.as_slice()).unwrap_or_default(),
+        live.map(|p| p.images.as_slice()).unwrap_or_default(),
+    ) {
+        diff_resource(changes, &scope, d, l);
+    }
+    for (d, l) in pair(
+        desired.map(|p| p.instances.as_slice()).unwrap_or_default(),
+        live.map(|p| p.instances.as_slice()).unwrap_or_default(),
+    ) {
+        diff_resource(changes, &scope, d, l);
+    }
+}
+
+/// The subnet the system creates with each VPC.
+const DEFAULT_SUBNET: &str = "default";
+
+fn diff_vpc(
+    changes: &mut Vec<Change>,
+    parents: &[String],
+    desired: Option<&VpcSpec>,
+    live: Option<&VpcSpec>,
+) {
+    let scope = diff_resource(changes, parents, desired, live);
+
+    for (d, l) in pair(
+        desired.map(|v| v.subnets.as_slice()).unwrap_or_default(),
+        live.map(|v| v.subnets.as_slice()).unwrap_or_default(),
+    ) {
+        if d.is_none() && desired.is_some() && l.map(|l| l.name.as_str()) == Some(DEFAULT_SUBNET) {
+            continue;
+        }
+        diff_resource(changes, &scope, d, l);
+    }
+    for (d, l) in pair(
+        desired.map(|v| v.routers.as_slice()).unwrap_or_default(),
+        live.map(|v| v.routers.as_slice()).unwrap_or_default(),
+    ) {
+        let router = diff_resource(changes, &scope, d, l);
+        for (d, l) in pair(
+            d.map(|r| r.routes.as_slice()).unwrap_or_default(),
+            l.map(|r| r.routes.as_slice()).unwrap_or_default(),
+        ) {
+            diff_resource(changes, &router, d, l);
+        }
+    }
+
+    if let Some(rules) = desired.and_then(|v| v.firewall_rules.as_ref()) {
+        let live_rules = live
+            .and_then(|v| v.firewall_rules.as_deref())
+            .unwrap_or_default();
+        let diff = diff_maps(
+            live_rules
+                .iter()
+                .map(|r| (r.name.to_string(), to_value(r)))
+                .collect(),
+            rules
+                .iter()
+                .map(|r| (r.name.to_string(), to_value(r)))
+                .collect(),
+        );
+        // A new VPC comes with default rules, which are replaced even if the
+        // manifest declares none.
+        if !diff.is_empty() || live.is_none() {
+            changes.push(Change {
+                kind: ResourceKind::FirewallRules,
+                action: Action::Update,
+                path: scope.join("/"),
+                diff,
+                unsupported: None,
+                scope,
+                operation: Some(Operation::PutFirewallRules(VpcFirewallRuleUpdateParams {
+                    rules: rules.clone(),
+                })),
+            });
+        }
+    }
+}
+
+/// Record the change, if any, needed to turn `live` into `desired` and return
+/// the scope of the resource.
+fn diff_resource<T: Resource>(
+    changes: &mut Vec<Change>,
+    parents: &[String],
+    desired: Option<&T>,
+    live: Option<&T>,
+) -> Vec<String> {
+    let mut scope = parents.to_vec();
+    scope.push(
+        desired
+            .or(live)
+            .map(T::name)
+            .unwrap_or_default()
+            .to_string(),
+    );
+
+    let mut before = fields(live, T::IGNORED);
+    let after = fields(desired, T::IGNORED);
+    if desired.is_some() {
+        // Fields the manifest leaves to the system are not drift.
+        for field in T::DEFAULTED {
+            if !after.contains_key(*field) {
+                before.remove(*field);
+            }
+        }
+    }
+    let diff = diff_maps(before, after);
+
+    let (action, operation, unsupported) = match (desired, live) {
+        (Some(d), None) => match d.create() {
+            Ok(operation) => (Action::Create, Some(operation), None),
+            Err(reason) => (Action::Create, None, Some(reason)),
+        },
+        (Some(_), Some(_)) if diff.is_empty() => return scope,
+        (Some(d), Some(_)) => {
+            let immutable: Vec<&str> = diff
+                .iter()
+                .map(|f| f.field.as_str())
+                .filter(|f| !T::MUTABLE.contains(f))
+                .collect();
+            if immutable.is_empty() {
+                (Action::Update, d.update(), None)
+            } else {
+                (
+                    Action::Update,
+                    None,
+                    Some(format!(
+                        "{} cannot be changed in place: {}",
+                        T::KIND,
+                        immutable.join(", ")
+                    )),
+                )
+            }
+        }
+        (None, Some(_)) => match T::delete() {
+            Some(operation) => (Action::Delete, Some(operation), None),
+            None => return scope,
+        },
+        (None, None) => return scope,
+    };
+
+    changes.push(Change {
+        kind: T::KIND,
+        action,
+        path: scope.join("/"),
+        diff: if action == Action::Delete {
+            Vec::new()
+        } else {
+            diff
+        },
+        unsupported,
+        scope: scope.clone(),
+        operation,
+    });
+
+    scope
+}
+
+/// Match desired and live resources by name. Desired resources come first,
+/// in order, followed by live resources that are not declared.
+fn pair<'a, T: Resource>(desired: &'a [T], live: &'a [T]) -> Vec<(Option<&'a T>, Option<&'a T>)> {
+    let mut pairs: Vec<_> = desired
+        .iter()
+        .map(|d| (Some(d), live.iter().find(|l| l.name() == d.name())))
+        .collect();
+    pairs.extend(
+        live.iter()
+            .filter(|l| !desired.iter().any(|d| d.name() == l.name()))
+            .map(|l| (None, Some(l))),
+    );
+    pairs
+}
+
+type Fields = serde_json::Map<String, serde_json::Value>;
+
+fn to_value<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or(serde_json::Value::Null)
+}
+
+fn fields<T: Serialize>(resource: Option<&T>, ignored: &[&str]) -> Fields {
+    match resource.map(to_value) {
+        Some(serde_json::Value::Object(mut fields)) => {
+            for field in ignored {
+                fields.remove(*field);
+            }
+            fields
+        }
+        _ => Fields::new(),
+    }
+}
+
+fn diff_maps(before: Fields, after: Fields) -> Vec<FieldDiff> {
+    let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    keys.into_iter()
+        .filter(|k| before.get(*k) != after.get(*k))
+        .map(|k| FieldDiff {
+            field: k.to_string(),
+            before: before.get(k).cloned(),
+            after: after.get(k).cloned(),
+        })
+        .collect()
+}
+
+/// How a change turned out.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum Outcome {
+    Applied,
+    Failed {
+        error: String,
+    },
+    /// The change was not attempted because a change it depends on failed.
+    Skipped {
+        reason: String,
+    },
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Applied => write!(f, "applied"),
+            Outcome::Failed { error } => write!(f, "failed: {}", error),
+            Outcome::Skipped { reason } => write!(f, "skipped: {}", reason),
+        }
+    }
+}
+
+/// The outcome of a single change.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Tabled)]
+pub struct ChangeResult {
+    pub kind: ResourceKind,
+    pub action: Action,
+    pub path: String,
+    pub outcome: Outcome,
+}
+
+/// The outcome of applying a plan, per resource.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+pub struct ApplyReport {
+    pub results: Vec<ChangeResult>,
+}
+
+impl ApplyReport {
+    /// Returns whether every change was applied.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|r| r.outcome == Outcome::Applied)
+    }
+
+    /// The changes that failed or were skipped.
+    pub fn failures(&self) -> Vec<&ChangeResult> {
+        self.results
+            .iter()
+            .filter(|r| r.outcome != Outcome::Applied)
+            .collect()
+    }
+
+    /// Render the report as a table.
+    pub fn to_table(&self) -> String {
+        tabled::Table::new(&self.results).to_string()
+    }
+
+    /// Render the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
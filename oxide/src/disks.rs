@@ -27,6 +27,23 @@ impl Disks {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_disks_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_disks_get",
+                tag = "disks",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/disks",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -53,7 +70,8 @@ impl Disks {
             query_
         );
 
-        let resp: crate::types::DiskResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::DiskResultsPage =
+            self.client.get("project_disks_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -66,6 +84,23 @@ impl Disks {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_disks_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_disks_get",
+                tag = "disks",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/disks",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         organization_name: &str,
@@ -84,7 +119,8 @@ impl Disks {
             query_
         );
 
-        let mut resp: crate::types::DiskResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::DiskResultsPage =
+            self.client.get("project_disks_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -94,12 +130,12 @@ impl Disks {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("project_disks_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("project_disks_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -126,6 +162,23 @@ impl Disks {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_disks_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_disks_post",
+                tag = "disks",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/disks",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(
         &self,
         organization_name: &str,
@@ -139,10 +192,77 @@ impl Disks {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "project_disks_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create a disk in a project.
+     *
+     * This function performs a `POST` to the `/organizations/{organization_name}/projects/{project_name}/disks` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/organizations/{organization_name}/projects/{project_name}/disks/{disk_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     *
+     * **Parameters:**
+     *
+     * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_disks_ensure",
+            skip_all,
+            err,
+            fields(
+                tag = "disks",
+                path = "/organizations/{organization_name}/projects/{project_name}/disks",
+            )
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        body: &crate::types::DiskCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::Disk>> {
+        match self.post(organization_name, project_name, body).await {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let disk_name = body.name.as_str();
+                let url = format!(
+                    "/organizations/{}/projects/{}/disks/{}",
+                    crate::progenitor_support::encode_path(organization_name),
+                    crate::progenitor_support::encode_path(project_name),
+                    crate::progenitor_support::encode_path(disk_name),
+                );
+                let get = self.client.get("project_disks_get_disk", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "project_disks_get_disk",
+                        operation_id = "project_disks_get_disk",
+                        tag = "disks",
+                        method = "GET",
+                        path = "/organizations/{organization_name}/projects/{project_name}/disks/\
+                                {disk_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Fetch a single disk in a project.
      *
@@ -154,6 +274,23 @@ impl Disks {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_disks_get_disk",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_disks_get_disk",
+                tag = "disks",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/disks/{disk_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(
         &self,
         disk_name: &str,
@@ -167,7 +304,7 @@ impl Disks {
             crate::progenitor_support::encode_path(disk_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("project_disks_get_disk", &url, None).await
     }
 
     /**
@@ -181,6 +318,23 @@ impl Disks {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_disks_delete_disk",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_disks_delete_disk",
+                tag = "disks",
+                method = "DELETE",
+                path = "/organizations/{organization_name}/projects/{project_name}/disks/{disk_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(
         &self,
         disk_name: &str,
@@ -194,6 +348,8 @@ impl Disks {
             crate::progenitor_support::encode_path(disk_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("project_disks_delete_disk", &url, None)
+            .await
     }
 }
@@ -23,6 +23,23 @@ impl Firewall {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_firewall_rules_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_firewall_rules_get",
+                tag = "firewall",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/firewall/rules",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn rules_get(
         &self,
         organization_name: &str,
@@ -36,7 +53,7 @@ impl Firewall {
             crate::progenitor_support::encode_path(vpc_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("vpc_firewall_rules_get", &url, None).await
     }
 
     /**
@@ -50,6 +67,23 @@ impl Firewall {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_firewall_rules_put",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_firewall_rules_put",
+                tag = "firewall",
+                method = "PUT",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/firewall/rules",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn rules_put(
         &self,
         organization_name: &str,
@@ -65,7 +99,11 @@ impl Firewall {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "vpc_firewall_rules_put",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 }
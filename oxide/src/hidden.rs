@@ -15,19 +15,57 @@ impl Hidden {
     /**
      * This function performs a `POST` to the `/login` endpoint.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "spoof_login",
+            skip_all,
+            err,
+            fields(
+                operation_id = "spoof_login",
+                tag = "hidden",
+                method = "POST",
+                path = "/login",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn spoof_login(&self, body: &crate::types::LoginParams) -> Result<()> {
         let url = "/login".to_string();
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "spoof_login",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
     /**
      * This function performs a `POST` to the `/logout` endpoint.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "logout",
+            skip_all,
+            err,
+            fields(
+                operation_id = "logout",
+                tag = "hidden",
+                method = "POST",
+                path = "/logout",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn logout(&self) -> Result<()> {
         let url = "/logout".to_string();
-        self.client.post(&url, None).await
+        self.client.post("logout", &url, None).await
     }
 
     /**
@@ -35,8 +73,25 @@ impl Hidden {
      *
      * This function performs a `GET` to the `/session/me` endpoint.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "session_me",
+            skip_all,
+            err,
+            fields(
+                operation_id = "session_me",
+                tag = "hidden",
+                method = "GET",
+                path = "/session/me",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn session_me(&self) -> Result<crate::types::SessionUser> {
         let url = "/session/me".to_string();
-        self.client.get(&url, None).await
+        self.client.get("session_me", &url, None).await
     }
 }
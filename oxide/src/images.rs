@@ -29,6 +29,23 @@ impl Images {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_images_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_images_get",
+                tag = "images",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/images",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -55,7 +72,8 @@ impl Images {
             query_
         );
 
-        let resp: crate::types::ImageResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::ImageResultsPage =
+            self.client.get("project_images_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -70,6 +88,23 @@ impl Images {
      *
      * List images in a project. The images are returned sorted by creation date, with the most recent images appearing first.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_images_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_images_get",
+                tag = "images",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/images",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         organization_name: &str,
@@ -88,7 +123,8 @@ impl Images {
             query_
         );
 
-        let mut resp: crate::types::ImageResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::ImageResultsPage =
+            self.client.get("project_images_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -98,12 +134,20 @@ impl Images {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "project_images_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "project_images_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
@@ -132,6 +176,23 @@ impl Images {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_images_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_images_post",
+                tag = "images",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/images",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(
         &self,
         organization_name: &str,
@@ -145,10 +206,79 @@ impl Images {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "project_images_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create an image.
+     *
+     * This function performs a `POST` to the `/organizations/{organization_name}/projects/{project_name}/images` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/organizations/{organization_name}/projects/{project_name}/images/{image_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     *
+     * Create a new image in a project.
+     *
+     * **Parameters:**
+     *
+     * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_images_ensure",
+            skip_all,
+            err,
+            fields(
+                tag = "images",
+                path = "/organizations/{organization_name}/projects/{project_name}/images",
+            )
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        body: &crate::types::ImageCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::Image>> {
+        match self.post(organization_name, project_name, body).await {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let image_name = body.name.as_str();
+                let url = format!(
+                    "/organizations/{}/projects/{}/images/{}",
+                    crate::progenitor_support::encode_path(organization_name),
+                    crate::progenitor_support::encode_path(project_name),
+                    crate::progenitor_support::encode_path(image_name),
+                );
+                let get = self.client.get("project_images_get_image", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "project_images_get_image",
+                        operation_id = "project_images_get_image",
+                        tag = "images",
+                        method = "GET",
+                        path = "/organizations/{organization_name}/projects/{project_name}/images/\
+                                {image_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Get an image.
      *
@@ -162,6 +292,23 @@ impl Images {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_images_get_image",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_images_get_image",
+                tag = "images",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/images/{image_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(
         &self,
         image_name: &str,
@@ -175,7 +322,9 @@ impl Images {
             crate::progenitor_support::encode_path(image_name),
         );
 
-        self.client.get(&url, None).await
+        self.client
+            .get("project_images_get_image", &url, None)
+            .await
     }
 
     /**
@@ -191,6 +340,23 @@ impl Images {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_images_delete_image",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_images_delete_image",
+                tag = "images",
+                method = "DELETE",
+                path = "/organizations/{organization_name}/projects/{project_name}/images/{image_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(
         &self,
         image_name: &str,
@@ -204,6 +370,8 @@ impl Images {
             crate::progenitor_support::encode_path(image_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("project_images_delete_image", &url, None)
+            .await
     }
 }
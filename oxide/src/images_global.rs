@@ -27,6 +27,23 @@ impl ImagesGlobal {
      *  
      *  Currently, we only support scanning in ascending order.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "images_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "images_get",
+                tag = "images_global",
+                method = "GET",
+                path = "/images",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn images_get(
         &self,
         limit: u32,
@@ -46,7 +63,8 @@ impl ImagesGlobal {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/images?{}", query_);
 
-        let resp: crate::types::GlobalImageResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::GlobalImageResultsPage =
+            self.client.get("images_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -61,6 +79,23 @@ impl ImagesGlobal {
      *
      * Returns a list of all the global images. Global images are returned sorted by creation date, with the most recent images appearing first.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "images_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "images_get",
+                tag = "images_global",
+                method = "GET",
+                path = "/images",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn images_get_all(
         &self,
         sort_by: crate::types::NameSortMode,
@@ -72,7 +107,8 @@ impl ImagesGlobal {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/images?{}", query_);
 
-        let mut resp: crate::types::GlobalImageResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::GlobalImageResultsPage =
+            self.client.get("images_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -82,12 +118,12 @@ impl ImagesGlobal {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("images_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("images_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -111,13 +147,34 @@ impl ImagesGlobal {
      *
      * Create a new global image. This image can then be used by any user as a base for instances.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "images_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "images_post",
+                tag = "images_global",
+                method = "POST",
+                path = "/images",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn images_post(
         &self,
         body: &crate::types::ImageCreate,
     ) -> Result<crate::types::GlobalImage> {
         let url = "/images".to_string();
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "images_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -132,13 +189,30 @@ impl ImagesGlobal {
      *
      * * `image_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "images_get_image",
+            skip_all,
+            err,
+            fields(
+                operation_id = "images_get_image",
+                tag = "images_global",
+                method = "GET",
+                path = "/images/{image_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn images_get_image(&self, image_name: &str) -> Result<crate::types::GlobalImage> {
         let url = format!(
             "/images/{}",
             crate::progenitor_support::encode_path(image_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("images_get_image", &url, None).await
     }
 
     /**
@@ -152,12 +226,29 @@ impl ImagesGlobal {
      *
      * * `image_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "images_delete_image",
+            skip_all,
+            err,
+            fields(
+                operation_id = "images_delete_image",
+                tag = "images_global",
+                method = "DELETE",
+                path = "/images/{image_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn images_delete_image(&self, image_name: &str) -> Result<()> {
         let url = format!(
             "/images/{}",
             crate::progenitor_support::encode_path(image_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client.delete("images_delete_image", &url, None).await
     }
 }
@@ -27,6 +27,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_instances_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_instances_get",
+                tag = "instances",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -53,7 +70,8 @@ impl Instances {
             query_
         );
 
-        let resp: crate::types::InstanceResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::InstanceResultsPage =
+            self.client.get("project_instances_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -66,6 +84,23 @@ impl Instances {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_instances_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_instances_get",
+                tag = "instances",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         organization_name: &str,
@@ -84,7 +119,8 @@ impl Instances {
             query_
         );
 
-        let mut resp: crate::types::InstanceResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::InstanceResultsPage =
+            self.client.get("project_instances_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -94,12 +130,20 @@ impl Instances {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "project_instances_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "project_instances_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
@@ -126,6 +170,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_instances_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_instances_post",
+                tag = "instances",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(
         &self,
         organization_name: &str,
@@ -139,10 +200,79 @@ impl Instances {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "project_instances_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create an instance in a project.
+     *
+     * This function performs a `POST` to the `/organizations/{organization_name}/projects/{project_name}/instances` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     *
+     * **Parameters:**
+     *
+     * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_instances_ensure",
+            skip_all,
+            err,
+            fields(
+                tag = "instances",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances",
+            )
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        body: &crate::types::InstanceCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::Instance>> {
+        match self.post(organization_name, project_name, body).await {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let instance_name = body.name.as_str();
+                let url = format!(
+                    "/organizations/{}/projects/{}/instances/{}",
+                    crate::progenitor_support::encode_path(organization_name),
+                    crate::progenitor_support::encode_path(project_name),
+                    crate::progenitor_support::encode_path(instance_name),
+                );
+                let get = self
+                    .client
+                    .get("project_instances_get_instance", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "project_instances_get_instance",
+                        operation_id = "project_instances_get_instance",
+                        tag = "instances",
+                        method = "GET",
+                        path = "/organizations/{organization_name}/projects/{project_name}/\
+                                instances/{instance_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Get an instance in a project.
      *
@@ -154,6 +284,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_instances_get_instance",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_instances_get_instance",
+                tag = "instances",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(
         &self,
         instance_name: &str,
@@ -167,7 +314,9 @@ impl Instances {
             crate::progenitor_support::encode_path(instance_name),
         );
 
-        self.client.get(&url, None).await
+        self.client
+            .get("project_instances_get_instance", &url, None)
+            .await
     }
 
     /**
@@ -181,6 +330,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_instances_delete_instance",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_instances_delete_instance",
+                tag = "instances",
+                method = "DELETE",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(
         &self,
         instance_name: &str,
@@ -194,7 +360,9 @@ impl Instances {
             crate::progenitor_support::encode_path(instance_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("project_instances_delete_instance", &url, None)
+            .await
     }
 
     /**
@@ -213,6 +381,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "instance_disks_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "instance_disks_get",
+                tag = "instances",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/disks",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn disks_get(
         &self,
         instance_name: &str,
@@ -241,7 +426,8 @@ impl Instances {
             query_
         );
 
-        let resp: crate::types::DiskResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::DiskResultsPage =
+            self.client.get("instance_disks_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -254,6 +440,23 @@ impl Instances {
      *
      * As opposed to `disks_get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "instance_disks_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "instance_disks_get",
+                tag = "instances",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/disks",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn disks_get_all(
         &self,
         instance_name: &str,
@@ -274,7 +477,8 @@ impl Instances {
             query_
         );
 
-        let mut resp: crate::types::DiskResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::DiskResultsPage =
+            self.client.get("instance_disks_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -284,12 +488,20 @@ impl Instances {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "instance_disks_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "instance_disks_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
@@ -315,6 +527,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "instance_disks_attach",
+            skip_all,
+            err,
+            fields(
+                operation_id = "instance_disks_attach",
+                tag = "instances",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/disks/attach",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn disks_attach(
         &self,
         instance_name: &str,
@@ -330,7 +559,11 @@ impl Instances {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "instance_disks_attach",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -343,6 +576,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "instance_disks_detach",
+            skip_all,
+            err,
+            fields(
+                operation_id = "instance_disks_detach",
+                tag = "instances",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/disks/detach",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn disks_detach(
         &self,
         instance_name: &str,
@@ -358,7 +608,11 @@ impl Instances {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "instance_disks_detach",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -373,6 +627,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_instances_migrate_instance",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_instances_migrate_instance",
+                tag = "instances",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/migrate",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn migrate(
         &self,
         instance_name: &str,
@@ -388,7 +659,11 @@ impl Instances {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "project_instances_migrate_instance",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -408,6 +683,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "instance_network_interfaces_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "instance_network_interfaces_get",
+                tag = "instances",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/network-interfaces",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn network_interfaces_get(
         &self,
         instance_name: &str,
@@ -436,7 +728,10 @@ impl Instances {
             query_
         );
 
-        let resp: crate::types::NetworkInterfaceResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::NetworkInterfaceResultsPage = self
+            .client
+            .get("instance_network_interfaces_get", &url, None)
+            .await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -449,6 +744,23 @@ impl Instances {
      *
      * As opposed to `network_interfaces_get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "instance_network_interfaces_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "instance_network_interfaces_get",
+                tag = "instances",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/network-interfaces",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn network_interfaces_get_all(
         &self,
         instance_name: &str,
@@ -469,8 +781,10 @@ impl Instances {
             query_
         );
 
-        let mut resp: crate::types::NetworkInterfaceResultsPage =
-            self.client.get(&url, None).await?;
+        let mut resp: crate::types::NetworkInterfaceResultsPage = self
+            .client
+            .get("instance_network_interfaces_get", &url, None)
+            .await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -480,12 +794,20 @@ impl Instances {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "instance_network_interfaces_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "instance_network_interfaces_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
@@ -513,6 +835,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "instance_network_interfaces_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "instance_network_interfaces_post",
+                tag = "instances",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/network-interfaces",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn network_interfaces_post(
         &self,
         instance_name: &str,
@@ -528,7 +867,11 @@ impl Instances {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "instance_network_interfaces_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -544,6 +887,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "instance_network_interfaces_get_interface",
+            skip_all,
+            err,
+            fields(
+                operation_id = "instance_network_interfaces_get_interface",
+                tag = "instances",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/network-interfaces/{interface_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn network_interfaces_get_interface(
         &self,
         instance_name: &str,
@@ -559,7 +919,9 @@ impl Instances {
             crate::progenitor_support::encode_path(interface_name),
         );
 
-        self.client.get(&url, None).await
+        self.client
+            .get("instance_network_interfaces_get_interface", &url, None)
+            .await
     }
 
     /**
@@ -574,6 +936,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "instance_network_interfaces_delete_interface",
+            skip_all,
+            err,
+            fields(
+                operation_id = "instance_network_interfaces_delete_interface",
+                tag = "instances",
+                method = "DELETE",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/network-interfaces/{interface_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn network_interfaces_delete_interface(
         &self,
         instance_name: &str,
@@ -589,7 +968,9 @@ impl Instances {
             crate::progenitor_support::encode_path(interface_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("instance_network_interfaces_delete_interface", &url, None)
+            .await
     }
 
     /**
@@ -603,6 +984,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_instances_instance_reboot",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_instances_instance_reboot",
+                tag = "instances",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/reboot",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn reboot(
         &self,
         instance_name: &str,
@@ -616,7 +1014,9 @@ impl Instances {
             crate::progenitor_support::encode_path(instance_name),
         );
 
-        self.client.post(&url, None).await
+        self.client
+            .post("project_instances_instance_reboot", &url, None)
+            .await
     }
 
     /**
@@ -630,6 +1030,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_instances_instance_start",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_instances_instance_start",
+                tag = "instances",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/start",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn start(
         &self,
         instance_name: &str,
@@ -643,7 +1060,9 @@ impl Instances {
             crate::progenitor_support::encode_path(instance_name),
         );
 
-        self.client.post(&url, None).await
+        self.client
+            .post("project_instances_instance_start", &url, None)
+            .await
     }
 
     /**
@@ -657,6 +1076,23 @@ impl Instances {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_instances_instance_stop",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_instances_instance_stop",
+                tag = "instances",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/instances/{instance_name}/stop",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn stop(
         &self,
         instance_name: &str,
@@ -670,6 +1106,8 @@ impl Instances {
             crate::progenitor_support::encode_path(instance_name),
         );
 
-        self.client.post(&url, None).await
+        self.client
+            .post("project_instances_instance_stop", &url, None)
+            .await
     }
 }
@@ -63,6 +63,18 @@
 #![allow(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+/// Start, stop, reboot or delete many instances at once.
+pub mod bulk;
+/// Record requests and responses, and replay them without a server.
+pub mod cassette;
+/// Copy the layout of a project, and optionally its data, into a new project.
+pub mod clone;
+/// Named connection profiles from a configuration file.
+pub mod config;
+/// Authenticating with a session cookie rather than a token.
+pub mod cookies;
+/// Declarative management of organizations and projects from a manifest.
+pub mod declarative;
 /// Virtual disks are used to store instance-local data which includes the operating system.
 ///
 ///FROM: http://oxide.computer/docs/#xxx
@@ -91,10 +103,14 @@ pub mod instances;
 ///
 ///FROM: http://oxide.computer/docs/#xxx
 pub mod metrics;
+/// Hooks that run around every request.
+pub mod middleware;
 /// Organizations represent a subset of users and projects in an Oxide deployment.
 ///
 ///FROM: http://oxide.computer/docs/#xxx
 pub mod organizations;
+/// Find resources that are likely no longer in use.
+pub mod orphans;
 /// System-wide IAM policy.
 ///
 ///FROM: http://oxide.computer/docs/#xxx
@@ -107,6 +123,12 @@ pub mod projects;
 ///
 ///FROM: http://oxide.computer/docs/#xxx
 pub mod racks;
+/// What the client leaves out of its logs.
+pub mod redact;
+/// Map resource ids back to the names that address them in the API.
+pub mod resolver;
+/// Scheduled disk snapshots with time-based retention.
+pub mod retention;
 /// Roles are a component of Identity and Access Management (IAM) that allow a user or agent account access to additional permissions.
 ///
 ///FROM: http://oxide.computer/docs/#xxx
@@ -123,6 +145,8 @@ pub mod routes;
 ///
 ///FROM: http://oxide.computer/docs/#xxx
 pub mod sagas;
+/// Find resources by name or id across every organization and project.
+pub mod search;
 /// Silos represent a logical partition of users and resources.
 ///
 ///FROM: http://oxide.computer/docs/#xxx
@@ -143,23 +167,37 @@ pub mod sshkeys;
 ///
 ///FROM: http://oxide.computer/docs/#xxx
 pub mod subnets;
+/// Cascading deletion of a project and everything in it.
+pub mod teardown;
 #[cfg(test)]
 mod tests;
+/// Where the token sent with each request comes from.
+pub mod token;
+/// Export the topology of a VPC as a graph.
+pub mod topology;
+/// How the client sends requests, and how to send them another way.
+pub mod transport;
 pub mod types;
 /// This tag should be moved into a operations tag.
 ///
 ///FROM: http://oxide.computer/docs/#xxx
 pub mod updates;
+/// Resource usage totals per project, per organization and fleet-wide.
+pub mod usage;
 /// This tag should be moved into an IAM tag.
 ///
 ///FROM: http://oxide.computer/docs/#xxx
 pub mod users;
+/// IP address utilization reporting for VPC subnets.
+pub mod utilization;
 #[doc(hidden)]
 pub mod utils;
 /// A Virtual Private Cloud (VPC) is an isolated network environment that should probaby be moved into a more generic networking tag.
 ///
 ///FROM: http://oxide.computer/docs/#xxx
 pub mod vpcs;
+/// Watch a collection for changes by polling it.
+pub mod watch;
 
 use anyhow::{anyhow, Error, Result};
 
@@ -183,15 +221,43 @@ mod progenitor_support {
     }
 }
 
-use std::env;
+use std::{env, sync::Arc};
 
 /// Entrypoint for interacting with the API client.
 #[derive(Clone)]
 pub struct Client {
     host: String,
-    token: String,
+    token: Arc<dyn crate::token::TokenProvider>,
 
-    client: reqwest::Client,
+    transport: Arc<dyn crate::transport::Transport>,
+    middleware: Vec<Arc<dyn crate::middleware::Middleware>>,
+    redaction: crate::redact::Redaction,
+    session: Arc<std::sync::Mutex<crate::cookies::Session>>,
+
+    organization: Option<String>,
+    project: Option<String>,
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // Never print the token.
+        f.debug_struct("Client")
+            .field("host", &self.host)
+            .field("redaction", &self.redaction)
+            .field("organization", &self.organization)
+            .field("project", &self.project)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The `Authorization` header for a token. A client without a token is
+/// authenticated by its session alone.
+fn bearer(token: &str) -> Option<String> {
+    if token.is_empty() {
+        None
+    } else {
+        Some(format!("Bearer {}", token))
+    }
 }
 
 impl Client {
@@ -207,9 +273,15 @@ impl Client {
         match client {
             Ok(c) => Client {
                 host: host.to_string(),
-                token: token.to_string(),
+                token: Arc::new(crate::token::StaticToken::new(token)),
+
+                transport: Arc::new(crate::transport::ReqwestTransport::new(c)),
+                middleware: Vec::new(),
+                redaction: Default::default(),
+                session: Default::default(),
 
-                client: c,
+                organization: None,
+                project: None,
             },
             Err(e) => panic!("creating reqwest client failed: {:?}", e),
         }
@@ -223,19 +295,125 @@ impl Client {
         Client::new(token, host)
     }
 
-    async fn url_and_auth(&self, uri: &str) -> Result<(reqwest::Url, Option<String>)> {
-        let parsed_url = uri.parse::<reqwest::Url>();
+    /// Create a new Client struct from a profile in the configuration file,
+    /// with any settings in the environment taking precedence. See the
+    /// `config` module.
+    pub fn from_profile(name: &str) -> Result<Self> {
+        crate::config::Config::load()?.profile(Some(name))?.client()
+    }
+
+    /// The organization to use when none is given, from the profile.
+    pub fn default_organization(&self) -> Option<&str> {
+        self.organization.as_deref()
+    }
+
+    /// The project to use when none is given, from the profile.
+    pub fn default_project(&self) -> Option<&str> {
+        self.project.as_deref()
+    }
+
+    /// Get the token for each request from `provider`, rather than sending
+    /// the one given to `new`. See the `token` module.
+    pub fn with_token_provider<P>(mut self, provider: P) -> Self
+    where
+        P: crate::token::TokenProvider + 'static,
+    {
+        self.token = Arc::new(provider);
+        self
+    }
+
+    /// Send every request with `transport` instead of the default `reqwest`
+    /// client. See the `transport` module.
+    pub fn with_transport<T>(mut self, transport: T) -> Self
+    where
+        T: crate::transport::Transport + 'static,
+    {
+        self.transport = Arc::new(transport);
+        self
+    }
 
-        let auth = format!("Bearer {}", self.token);
-        parsed_url.map(|u| (u, Some(auth))).map_err(Error::from)
+    /// Send every request with a `reqwest` client built from `options`.
+    /// See `TransportOptions` in the `transport` module.
+    pub fn with_transport_options(
+        self,
+        options: &crate::transport::TransportOptions,
+    ) -> Result<Self> {
+        Ok(self.with_transport(options.build()?))
+    }
+
+    /// Run every request through `middleware`, after any middleware added
+    /// before it. See the `middleware` module.
+    pub fn with_middleware<M>(mut self, middleware: M) -> Self
+    where
+        M: crate::middleware::Middleware + 'static,
+    {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Mask the body fields in `redaction`, rather than the default ones,
+    /// when logging. See the `redact` module.
+    pub fn with_redaction(mut self, redaction: crate::redact::Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// Send the cookies in `session` with every request, rather than
+    /// starting without any. See the `cookies` module.
+    pub fn with_session(mut self, session: crate::cookies::Session) -> Self {
+        self.session = Arc::new(std::sync::Mutex::new(session));
+        self
+    }
+
+    /// The cookies the server has set, to save for later.
+    pub fn session(&self) -> crate::cookies::Session {
+        self.session.lock().unwrap().clone()
+    }
+
+    /// Log in as `username`, and use the session from then on. This is the
+    /// `spoof_login` endpoint, so it only works against servers that allow it.
+    pub async fn login(&self, username: &str) -> Result<()> {
+        self.hidden()
+            .spoof_login(&crate::types::LoginParams {
+                username: username.to_string(),
+            })
+            .await?;
+        if self.session.lock().unwrap().is_empty() {
+            return Err(anyhow!(
+                "logging in as {} did not start a session",
+                username
+            ));
+        }
+        Ok(())
+    }
+
+    /// End the session, and forget it even if the server could not be told.
+    pub async fn logout(&self) -> Result<()> {
+        let result = self.hidden().logout().await;
+        self.session.lock().unwrap().clear();
+        result
+    }
+
+    /// Send every request through `cassette`, which either records it or
+    /// replays a response recorded earlier. See the `cassette` module.
+    pub fn with_cassette(self, cassette: crate::cassette::Cassette) -> Self {
+        let inner = self.transport.clone();
+        self.with_transport(crate::cassette::CassetteTransport { cassette, inner })
+    }
+
+    async fn url_and_auth(&self, uri: &str) -> Result<(url::Url, Option<String>)> {
+        let parsed_url = uri.parse::<url::Url>();
+
+        let token = self.token.token().await?;
+        parsed_url.map(|u| (u, bearer(&token))).map_err(Error::from)
     }
 
     pub async fn request_raw(
         &self,
-        method: reqwest::Method,
+        method: http::Method,
         uri: &str,
-        body: Option<reqwest::Body>,
-    ) -> Result<reqwest::RequestBuilder> {
+        body: Option<bytes::Bytes>,
+    ) -> Result<http::Request<bytes::Bytes>> {
         let u = if uri.starts_with("https://") || uri.starts_with("http://") {
             uri.to_string()
         } else {
@@ -243,65 +421,107 @@ impl Client {
         };
         let (url, auth) = self.url_and_auth(&u).await?;
 
-        let instance = <&Client>::clone(&self);
-
-        let mut req = instance.client.request(method.clone(), url);
+        let mut req = http::Request::builder().method(method).uri(url.as_str());
 
         // Set the default headers.
         req = req.header(
-            reqwest::header::ACCEPT,
-            reqwest::header::HeaderValue::from_static("application/json"),
+            http::header::ACCEPT,
+            http::header::HeaderValue::from_static("application/json"),
         );
         req = req.header(
-            reqwest::header::CONTENT_TYPE,
-            reqwest::header::HeaderValue::from_static("application/json"),
+            http::header::CONTENT_TYPE,
+            http::header::HeaderValue::from_static("application/json"),
         );
 
         if let Some(auth_str) = auth {
             req = req.header(http::header::AUTHORIZATION, &*auth_str);
         }
-
-        if let Some(body) = body {
-            log::debug!(
-                "body: {:?}",
-                String::from_utf8(body.as_bytes().unwrap().to_vec()).unwrap()
-            );
-            req = req.body(body);
+        if let Some(cookie) = self.session.lock().unwrap().cookie_header() {
+            req = req.header(http::header::COOKIE, cookie);
         }
-        log::debug!("request: {:?}", &req);
+
+        let body = match body {
+            Some(body) => {
+                log::debug!("body: {}", self.redaction.body(&body));
+                body
+            }
+            None => bytes::Bytes::new(),
+        };
+        let req = req.body(body)?;
+        log::debug!("request: {}", self.redaction.request(&req));
         Ok(req)
     }
 
     pub async fn response_raw(
         &self,
-        method: reqwest::Method,
+        method: http::Method,
         uri: &str,
-        body: Option<reqwest::Body>,
-    ) -> Result<reqwest::Response> {
+        body: Option<bytes::Bytes>,
+    ) -> Result<http::Response<bytes::Bytes>> {
         let req = self.request_raw(method, uri, body).await?;
-        Ok(req.send().await?)
+        self.send(req).await
+    }
+
+    async fn send(&self, req: http::Request<bytes::Bytes>) -> Result<http::Response<bytes::Bytes>> {
+        let response = crate::middleware::Next::new(&self.middleware, &*self.transport)
+            .run(req)
+            .await?;
+        self.session.lock().unwrap().update(response.headers());
+        Ok(response)
     }
 
     async fn request<Out>(
         &self,
-        method: reqwest::Method,
+        operation_id: &'static str,
+        method: http::Method,
         uri: &str,
-        body: Option<reqwest::Body>,
+        body: Option<bytes::Bytes>,
     ) -> Result<Out>
     where
         Out: serde::de::DeserializeOwned + 'static + Send,
     {
-        let response = self.response_raw(method, uri, body).await?;
+        let mut retry_count = 0u32;
+        let response = loop {
+            let mut req = self.request_raw(method.clone(), uri, body.clone()).await?;
+            req.extensions_mut()
+                .insert(crate::middleware::OperationId(operation_id));
+            let sent = req.headers().get(http::header::AUTHORIZATION).cloned();
+            let response = self.send(req).await?;
+
+            // The token may have been rotated since it was fetched, so get a
+            // new one and try once more, unless it is the one just rejected.
+            if response.status() == http::StatusCode::UNAUTHORIZED && retry_count == 0 {
+                log::debug!("{} was unauthenticated, refreshing the token", operation_id);
+                let refreshed = bearer(&self.token.refresh().await?);
+                if refreshed.as_deref() != sent.as_ref().and_then(|v| v.to_str().ok()) {
+                    retry_count += 1;
+                    continue;
+                }
+            }
+            break response;
+        };
 
         let status = response.status();
 
-        let response_body = response.bytes().await?;
+        // Fill in the span opened by the generated function.
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("status", status.as_u16());
+            span.record("retry_count", retry_count);
+            if let Some(request_id) = response
+                .headers()
+                .get("x-request-id")
+                .and_then(|v| v.to_str().ok())
+            {
+                span.record("request_id", request_id);
+            }
+        }
+
+        let response_body = response.into_body();
 
         if status.is_success() {
-            log::debug!(
-                "response payload {}",
-                String::from_utf8_lossy(&response_body)
-            );
+            log::debug!("response payload {}", self.redaction.body(&response_body));
             let parsed_response = if status == http::StatusCode::NO_CONTENT
                 || std::any::TypeId::of::<Out>() == std::any::TypeId::of::<()>()
             {
@@ -317,6 +537,8 @@ impl Client {
                 // Parse the error as the error type.
                 match serde_json::from_slice::<crate::types::ErrorResponse>(&response_body) {
                     Ok(resp) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::Span::current().record("request_id", resp.request_id.as_str());
                         let e: crate::types::Error = resp.into();
                         e.into()
                     }
@@ -336,55 +558,102 @@ impl Client {
 
     async fn request_entity<D>(
         &self,
+        operation_id: &'static str,
         method: http::Method,
         uri: &str,
-        body: Option<reqwest::Body>,
+        body: Option<bytes::Bytes>,
     ) -> Result<D>
     where
         D: serde::de::DeserializeOwned + 'static + Send,
     {
-        let r = self.request(method, uri, body).await?;
+        let r = self.request(operation_id, method, uri, body).await?;
         Ok(r)
     }
 
-    async fn get<D>(&self, uri: &str, message: Option<reqwest::Body>) -> Result<D>
+    async fn get<D>(
+        &self,
+        operation_id: &'static str,
+        uri: &str,
+        message: Option<bytes::Bytes>,
+    ) -> Result<D>
     where
         D: serde::de::DeserializeOwned + 'static + Send,
     {
-        self.request_entity(http::Method::GET, &(self.host.to_string() + uri), message)
-            .await
+        self.request_entity(
+            operation_id,
+            http::Method::GET,
+            &(self.host.to_string() + uri),
+            message,
+        )
+        .await
     }
 
-    async fn post<D>(&self, uri: &str, message: Option<reqwest::Body>) -> Result<D>
+    async fn post<D>(
+        &self,
+        operation_id: &'static str,
+        uri: &str,
+        message: Option<bytes::Bytes>,
+    ) -> Result<D>
     where
         D: serde::de::DeserializeOwned + 'static + Send,
     {
-        self.request_entity(http::Method::POST, &(self.host.to_string() + uri), message)
-            .await
+        self.request_entity(
+            operation_id,
+            http::Method::POST,
+            &(self.host.to_string() + uri),
+            message,
+        )
+        .await
     }
 
     #[allow(dead_code)]
-    async fn patch<D>(&self, uri: &str, message: Option<reqwest::Body>) -> Result<D>
+    async fn patch<D>(
+        &self,
+        operation_id: &'static str,
+        uri: &str,
+        message: Option<bytes::Bytes>,
+    ) -> Result<D>
     where
         D: serde::de::DeserializeOwned + 'static + Send,
     {
-        self.request_entity(http::Method::PATCH, &(self.host.to_string() + uri), message)
-            .await
+        self.request_entity(
+            operation_id,
+            http::Method::PATCH,
+            &(self.host.to_string() + uri),
+            message,
+        )
+        .await
     }
 
-    async fn put<D>(&self, uri: &str, message: Option<reqwest::Body>) -> Result<D>
+    async fn put<D>(
+        &self,
+        operation_id: &'static str,
+        uri: &str,
+        message: Option<bytes::Bytes>,
+    ) -> Result<D>
     where
         D: serde::de::DeserializeOwned + 'static + Send,
     {
-        self.request_entity(http::Method::PUT, &(self.host.to_string() + uri), message)
-            .await
+        self.request_entity(
+            operation_id,
+            http::Method::PUT,
+            &(self.host.to_string() + uri),
+            message,
+        )
+        .await
     }
 
-    async fn delete<D>(&self, uri: &str, message: Option<reqwest::Body>) -> Result<D>
+    async fn delete<D>(
+        &self,
+        operation_id: &'static str,
+        uri: &str,
+        message: Option<bytes::Bytes>,
+    ) -> Result<D>
     where
         D: serde::de::DeserializeOwned + 'static + Send,
     {
         self.request_entity(
+            operation_id,
             http::Method::DELETE,
             &(self.host.to_string() + uri),
             message,
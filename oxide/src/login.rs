@@ -24,7 +24,7 @@ body: &crate::types::LoginParams
 ) -> Result<()> {
 let url =
 "/login".to_string();
-self.client.post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?))).await
+self.client.post(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?))).await
 }
 
 
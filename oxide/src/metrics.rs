@@ -22,6 +22,23 @@ impl Metrics {
      * * `limit: u32` -- Maximum number of items returned by a single call.
      * * `page_token: &str` -- Token returned by previous call to retreive the subsequent page.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "timeseries_schema_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "timeseries_schema_get",
+                tag = "metrics",
+                method = "GET",
+                path = "/timeseries/schema",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn timeseries_schema_get(
         &self,
         limit: u32,
@@ -37,7 +54,8 @@ impl Metrics {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/timeseries/schema?{}", query_);
 
-        let resp: crate::types::TimeseriesSchemaResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::TimeseriesSchemaResultsPage =
+            self.client.get("timeseries_schema_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -50,10 +68,27 @@ impl Metrics {
      *
      * As opposed to `timeseries_schema_get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "timeseries_schema_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "timeseries_schema_get",
+                tag = "metrics",
+                method = "GET",
+                path = "/timeseries/schema",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn timeseries_schema_get_all(&self) -> Result<Vec<crate::types::TimeseriesSchema>> {
         let url = "/timeseries/schema".to_string();
         let mut resp: crate::types::TimeseriesSchemaResultsPage =
-            self.client.get(&url, None).await?;
+            self.client.get("timeseries_schema_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -63,12 +98,20 @@ impl Metrics {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "timeseries_schema_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "timeseries_schema_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
@@ -0,0 +1,130 @@
+//! Hooks that run around every request a [`Client`](crate::Client) sends.
+//!
+//! Middleware is added with [`Client::with_middleware`](crate::Client::with_middleware)
+//! and runs in the order it was added, the first added seeing the request
+//! first and the response last. Each is handed the request and the rest of
+//! the chain as a [`Next`], so it can change the request before passing it
+//! on, look at the response on the way back, answer the request itself
+//! without calling `next`, or fail it:
+//!
+//! ```
+//! use std::time::Instant;
+//!
+//! use bytes::Bytes;
+//! use oxide_api::{
+//!     middleware::{operation_id, Middleware, Next},
+//!     transport::BoxFuture,
+//! };
+//!
+//! struct Timer;
+//!
+//! impl Middleware for Timer {
+//!     fn handle<'a>(
+//!         &'a self,
+//!         request: http::Request<Bytes>,
+//!         next: Next<'a>,
+//!     ) -> BoxFuture<'a, anyhow::Result<http::Response<Bytes>>> {
+//!         Box::pin(async move {
+//!             let operation = operation_id(&request).unwrap_or("unknown");
+//!             let start = Instant::now();
+//!             let response = next.run(request).await;
+//!             println!("{} took {:?}", operation, start.elapsed());
+//!             response
+//!         })
+//!     }
+//! }
+//!
+//! let client = oxide_api::Client::new("token", "http://oxide.test").with_middleware(Timer);
+//! ```
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use bytes::Bytes;
+
+use crate::transport::{BoxFuture, Transport};
+
+/// The OpenAPI operation id of a generated call, such as
+/// `project_instances_get`. It is stored in the extensions of the request,
+/// and is missing from requests made with
+/// [`Client::response_raw`](crate::Client::response_raw).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OperationId(pub &'static str);
+
+/// The operation id of `request`, if it was made by a generated call.
+pub fn operation_id<T>(request: &http::Request<T>) -> Option<&'static str> {
+    request
+        .extensions()
+        .get::<OperationId>()
+        .map(|operation| operation.0)
+}
+
+/// A hook around every request.
+pub trait Middleware: Send + Sync {
+    fn handle<'a>(
+        &'a self,
+        request: http::Request<Bytes>,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<http::Response<Bytes>>>;
+}
+
+impl<T: Middleware + ?Sized> Middleware for Arc<T> {
+    fn handle<'a>(
+        &'a self,
+        request: http::Request<Bytes>,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<http::Response<Bytes>>> {
+        (**self).handle(request, next)
+    }
+}
+
+/// The rest of the chain after a middleware: any later middleware, then the
+/// transport.
+pub struct Next<'a> {
+    middleware: &'a [Arc<dyn Middleware>],
+    transport: &'a dyn Transport,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(middleware: &'a [Arc<dyn Middleware>], transport: &'a dyn Transport) -> Self {
+        Next {
+            middleware,
+            transport,
+        }
+    }
+
+    /// Pass `request` on, and wait for its response.
+    pub fn run(
+        self,
+        request: http::Request<Bytes>,
+    ) -> BoxFuture<'a, Result<http::Response<Bytes>>> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => middleware.handle(request, Next::new(rest, self.transport)),
+            None => self.transport.send(request),
+        }
+    }
+}
+
+/// Fails every request that could change something, so that a client can be
+/// handed to code that should only ever look.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadOnly;
+
+impl Middleware for ReadOnly {
+    fn handle<'a>(
+        &'a self,
+        request: http::Request<Bytes>,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<http::Response<Bytes>>> {
+        Box::pin(async move {
+            if !request.method().is_safe() {
+                bail!(
+                    "{} {} ({}) is not allowed by a read-only client",
+                    request.method(),
+                    request.uri().path(),
+                    operation_id(&request).unwrap_or("no operation id")
+                );
+            }
+            next.run(request).await
+        })
+    }
+}
@@ -141,7 +141,7 @@ impl Networking {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?)))
             .await
     }
 
@@ -198,7 +198,7 @@ impl Networking {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?)))
             .await
     }
 
@@ -367,7 +367,7 @@ impl Networking {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?)))
             .await
     }
 
@@ -508,7 +508,7 @@ impl Networking {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?)))
             .await
     }
 
@@ -571,7 +571,7 @@ impl Networking {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?)))
             .await
     }
 
@@ -750,7 +750,7 @@ impl Networking {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?)))
             .await
     }
 
@@ -819,7 +819,7 @@ impl Networking {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?)))
             .await
     }
 
@@ -993,7 +993,7 @@ impl Networking {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?)))
             .await
     }
 
@@ -1056,7 +1056,7 @@ impl Networking {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?)))
             .await
     }
 
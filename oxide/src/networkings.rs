@@ -115,7 +115,7 @@ let url =
 format!("/organizations/{}/projects/{}/vpcs",
 crate::progenitor_support::encode_path(organization_name),crate::progenitor_support::encode_path(project_name),);
 
-self.client.post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?))).await
+self.client.post(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?))).await
 }
 
 /**
@@ -160,7 +160,7 @@ let url =
 format!("/organizations/{}/projects/{}/vpcs/{}",
 crate::progenitor_support::encode_path(organization_name),crate::progenitor_support::encode_path(project_name),crate::progenitor_support::encode_path(vpc_name),);
 
-self.client.put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?))).await
+self.client.put(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?))).await
 }
 
 /**
@@ -287,7 +287,7 @@ let url =
 format!("/organizations/{}/projects/{}/vpcs/{}/firewall/rules",
 crate::progenitor_support::encode_path(organization_name),crate::progenitor_support::encode_path(project_name),crate::progenitor_support::encode_path(vpc_name),);
 
-self.client.put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?))).await
+self.client.put(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?))).await
 }
 
 /**
@@ -392,7 +392,7 @@ let url =
 format!("/organizations/{}/projects/{}/vpcs/{}/routers",
 crate::progenitor_support::encode_path(organization_name),crate::progenitor_support::encode_path(project_name),crate::progenitor_support::encode_path(vpc_name),);
 
-self.client.post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?))).await
+self.client.post(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?))).await
 }
 
 /**
@@ -439,7 +439,7 @@ let url =
 format!("/organizations/{}/projects/{}/vpcs/{}/routers/{}",
 crate::progenitor_support::encode_path(organization_name),crate::progenitor_support::encode_path(project_name),crate::progenitor_support::encode_path(vpc_name),crate::progenitor_support::encode_path(router_name),);
 
-self.client.put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?))).await
+self.client.put(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?))).await
 }
 
 /**
@@ -569,7 +569,7 @@ let url =
 format!("/organizations/{}/projects/{}/vpcs/{}/routers/{}/routes",
 crate::progenitor_support::encode_path(organization_name),crate::progenitor_support::encode_path(project_name),crate::progenitor_support::encode_path(vpc_name),crate::progenitor_support::encode_path(router_name),);
 
-self.client.post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?))).await
+self.client.post(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?))).await
 }
 
 /**
@@ -618,7 +618,7 @@ let url =
 format!("/organizations/{}/projects/{}/vpcs/{}/routers/{}/routes/{}",
 crate::progenitor_support::encode_path(organization_name),crate::progenitor_support::encode_path(project_name),crate::progenitor_support::encode_path(vpc_name),crate::progenitor_support::encode_path(router_name),crate::progenitor_support::encode_path(route_name),);
 
-self.client.put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?))).await
+self.client.put(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?))).await
 }
 
 /**
@@ -747,7 +747,7 @@ let url =
 format!("/organizations/{}/projects/{}/vpcs/{}/subnets",
 crate::progenitor_support::encode_path(organization_name),crate::progenitor_support::encode_path(project_name),crate::progenitor_support::encode_path(vpc_name),);
 
-self.client.post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?))).await
+self.client.post(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?))).await
 }
 
 /**
@@ -794,7 +794,7 @@ let url =
 format!("/organizations/{}/projects/{}/vpcs/{}/subnets/{}",
 crate::progenitor_support::encode_path(organization_name),crate::progenitor_support::encode_path(project_name),crate::progenitor_support::encode_path(vpc_name),crate::progenitor_support::encode_path(subnet_name),);
 
-self.client.put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?))).await
+self.client.put(&url, Some(bytes::Bytes::from(serde_json::to_vec(body)?))).await
 }
 
 /**
@@ -23,6 +23,23 @@ impl Organizations {
      * * `page_token: &str` -- Token returned by previous call to retreive the subsequent page.
      * * `sort_by: crate::types::NameOrIdSortMode` -- Supported set of sort modes for scanning by name or id.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organizations_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organizations_get",
+                tag = "organizations",
+                method = "GET",
+                path = "/organizations",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -42,7 +59,8 @@ impl Organizations {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/organizations?{}", query_);
 
-        let resp: crate::types::OrganizationResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::OrganizationResultsPage =
+            self.client.get("organizations_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -55,6 +73,23 @@ impl Organizations {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organizations_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organizations_get",
+                tag = "organizations",
+                method = "GET",
+                path = "/organizations",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         sort_by: crate::types::NameOrIdSortMode,
@@ -66,7 +101,8 @@ impl Organizations {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/organizations?{}", query_);
 
-        let mut resp: crate::types::OrganizationResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::OrganizationResultsPage =
+            self.client.get("organizations_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -76,12 +112,12 @@ impl Organizations {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("organizations_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("organizations_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -103,16 +139,89 @@ impl Organizations {
      *
      * This function performs a `POST` to the `/organizations` endpoint.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organizations_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organizations_post",
+                tag = "organizations",
+                method = "POST",
+                path = "/organizations",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(
         &self,
         body: &crate::types::OrganizationCreate,
     ) -> Result<crate::types::Organization> {
         let url = "/organizations".to_string();
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "organizations_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create a new organization.
+     *
+     * This function performs a `POST` to the `/organizations` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/organizations/{organization_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organizations_ensure",
+            skip_all,
+            err,
+            fields(tag = "organizations", path = "/organizations",)
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        body: &crate::types::OrganizationCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::Organization>> {
+        match self.post(body).await {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let organization_name = body.name.as_str();
+                let url = format!(
+                    "/organizations/{}",
+                    crate::progenitor_support::encode_path(organization_name),
+                );
+                let get = self
+                    .client
+                    .get("organizations_get_organization", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "organizations_get_organization",
+                        operation_id = "organizations_get_organization",
+                        tag = "organizations",
+                        method = "GET",
+                        path = "/organizations/{organization_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Fetch a specific organization.
      *
@@ -122,13 +231,32 @@ impl Organizations {
      *
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organizations_get_organization",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organizations_get_organization",
+                tag = "organizations",
+                method = "GET",
+                path = "/organizations/{organization_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(&self, organization_name: &str) -> Result<crate::types::Organization> {
         let url = format!(
             "/organizations/{}",
             crate::progenitor_support::encode_path(organization_name),
         );
 
-        self.client.get(&url, None).await
+        self.client
+            .get("organizations_get_organization", &url, None)
+            .await
     }
 
     /**
@@ -140,6 +268,23 @@ impl Organizations {
      *
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organizations_put_organization",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organizations_put_organization",
+                tag = "organizations",
+                method = "PUT",
+                path = "/organizations/{organization_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn put(
         &self,
         organization_name: &str,
@@ -151,7 +296,11 @@ impl Organizations {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "organizations_put_organization",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -164,13 +313,32 @@ impl Organizations {
      *
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organizations_delete_organization",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organizations_delete_organization",
+                tag = "organizations",
+                method = "DELETE",
+                path = "/organizations/{organization_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(&self, organization_name: &str) -> Result<()> {
         let url = format!(
             "/organizations/{}",
             crate::progenitor_support::encode_path(organization_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("organizations_delete_organization", &url, None)
+            .await
     }
 
     /**
@@ -182,6 +350,23 @@ impl Organizations {
      *
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_get_policy",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organization_get_policy",
+                tag = "organizations",
+                method = "GET",
+                path = "/organizations/{organization_name}/policy",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_policy(
         &self,
         organization_name: &str,
@@ -191,7 +376,7 @@ impl Organizations {
             crate::progenitor_support::encode_path(organization_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("organization_get_policy", &url, None).await
     }
 
     /**
@@ -203,6 +388,23 @@ impl Organizations {
      *
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_put_policy",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organization_put_policy",
+                tag = "organizations",
+                method = "PUT",
+                path = "/organizations/{organization_name}/policy",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn put_policy(
         &self,
         organization_name: &str,
@@ -214,7 +416,11 @@ impl Organizations {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "organization_put_policy",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 }
@@ -0,0 +1,466 @@
+//! Find resources that are likely no longer in use.
+//!
+//! [`OrphanReport::scan`] looks through every project for detached disks and
+//! stopped instances that have not changed in a while, snapshots of disks
+//! that no longer exist, images no disk was created from, and VPCs and
+//! projects with nothing in them. Nothing is deleted unless
+//! [`OrphanReport::remediate`] is called, and then only the findings it is
+//! told to.
+use std::{collections::BTreeSet, fmt, time::Duration};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tabled::Tabled;
+
+use crate::{
+    declarative::Outcome,
+    resolver::ResourcePath,
+    teardown::{Step, TeardownOptions},
+    types::{
+        Disk, DiskState, Image, Instance, InstanceState, NameOrIdSortMode, NameSortMode,
+        NetworkInterface, Project, Snapshot, Vpc,
+    },
+    utils::DisplayOptionDateTime,
+};
+
+/// Why a resource was reported.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    /// A disk that is not attached to any instance.
+    DetachedDisk,
+    /// A snapshot of a disk that has since been deleted.
+    OrphanedSnapshot,
+    /// An image that no disk in its project was created from.
+    UnusedImage,
+    /// An instance that has been stopped for a while.
+    StoppedInstance,
+    /// A VPC with no network interfaces in it.
+    EmptyVpc,
+    /// A project with no instances, disks, snapshots or images.
+    EmptyProject,
+}
+
+impl fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FindingKind::DetachedDisk => "detached disk",
+            FindingKind::OrphanedSnapshot => "orphaned snapshot",
+            FindingKind::UnusedImage => "unused image",
+            FindingKind::StoppedInstance => "stopped instance",
+            FindingKind::EmptyVpc => "empty vpc",
+            FindingKind::EmptyProject => "empty project",
+        })
+    }
+}
+
+/// A resource that is likely no longer in use.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Tabled)]
+pub struct Finding {
+    pub kind: FindingKind,
+    pub path: ResourcePath,
+    pub id: String,
+    /// When the resource was created or last changed, whichever the finding
+    /// is based on.
+    pub since: DisplayOptionDateTime,
+    /// The number of whole days since then.
+    pub age_days: i64,
+}
+
+/// Thresholds for what counts as unused.
+#[derive(Debug, Clone)]
+pub struct OrphanOptions {
+    /// How long a disk must have been detached and unchanged.
+    pub detached_disk_age: Duration,
+    /// How long an instance must have been stopped.
+    pub stopped_instance_age: Duration,
+    /// How old any other resource must be, so that resources still being
+    /// set up are not reported.
+    pub min_age: Duration,
+}
+
+impl Default for OrphanOptions {
+    fn default() -> Self {
+        OrphanOptions {
+            detached_disk_age: Duration::from_secs(30 * 24 * 60 * 60),
+            stopped_instance_age: Duration::from_secs(30 * 24 * 60 * 60),
+            min_age: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+/// Everything in a project that is considered when looking for orphans.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct ProjectContents {
+    pub organization_name: String,
+    pub project: Project,
+    pub vpcs: Vec<Vpc>,
+    pub instances: Vec<Instance>,
+    pub network_interfaces: Vec<NetworkInterface>,
+    pub disks: Vec<Disk>,
+    pub snapshots: Vec<Snapshot>,
+    pub images: Vec<Image>,
+}
+
+impl ProjectContents {
+    /// Find the resources in the project that are likely unused as of `now`.
+    ///
+    /// An empty project is reported on its own, rather than along with each
+    /// of its VPCs.
+    pub fn find_orphans(&self, now: DateTime<Utc>, options: &OrphanOptions) -> Vec<Finding> {
+        let o = &self.organization_name;
+        let p = &self.project.name;
+        let finding = |kind, path, id: &str, since: &DisplayOptionDateTime, threshold: Duration| {
+            let since_time = since.0?;
+            let age = now - since_time;
+            if age < chrono::Duration::from_std(threshold).ok()? {
+                return None;
+            }
+            Some(Finding {
+                kind,
+                path,
+                id: id.to_string(),
+                since: since.clone(),
+                age_days: age.num_days(),
+            })
+        };
+
+        if self.instances.is_empty()
+            && self.disks.is_empty()
+            && self.snapshots.is_empty()
+            && self.images.is_empty()
+        {
+            return finding(
+                FindingKind::EmptyProject,
+                ResourcePath::Project {
+                    organization: o.to_string(),
+                    project: p.to_string(),
+                },
+                &self.project.id,
+                &self.project.time_created,
+                options.min_age,
+            )
+            .into_iter()
+            .collect();
+        }
+
+        let mut findings = Vec::new();
+
+        for instance in &self.instances {
+            if instance.run_state == InstanceState::Stopped {
+                findings.extend(finding(
+                    FindingKind::StoppedInstance,
+                    ResourcePath::Instance {
+                        organization: o.to_string(),
+                        project: p.to_string(),
+                        instance: instance.name.to_string(),
+                    },
+                    &instance.id,
+                    &instance.time_run_state_updated,
+                    options.stopped_instance_age,
+                ));
+            }
+        }
+
+        for disk in &self.disks {
+            if disk.state == DiskState::Detached {
+                findings.extend(finding(
+                    FindingKind::DetachedDisk,
+                    ResourcePath::Disk {
+                        organization: o.to_string(),
+                        project: p.to_string(),
+                        disk: disk.name.to_string(),
+                    },
+                    &disk.id,
+                    &disk.time_modified,
+                    options.detached_disk_age,
+                ));
+            }
+        }
+
+        let disk_ids: BTreeSet<&str> = self.disks.iter().map(|d| d.id.as_str()).collect();
+        for snapshot in &self.snapshots {
+            if !disk_ids.contains(snapshot.disk_id.as_str()) {
+                findings.extend(finding(
+                    FindingKind::OrphanedSnapshot,
+                    ResourcePath::Snapshot {
+                        organization: o.to_string(),
+                        project: p.to_string(),
+                        snapshot: snapshot.name.to_string(),
+                    },
+                    &snapshot.id,
+                    &snapshot.time_created,
+                    options.min_age,
+                ));
+            }
+        }
+
+        let image_ids: BTreeSet<&str> = self.disks.iter().map(|d| d.image_id.as_str()).collect();
+        for image in &self.images {
+            if !image_ids.contains(image.id.as_str()) {
+                findings.extend(finding(
+                    FindingKind::UnusedImage,
+                    ResourcePath::Image {
+                        organization: o.to_string(),
+                        project: p.to_string(),
+                        image: image.name.to_string(),
+                    },
+                    &image.id,
+                    &image.time_created,
+                    options.min_age,
+                ));
+            }
+        }
+
+        let vpc_ids: BTreeSet<&str> = self
+            .network_interfaces
+            .iter()
+            .map(|n| n.vpc_id.as_str())
+            .collect();
+        for vpc in &self.vpcs {
+            if !vpc_ids.contains(vpc.id.as_str()) {
+                findings.extend(finding(
+                    FindingKind::EmptyVpc,
+                    ResourcePath::Vpc {
+                        organization: o.to_string(),
+                        project: p.to_string(),
+                        vpc: vpc.name.to_string(),
+                    },
+                    &vpc.id,
+                    &vpc.time_created,
+                    options.min_age,
+                ));
+            }
+        }
+
+        findings
+    }
+}
+
+/// The resources found to be likely unused.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default, JsonSchema)]
+pub struct OrphanReport {
+    pub findings: Vec<Finding>,
+}
+
+impl OrphanReport {
+    /// Look through every project visible to the client.
+    pub async fn scan(client: &crate::Client, options: &OrphanOptions) -> Result<Self> {
+        let now = Utc::now();
+        let mut findings = Vec::new();
+        for organization in client
+            .organizations()
+            .get_all(NameOrIdSortMode::NameAscending)
+            .await?
+        {
+            for project in client
+                .projects()
+                .get_all(&organization.name, NameOrIdSortMode::NameAscending)
+                .await?
+            {
+                let contents = project_contents(client, &organization.name, project).await?;
+                findings.extend(contents.find_orphans(now, options));
+            }
+        }
+
+        Ok(OrphanReport { findings })
+    }
+
+    /// Render the report as a table.
+    pub fn to_table(&self) -> String {
+        tabled::Table::new(&self.findings).to_string()
+    }
+
+    /// Render the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Delete each finding for which `confirm` returns true.
+    ///
+    /// Stopped instances have their disks detached and network interfaces
+    /// deleted first, VPCs their subnets and custom routers, and empty
+    /// projects are torn down entirely. Instances are deleted before disks,
+    /// and VPCs after both, so the order of the findings does not matter.
+    pub async fn remediate<F>(&self, client: &crate::Client, mut confirm: F) -> RemediationReport
+    where
+        F: FnMut(&Finding) -> bool,
+    {
+        let mut findings: Vec<&Finding> = self.findings.iter().collect();
+        findings.sort_by_key(|f| match f.kind {
+            FindingKind::StoppedInstance => 0,
+            FindingKind::DetachedDisk => 1,
+            FindingKind::OrphanedSnapshot => 2,
+            FindingKind::UnusedImage => 3,
+            FindingKind::EmptyVpc => 4,
+            FindingKind::EmptyProject => 5,
+        });
+
+        let mut results = Vec::with_capacity(findings.len());
+        for finding in findings {
+            let outcome = if confirm(finding) {
+                match delete(client, finding).await {
+                    Ok(()) => Outcome::Applied,
+                    Err(e) => Outcome::Failed {
+                        error: format!("{:#}", e),
+                    },
+                }
+            } else {
+                Outcome::Skipped {
+                    reason: "not confirmed".to_string(),
+                }
+            };
+            results.push(RemediationResult {
+                finding: finding.clone(),
+                outcome,
+            });
+        }
+
+        RemediationReport { results }
+    }
+}
+
+/// The outcome of deleting a single finding.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Tabled)]
+pub struct RemediationResult {
+    #[header(inline)]
+    pub finding: Finding,
+    pub outcome: Outcome,
+}
+
+/// The outcome of deleting findings.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default, JsonSchema)]
+pub struct RemediationReport {
+    pub results: Vec<RemediationResult>,
+}
+
+impl RemediationReport {
+    /// Returns whether every confirmed finding was deleted.
+    pub fn is_success(&self) -> bool {
+        !self
+            .results
+            .iter()
+            .any(|r| matches!(r.outcome, Outcome::Failed { .. }))
+    }
+
+    /// Render the report as a table.
+    pub fn to_table(&self) -> String {
+        tabled::Table::new(&self.results).to_string()
+    }
+
+    /// Render the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+async fn project_contents(
+    client: &crate::Client,
+    organization_name: &str,
+    project: Project,
+) -> Result<ProjectContents> {
+    let (o, p) = (organization_name, project.name.as_str());
+    let instances = client
+        .instances()
+        .get_all(o, p, NameSortMode::NameAscending)
+        .await?;
+    let mut network_interfaces = Vec::new();
+    for instance in &instances {
+        network_interfaces.append(
+            &mut client
+                .instances()
+                .network_interfaces_get_all(&instance.name, o, p, NameSortMode::NameAscending)
+                .await?,
+        );
+    }
+
+    Ok(ProjectContents {
+        organization_name: o.to_string(),
+        vpcs: client
+            .vpcs()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?,
+        instances,
+        network_interfaces,
+        disks: client
+            .disks()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?,
+        snapshots: client
+            .snapshots()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?,
+        images: client
+            .images()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?,
+        project,
+    })
+}
+
+async fn delete(client: &crate::Client, finding: &Finding) -> Result<()> {
+    let o = finding.path.organization_name().unwrap_or_default();
+    let p = finding.path.project_name().unwrap_or_default();
+    let stages = match &finding.path {
+        ResourcePath::Instance { instance, .. } => {
+            let mut detach = Vec::new();
+            for disk in client
+                .instances()
+                .disks_get_all(instance, o, p, NameSortMode::NameAscending)
+                .await?
+            {
+                detach.push(Step::DetachDisk {
+                    instance: instance.to_string(),
+                    disk: disk.name,
+                });
+            }
+            for interface in client
+                .instances()
+                .network_interfaces_get_all(instance, o, p, NameSortMode::NameAscending)
+                .await?
+            {
+                detach.push(Step::DeleteNetworkInterface {
+                    instance: instance.to_string(),
+                    interface: interface.name,
+                });
+            }
+            vec![
+                detach,
+                vec![Step::DeleteInstance {
+                    instance: instance.to_string(),
+                }],
+            ]
+        }
+        ResourcePath::Disk { disk, .. } => vec![vec![Step::DeleteDisk {
+            disk: disk.to_string(),
+        }]],
+        ResourcePath::Snapshot { snapshot, .. } => vec![vec![Step::DeleteSnapshot {
+            snapshot: snapshot.to_string(),
+        }]],
+        ResourcePath::Image { image, .. } => vec![vec![Step::DeleteImage {
+            image: image.to_string(),
+        }]],
+        ResourcePath::Vpc { vpc, .. } => crate::teardown::vpc_stages(client, o, p, vpc)
+            .await?
+            .to_vec(),
+        ResourcePath::Project { .. } => {
+            let report = client
+                .projects()
+                .teardown(o, p, &TeardownOptions::default())
+                .await?;
+            if let Some(blocked) = report.blocked().first() {
+                anyhow::bail!("could not {}: {}", blocked.step, blocked.outcome);
+            }
+            return Ok(());
+        }
+        other => anyhow::bail!("cannot delete {}", other),
+    };
+
+    let stop_timeout = TeardownOptions::default().stop_timeout;
+    for step in stages.iter().flatten() {
+        step.execute(client, o, p, stop_timeout).await?;
+    }
+    Ok(())
+}
@@ -17,9 +17,26 @@ impl Policy {
      *
      * This function performs a `GET` to the `/policy` endpoint.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "policy_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "policy_get",
+                tag = "policy",
+                method = "GET",
+                path = "/policy",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(&self) -> Result<crate::types::FleetRolesPolicy> {
         let url = "/policy".to_string();
-        self.client.get(&url, None).await
+        self.client.get("policy_get", &url, None).await
     }
 
     /**
@@ -27,13 +44,34 @@ impl Policy {
      *
      * This function performs a `PUT` to the `/policy` endpoint.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "policy_put",
+            skip_all,
+            err,
+            fields(
+                operation_id = "policy_put",
+                tag = "policy",
+                method = "PUT",
+                path = "/policy",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn put(
         &self,
         body: &crate::types::FleetRolesPolicy,
     ) -> Result<crate::types::FleetRolesPolicy> {
         let url = "/policy".to_string();
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "policy_put",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 }
@@ -24,6 +24,23 @@ impl Projects {
      * * `sort_by: crate::types::NameOrIdSortMode` -- Supported set of sort modes for scanning by name or id.
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_projects_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organization_projects_get",
+                tag = "projects",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -48,7 +65,10 @@ impl Projects {
             query_
         );
 
-        let resp: crate::types::ProjectResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::ProjectResultsPage = self
+            .client
+            .get("organization_projects_get", &url, None)
+            .await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -61,6 +81,23 @@ impl Projects {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_projects_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organization_projects_get",
+                tag = "projects",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         organization_name: &str,
@@ -77,7 +114,10 @@ impl Projects {
             query_
         );
 
-        let mut resp: crate::types::ProjectResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::ProjectResultsPage = self
+            .client
+            .get("organization_projects_get", &url, None)
+            .await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -87,12 +127,20 @@ impl Projects {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "organization_projects_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "organization_projects_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
@@ -118,6 +166,23 @@ impl Projects {
      *
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_projects_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organization_projects_post",
+                tag = "projects",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(
         &self,
         organization_name: &str,
@@ -129,10 +194,72 @@ impl Projects {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "organization_projects_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create a new project.
+     *
+     * This function performs a `POST` to the `/organizations/{organization_name}/projects` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/organizations/{organization_name}/projects/{project_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     *
+     * **Parameters:**
+     *
+     * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_projects_ensure",
+            skip_all,
+            err,
+            fields(tag = "projects", path = "/organizations/{organization_name}/projects",)
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        organization_name: &str,
+        body: &crate::types::ProjectCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::Project>> {
+        match self.post(organization_name, body).await {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let project_name = body.name.as_str();
+                let url = format!(
+                    "/organizations/{}/projects/{}",
+                    crate::progenitor_support::encode_path(organization_name),
+                    crate::progenitor_support::encode_path(project_name),
+                );
+                let get = self
+                    .client
+                    .get("organization_projects_get_project", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "organization_projects_get_project",
+                        operation_id = "organization_projects_get_project",
+                        tag = "projects",
+                        method = "GET",
+                        path = "/organizations/{organization_name}/projects/{project_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Fetch a specific project.
      *
@@ -143,6 +270,23 @@ impl Projects {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_projects_get_project",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organization_projects_get_project",
+                tag = "projects",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(
         &self,
         organization_name: &str,
@@ -154,7 +298,9 @@ impl Projects {
             crate::progenitor_support::encode_path(project_name),
         );
 
-        self.client.get(&url, None).await
+        self.client
+            .get("organization_projects_get_project", &url, None)
+            .await
     }
 
     /**
@@ -167,6 +313,23 @@ impl Projects {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_projects_put_project",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organization_projects_put_project",
+                tag = "projects",
+                method = "PUT",
+                path = "/organizations/{organization_name}/projects/{project_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn put(
         &self,
         organization_name: &str,
@@ -180,7 +343,11 @@ impl Projects {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "organization_projects_put_project",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -194,6 +361,23 @@ impl Projects {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_projects_delete_project",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organization_projects_delete_project",
+                tag = "projects",
+                method = "DELETE",
+                path = "/organizations/{organization_name}/projects/{project_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(&self, organization_name: &str, project_name: &str) -> Result<()> {
         let url = format!(
             "/organizations/{}/projects/{}",
@@ -201,7 +385,9 @@ impl Projects {
             crate::progenitor_support::encode_path(project_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("organization_projects_delete_project", &url, None)
+            .await
     }
 
     /**
@@ -214,6 +400,23 @@ impl Projects {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_projects_get_project_policy",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organization_projects_get_project_policy",
+                tag = "projects",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/policy",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_policy(
         &self,
         organization_name: &str,
@@ -225,7 +428,9 @@ impl Projects {
             crate::progenitor_support::encode_path(project_name),
         );
 
-        self.client.get(&url, None).await
+        self.client
+            .get("organization_projects_get_project_policy", &url, None)
+            .await
     }
 
     /**
@@ -238,6 +443,23 @@ impl Projects {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "organization_projects_put_project_policy",
+            skip_all,
+            err,
+            fields(
+                operation_id = "organization_projects_put_project_policy",
+                tag = "projects",
+                method = "PUT",
+                path = "/organizations/{organization_name}/projects/{project_name}/policy",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn put_policy(
         &self,
         organization_name: &str,
@@ -251,7 +473,11 @@ impl Projects {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "organization_projects_put_project_policy",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 }
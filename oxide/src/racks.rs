@@ -25,6 +25,23 @@ impl Racks {
      *  
      *  Currently, we only support scanning in ascending order.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "hardware_racks_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "hardware_racks_get",
+                tag = "racks",
+                method = "GET",
+                path = "/hardware/racks",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -44,7 +61,8 @@ impl Racks {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/hardware/racks?{}", query_);
 
-        let resp: crate::types::RackResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::RackResultsPage =
+            self.client.get("hardware_racks_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -57,6 +75,23 @@ impl Racks {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "hardware_racks_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "hardware_racks_get",
+                tag = "racks",
+                method = "GET",
+                path = "/hardware/racks",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         sort_by: crate::types::IdSortMode,
@@ -68,7 +103,8 @@ impl Racks {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/hardware/racks?{}", query_);
 
-        let mut resp: crate::types::RackResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::RackResultsPage =
+            self.client.get("hardware_racks_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -78,12 +114,20 @@ impl Racks {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "hardware_racks_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "hardware_racks_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
@@ -109,12 +153,29 @@ impl Racks {
      *
      * * `rack_id: &str` -- The rack's unique ID.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "hardware_racks_get_rack",
+            skip_all,
+            err,
+            fields(
+                operation_id = "hardware_racks_get_rack",
+                tag = "racks",
+                method = "GET",
+                path = "/hardware/racks/{rack_id}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(&self, rack_id: &str) -> Result<crate::types::Rack> {
         let url = format!(
             "/hardware/racks/{}",
             crate::progenitor_support::encode_path(rack_id),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("hardware_racks_get_rack", &url, None).await
     }
 }
@@ -0,0 +1,140 @@
+//! What a [`Client`](crate::Client) leaves out of its logs.
+//!
+//! Credentials in the `Authorization` and cookie headers are always masked.
+//! Request and response bodies are logged with the value at each of the
+//! policy's paths masked, which by default is every `user_data` field, since
+//! that is where instance secrets usually end up.
+//!
+//! A path is a list of object keys separated by `.`, optionally starting with
+//! `$.`. A `*` matches any key, and arrays are searched element by element,
+//! so `items.*.password` masks the `password` of every object in any field
+//! of every element of `items`.
+use std::fmt;
+
+use bytes::Bytes;
+
+/// Stands in for anything that has been masked.
+pub const REDACTED: &str = "<redacted>";
+
+/// Headers whose values are always masked.
+pub(crate) const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "proxy-authorization",
+    "set-cookie",
+];
+
+/// The body fields to mask in logs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Redaction {
+    paths: Vec<Vec<String>>,
+}
+
+impl Default for Redaction {
+    fn default() -> Self {
+        Redaction::none().path("user_data")
+    }
+}
+
+impl Redaction {
+    /// Mask no body fields. Credentials in headers are still masked.
+    pub fn none() -> Self {
+        Redaction { paths: Vec::new() }
+    }
+
+    /// Also mask the value at `path`.
+    pub fn path(mut self, path: &str) -> Self {
+        let path = path.strip_prefix("$.").unwrap_or(path);
+        self.paths
+            .push(path.split('.').map(str::to_string).collect());
+        self
+    }
+
+    /// `body` as it may be logged. A body that is not JSON is left out
+    /// entirely, since there is no telling what is in it.
+    pub fn body(&self, body: &[u8]) -> String {
+        if body.is_empty() {
+            return String::new();
+        }
+        match serde_json::from_slice::<serde_json::Value>(body) {
+            Ok(mut value) => {
+                for path in &self.paths {
+                    mask(&mut value, path);
+                }
+                value.to_string()
+            }
+            Err(_) => format!("<{} bytes that are not JSON>", body.len()),
+        }
+    }
+
+    /// `headers` as they may be logged.
+    pub fn headers<'a>(&self, headers: &'a http::HeaderMap) -> RedactedHeaders<'a> {
+        RedactedHeaders(headers)
+    }
+
+    /// `request` as it may be logged.
+    pub fn request(&self, request: &http::Request<Bytes>) -> String {
+        format!(
+            "{} {} headers: {:?} body: {}",
+            request.method(),
+            request.uri(),
+            self.headers(request.headers()),
+            self.body(request.body())
+        )
+    }
+}
+
+fn mask(value: &mut serde_json::Value, path: &[String]) {
+    let (first, rest) = match path.split_first() {
+        Some(split) => split,
+        None => {
+            *value = serde_json::Value::String(REDACTED.to_string());
+            return;
+        }
+    };
+    match value {
+        serde_json::Value::Array(items) => {
+            for item in items {
+                mask(item, path);
+            }
+        }
+        serde_json::Value::Object(fields) if first == "*" => {
+            for field in fields.values_mut() {
+                mask(field, rest);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            if let Some(field) = fields.get_mut(first) {
+                mask(field, rest);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Headers with credentials masked, for `Debug` formatting.
+pub struct RedactedHeaders<'a>(&'a http::HeaderMap);
+
+impl fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(name, value)| {
+                let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                    redact_credential(value)
+                } else {
+                    String::from_utf8_lossy(value.as_bytes()).to_string()
+                };
+                (name.as_str(), value)
+            }))
+            .finish()
+    }
+}
+
+/// Keeps the scheme of an `Authorization` value, such as `Bearer`, so logs
+/// still show how a request was authenticated.
+fn redact_credential(value: &http::HeaderValue) -> String {
+    match value.to_str().ok().and_then(|v| v.split_once(' ')) {
+        Some((scheme, _)) if !scheme.contains('=') => format!("{} {}", scheme, REDACTED),
+        _ => REDACTED.to_string(),
+    }
+}
@@ -0,0 +1,557 @@
+//! Map resource ids back to the names that address them in the API.
+//!
+//! Resources refer to their parents by id, e.g. [`Disk::project_id`], while
+//! every endpoint is addressed by organization and project names. A
+//! [`ResourceIndex`] is built by walking the list endpoints once, after which
+//! any id it has seen can be resolved to a [`ResourcePath`] without further
+//! requests.
+//!
+//! [`Disk::project_id`]: crate::types::Disk::project_id
+use std::{collections::HashMap, fmt};
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{NameOrIdSortMode, NameSortMode};
+
+/// The names that address a resource, from its organization down.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum ResourcePath {
+    Organization {
+        organization: String,
+    },
+    Project {
+        organization: String,
+        project: String,
+    },
+    Vpc {
+        organization: String,
+        project: String,
+        vpc: String,
+    },
+    Subnet {
+        organization: String,
+        project: String,
+        vpc: String,
+        subnet: String,
+    },
+    Router {
+        organization: String,
+        project: String,
+        vpc: String,
+        router: String,
+    },
+    Route {
+        organization: String,
+        project: String,
+        vpc: String,
+        router: String,
+        route: String,
+    },
+    Disk {
+        organization: String,
+        project: String,
+        disk: String,
+    },
+    Instance {
+        organization: String,
+        project: String,
+        instance: String,
+    },
+    NetworkInterface {
+        organization: String,
+        project: String,
+        instance: String,
+        network_interface: String,
+    },
+    Snapshot {
+        organization: String,
+        project: String,
+        snapshot: String,
+    },
+    Image {
+        organization: String,
+        project: String,
+        image: String,
+    },
+    Silo {
+        silo: String,
+    },
+    User {
+        user: String,
+    },
+    SshKey {
+        ssh_key: String,
+    },
+}
+
+impl ResourcePath {
+    /// The name of the resource itself.
+    pub fn name(&self) -> &str {
+        match self {
+            ResourcePath::Organization { organization } => organization,
+            ResourcePath::Project { project, .. } => project,
+            ResourcePath::Vpc { vpc, .. } => vpc,
+            ResourcePath::Subnet { subnet, .. } => subnet,
+            ResourcePath::Router { router, .. } => router,
+            ResourcePath::Route { route, .. } => route,
+            ResourcePath::Disk { disk, .. } => disk,
+            ResourcePath::Instance { instance, .. } => instance,
+            ResourcePath::NetworkInterface {
+                network_interface, ..
+            } => network_interface,
+            ResourcePath::Snapshot { snapshot, .. } => snapshot,
+            ResourcePath::Image { image, .. } => image,
+            ResourcePath::Silo { silo } => silo,
+            ResourcePath::User { user } => user,
+            ResourcePath::SshKey { ssh_key } => ssh_key,
+        }
+    }
+
+    /// The organization the resource belongs to, if any.
+    pub fn organization_name(&self) -> Option<&str> {
+        match self {
+            ResourcePath::Organization { organization }
+            | ResourcePath::Project { organization, .. }
+            | ResourcePath::Vpc { organization, .. }
+            | ResourcePath::Subnet { organization, .. }
+            | ResourcePath::Router { organization, .. }
+            | ResourcePath::Route { organization, .. }
+            | ResourcePath::Disk { organization, .. }
+            | ResourcePath::Instance { organization, .. }
+            | ResourcePath::NetworkInterface { organization, .. }
+            | ResourcePath::Snapshot { organization, .. }
+            | ResourcePath::Image { organization, .. } => Some(organization),
+            ResourcePath::Silo { .. } | ResourcePath::User { .. } | ResourcePath::SshKey { .. } => {
+                None
+            }
+        }
+    }
+
+    /// The project the resource belongs to, if any.
+    pub fn project_name(&self) -> Option<&str> {
+        match self {
+            ResourcePath::Project { project, .. }
+            | ResourcePath::Vpc { project, .. }
+            | ResourcePath::Subnet { project, .. }
+            | ResourcePath::Router { project, .. }
+            | ResourcePath::Route { project, .. }
+            | ResourcePath::Disk { project, .. }
+            | ResourcePath::Instance { project, .. }
+            | ResourcePath::NetworkInterface { project, .. }
+            | ResourcePath::Snapshot { project, .. }
+            | ResourcePath::Image { project, .. } => Some(project),
+            ResourcePath::Organization { .. }
+            | ResourcePath::Silo { .. }
+            | ResourcePath::User { .. }
+            | ResourcePath::SshKey { .. } => None,
+        }
+    }
+}
+
+/// Formats the path as in `acme/web/instances/db-1`.
+impl fmt::Display for ResourcePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourcePath::Organization { organization } => write!(f, "{}", organization),
+            ResourcePath::Project {
+                organization,
+                project,
+            } => write!(f, "{}/{}", organization, project),
+            ResourcePath::Vpc {
+                organization,
+                project,
+                vpc,
+            } => write!(f, "{}/{}/vpcs/{}", organization, project, vpc),
+            ResourcePath::Subnet {
+                organization,
+                project,
+                vpc,
+                subnet,
+            } => write!(
+                f,
+                "{}/{}/vpcs/{}/subnets/{}",
+                organization, project, vpc, subnet
+            ),
+            ResourcePath::Router {
+                organization,
+                project,
+                vpc,
+                router,
+            } => write!(
+                f,
+                "{}/{}/vpcs/{}/routers/{}",
+                organization, project, vpc, router
+            ),
+            ResourcePath::Route {
+                organization,
+                project,
+                vpc,
+                router,
+                route,
+            } => write!(
+                f,
+                "{}/{}/vpcs/{}/routers/{}/routes/{}",
+                organization, project, vpc, router, route
+            ),
+            ResourcePath::Disk {
+                organization,
+                project,
+                disk,
+            } => write!(f, "{}/{}/disks/{}", organization, project, disk),
+            ResourcePath::Instance {
+                organization,
+                project,
+                instance,
+            } => write!(f, "{}/{}/instances/{}", organization, project, instance),
+            ResourcePath::NetworkInterface {
+                organization,
+                project,
+                instance,
+                network_interface,
+            } => write!(
+                f,
+                "{}/{}/instances/{}/network-interfaces/{}",
+                organization, project, instance, network_interface
+            ),
+            ResourcePath::Snapshot {
+                organization,
+                project,
+                snapshot,
+            } => write!(f, "{}/{}/snapshots/{}", organization, project, snapshot),
+            ResourcePath::Image {
+                organization,
+                project,
+                image,
+            } => write!(f, "{}/{}/images/{}", organization, project, image),
+            ResourcePath::Silo { silo } => write!(f, "silos/{}", silo),
+            ResourcePath::User { user } => write!(f, "users/{}", user),
+            ResourcePath::SshKey { ssh_key } => write!(f, "sshkeys/{}", ssh_key),
+        }
+    }
+}
+
+/// How long [`ResourceIndex::resolve_or_refresh`] waits after a refresh
+/// before refreshing again, unless changed with
+/// [`ResourceIndex::with_min_refresh_interval`].
+pub const DEFAULT_MIN_REFRESH_INTERVAL_SECS: i64 = 30;
+
+/// An in-memory index from resource ids to their paths.
+#[derive(Debug, Clone)]
+pub struct ResourceIndex {
+    paths: HashMap<String, ResourcePath>,
+    refreshed: Option<DateTime<Utc>>,
+    min_refresh_interval: Duration,
+}
+
+impl Default for ResourceIndex {
+    fn default() -> Self {
+        ResourceIndex {
+            paths: HashMap::new(),
+            refreshed: None,
+            min_refresh_interval: Duration::seconds(DEFAULT_MIN_REFRESH_INTERVAL_SECS),
+        }
+    }
+}
+
+impl ResourceIndex {
+    /// Build an index of every resource visible to the client.
+    pub async fn build(client: &crate::Client) -> Result<Self> {
+        let mut index = ResourceIndex::default();
+        index.refresh(client).await?;
+        Ok(index)
+    }
+
+    /// Rebuild the index from the list endpoints.
+    ///
+    /// The index is left unchanged if any request fails.
+    pub async fn refresh(&mut self, client: &crate::Client) -> Result<()> {
+        let started = Utc::now();
+        let mut paths = HashMap::new();
+
+        for organization in client
+            .organizations()
+            .get_all(NameOrIdSortMode::NameAscending)
+            .await?
+        {
+            let o = organization.name;
+            paths.insert(
+                organization.id,
+                ResourcePath::Organization {
+                    organization: o.to_string(),
+                },
+            );
+            for project in client
+                .projects()
+                .get_all(&o, NameOrIdSortMode::NameAscending)
+                .await?
+            {
+                paths.insert(
+                    project.id,
+                    ResourcePath::Project {
+                        organization: o.to_string(),
+                        project: project.name.to_string(),
+                    },
+                );
+                index_project(client, &mut paths, &o, &project.name).await?;
+            }
+        }
+
+        // Listing silos requires fleet-wide privileges, which not every user
+        // has, so their absence is not an error.
+        for silo in permitted(
+            client
+                .silos()
+                .get_all(NameOrIdSortMode::NameAscending)
+                .await,
+        )? {
+            paths.insert(silo.id, ResourcePath::Silo { silo: silo.name });
+        }
+        for user in permitted(client.users().get_all(NameSortMode::NameAscending).await)? {
+            paths.insert(user.id, ResourcePath::User { user: user.name });
+        }
+        for ssh_key in client
+            .sshkeys()
+            .get_all(NameSortMode::NameAscending)
+            .await?
+        {
+            paths.insert(
+                ssh_key.id,
+                ResourcePath::SshKey {
+                    ssh_key: ssh_key.name,
+                },
+            );
+        }
+
+        self.paths = paths;
+        self.refreshed = Some(started);
+        Ok(())
+    }
+
+    /// Set how long [`resolve_or_refresh`](ResourceIndex::resolve_or_refresh)
+    /// waits after a refresh before refreshing again.
+    pub fn with_min_refresh_interval(mut self, interval: Duration) -> Self {
+        self.min_refresh_interval = interval;
+        self
+    }
+
+    /// When the index was last refreshed, if ever.
+    pub fn refreshed(&self) -> Option<DateTime<Utc>> {
+        self.refreshed
+    }
+
+    /// Add or replace the path of a single resource.
+    pub fn insert(&mut self, id: &str, path: ResourcePath) {
+        self.paths.insert(id.to_string(), path);
+    }
+
+    /// Remove a resource from the index.
+    pub fn remove(&mut self, id: &str) -> Option<ResourcePath> {
+        self.paths.remove(id)
+    }
+
+    /// Look up the path of a resource.
+    pub fn resolve(&self, id: &str) -> Option<&ResourcePath> {
+        self.paths.get(id)
+    }
+
+    /// Look up the path of a resource, refreshing the index first if the id
+    /// is not known, e.g. because the resource was created since the index
+    /// was built.
+    ///
+    /// Every refresh walks the whole fleet, so the index is not refreshed
+    /// again within the minimum refresh interval of the last refresh, and ids
+    /// that are still unknown resolve to `None` until it has passed.
+    pub async fn resolve_or_refresh(
+        &mut self,
+        client: &crate::Client,
+        id: &str,
+    ) -> Result<Option<&ResourcePath>> {
+        let stale = match self.refreshed {
+            Some(refreshed) => Utc::now() - refreshed >= self.min_refresh_interval,
+            None => true,
+        };
+        if stale && !self.paths.contains_key(id) {
+            self.refresh(client).await?;
+        }
+        Ok(self.paths.get(id))
+    }
+
+    /// Format the path of a resource for display, falling back to the id
+    /// itself if it is not known.
+    pub fn display(&self, id: &str) -> String {
+        match self.resolve(id) {
+            Some(path) => path.to_string(),
+            None => id.to_string(),
+        }
+    }
+
+    /// The number of resources in the index.
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Returns whether the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+async fn index_project(
+    client: &crate::Client,
+    paths: &mut HashMap<String, ResourcePath>,
+    o: &str,
+    p: &str,
+) -> Result<()> {
+    for vpc in client
+        .vpcs()
+        .get_all(o, p, NameSortMode::NameAscending)
+        .await?
+    {
+        let v = vpc.name;
+        paths.insert(
+            vpc.id,
+            ResourcePath::Vpc {
+                organization: o.to_string(),
+                project: p.to_string(),
+                vpc: v.to_string(),
+            },
+        );
+        for subnet in client
+            .subnets()
+            .get_all(o, p, NameSortMode::NameAscending, &v)
+            .await?
+        {
+            paths.insert(
+                subnet.id,
+                ResourcePath::Subnet {
+                    organization: o.to_string(),
+                    project: p.to_string(),
+                    vpc: v.to_string(),
+                    subnet: subnet.name,
+                },
+            );
+        }
+        for router in client
+            .routers()
+            .get_all(o, p, NameSortMode::NameAscending, &v)
+            .await?
+        {
+            for route in client
+                .routes()
+                .get_all(o, p, &router.name, NameSortMode::NameAscending, &v)
+                .await?
+            {
+                paths.insert(
+                    route.id,
+                    ResourcePath::Route {
+                        organization: o.to_string(),
+                        project: p.to_string(),
+                        vpc: v.to_string(),
+                        router: router.name.to_string(),
+                        route: route.name,
+                    },
+                );
+            }
+            paths.insert(
+                router.id,
+                ResourcePath::Router {
+                    organization: o.to_string(),
+                    project: p.to_string(),
+                    vpc: v.to_string(),
+                    router: router.name,
+                },
+            );
+        }
+    }
+
+    for disk in client
+        .disks()
+        .get_all(o, p, NameSortMode::NameAscending)
+        .await?
+    {
+        paths.insert(
+            disk.id,
+            ResourcePath::Disk {
+                organization: o.to_string(),
+                project: p.to_string(),
+                disk: disk.name,
+            },
+        );
+    }
+
+    for instance in client
+        .instances()
+        .get_all(o, p, NameSortMode::NameAscending)
+        .await?
+    {
+        for network_interface in client
+            .instances()
+            .network_interfaces_get_all(&instance.name, o, p, NameSortMode::NameAscending)
+            .await?
+        {
+            paths.insert(
+                network_interface.id,
+                ResourcePath::NetworkInterface {
+                    organization: o.to_string(),
+                    project: p.to_string(),
+                    instance: instance.name.to_string(),
+                    network_interface: network_interface.name,
+                },
+            );
+        }
+        paths.insert(
+            instance.id,
+            ResourcePath::Instance {
+                organization: o.to_string(),
+                project: p.to_string(),
+                instance: instance.name,
+            },
+        );
+    }
+
+    for snapshot in client
+        .snapshots()
+        .get_all(o, p, NameSortMode::NameAscending)
+        .await?
+    {
+        paths.insert(
+            snapshot.id,
+            ResourcePath::Snapshot {
+                organization: o.to_string(),
+                project: p.to_string(),
+                snapshot: snapshot.name,
+            },
+        );
+    }
+
+    for image in client
+        .images()
+        .get_all(o, p, NameSortMode::NameAscending)
+        .await?
+    {
+        paths.insert(
+            image.id,
+            ResourcePath::Image {
+                organization: o.to_string(),
+                project: p.to_string(),
+                image: image.name,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// Treat a listing the user is not allowed to see as empty.
+fn permitted<T>(result: Result<Vec<T>>) -> Result<Vec<T>> {
+    match result {
+        Err(e) if matches!(e.downcast_ref(), Some(crate::types::Error::Forbidden)) => {
+            Ok(Vec::new())
+        }
+        result => result,
+    }
+}
@@ -0,0 +1,363 @@
+//! Scheduled disk snapshots with time-based retention.
+//!
+//! A [`RetentionPolicy`] describes, for each disk in a project, how often it
+//! should be snapshotted and how long those snapshots are kept. Snapshots are
+//! named after the disk, the frequency and the start of the period they cover,
+//! so [`RetentionPolicy::enforce`] can be run as often as desired: it creates a
+//! snapshot only when the current period has none, and deletes snapshots once
+//! they have expired. Snapshots that were not created by the policy are never
+//! touched.
+use std::{convert::TryFrom, fmt};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tabled::Tabled;
+
+use crate::{
+    declarative::Outcome,
+    types::{Disk, NameSortMode, Snapshot, SnapshotCreate},
+};
+
+/// Disk names are truncated to this length when naming their snapshots, to
+/// leave room for the suffix within the 63 character limit on names. The
+/// longest suffixes are `-weekly-YYYYMMDDHHMM` here and
+/// `-clone-YYYYMMDDHHMMSS` in [`clone`](crate::clone).
+pub(crate) const MAX_SNAPSHOT_PREFIX_LEN: usize = 40;
+
+/// How often a snapshot is taken.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Frequency {
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+impl Frequency {
+    fn period(&self) -> chrono::Duration {
+        match self {
+            Frequency::Hourly => chrono::Duration::hours(1),
+            Frequency::Daily => chrono::Duration::days(1),
+            Frequency::Weekly => chrono::Duration::weeks(1),
+        }
+    }
+
+    /// The length of `keep` periods, unless that is too long to represent.
+    fn lifetime(&self, keep: u32) -> Option<chrono::Duration> {
+        let hours = match self {
+            Frequency::Hourly => 1,
+            Frequency::Daily => 24,
+            Frequency::Weekly => 24 * 7,
+        };
+        let hours = i32::try_from(keep).ok()?.checked_mul(hours)?;
+
+        Some(chrono::Duration::hours(hours.into()))
+    }
+
+    /// The start of the period containing `time`. Weeks start on Monday.
+    fn period_start(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        // The Unix epoch fell on a Thursday.
+        let offset = match self {
+            Frequency::Weekly => chrono::Duration::days(3).num_seconds(),
+            Frequency::Hourly | Frequency::Daily => 0,
+        };
+        let into_period = (time.timestamp() + offset).rem_euclid(self.period().num_seconds());
+
+        time - chrono::Duration::seconds(into_period)
+            - chrono::Duration::nanoseconds(time.timestamp_subsec_nanos() as i64)
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Frequency::Hourly => "hourly",
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+        })
+    }
+}
+
+/// Take a snapshot every `frequency` and keep each for `keep` periods, e.g.
+/// hourly snapshots kept for 24 hours.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct RetentionRule {
+    pub frequency: Frequency,
+    pub keep: u32,
+}
+
+/// The retention rules for a single disk.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct DiskPolicy {
+    /// The name of the disk.
+    pub disk: String,
+    pub rules: Vec<RetentionRule>,
+}
+
+/// Snapshot schedules and retention for the disks in a project.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct RetentionPolicy {
+    pub organization_name: String,
+    pub project_name: String,
+    pub disks: Vec<DiskPolicy>,
+}
+
+/// A snapshot to create or delete.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "action")]
+pub enum RetentionAction {
+    Create {
+        disk: String,
+        snapshot: String,
+        frequency: Frequency,
+    },
+    Delete {
+        disk: String,
+        snapshot: String,
+        frequency: Frequency,
+        /// When the snapshot expired.
+        expired: DateTime<Utc>,
+    },
+}
+
+impl fmt::Display for RetentionAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetentionAction::Create {
+                disk,
+                snapshot,
+                frequency,
+            } => write!(
+                f,
+                "+ snapshot {} ({} snapshot of disk {})",
+                snapshot, frequency, disk
+            ),
+            RetentionAction::Delete {
+                disk,
+                snapshot,
+                frequency,
+                expired,
+            } => write!(
+                f,
+                "- snapshot {} ({} snapshot of disk {}, expired {})",
+                snapshot,
+                frequency,
+                disk,
+                expired.to_rfc3339()
+            ),
+        }
+    }
+}
+
+/// The snapshots a policy would create and delete.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct RetentionPlan {
+    pub organization_name: String,
+    pub project_name: String,
+    pub actions: Vec<RetentionAction>,
+}
+
+impl RetentionPlan {
+    /// Compute the actions needed at `now` to bring `snapshots` in line with
+    /// `policy`, given the project's `disks`.
+    ///
+    /// Policies for disks that do not exist are ignored. Fails if a rule
+    /// keeps snapshots for longer than can be represented.
+    pub fn new(
+        policy: &RetentionPolicy,
+        disks: &[Disk],
+        snapshots: &[Snapshot],
+        now: DateTime<Utc>,
+    ) -> Result<Self> {
+        let mut actions = Vec::new();
+        for disk_policy in &policy.disks {
+            let disk = match disks.iter().find(|d| d.name == disk_policy.disk) {
+                Some(disk) => disk,
+                None => continue,
+            };
+            let prefix = &disk.name[..disk.name.len().min(MAX_SNAPSHOT_PREFIX_LEN)];
+
+            for rule in &disk_policy.rules {
+                let name_prefix = format!("{}-{}-", prefix, rule.frequency);
+                let current = format!(
+                    "{}{}",
+                    name_prefix,
+                    rule.frequency.period_start(now).format("%Y%m%d%H%M")
+                );
+                let owned: Vec<&Snapshot> = snapshots
+                    .iter()
+                    .filter(|s| s.disk_id == disk.id && s.name.starts_with(&name_prefix))
+                    .collect();
+
+                if !owned.iter().any(|s| s.name == current) {
+                    actions.push(RetentionAction::Create {
+                        disk: disk.name.to_string(),
+                        snapshot: current,
+                        frequency: rule.frequency,
+                    });
+                }
+
+                let lifetime = rule.frequency.lifetime(rule.keep).ok_or_else(|| {
+                    anyhow!(
+                        "cannot keep {} snapshots of disk {} for {} periods",
+                        rule.frequency,
+                        disk.name,
+                        rule.keep
+                    )
+                })?;
+                for snapshot in owned {
+                    let expired = match snapshot
+                        .time_created
+                        .0
+                        .and_then(|created| created.checked_add_signed(lifetime))
+                    {
+                        Some(expired) => expired,
+                        None => continue,
+                    };
+                    if expired <= now {
+                        actions.push(RetentionAction::Delete {
+                            disk: disk.name.to_string(),
+                            snapshot: snapshot.name.to_string(),
+                            frequency: rule.frequency,
+                            expired,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(RetentionPlan {
+            organization_name: policy.organization_name.to_string(),
+            project_name: policy.project_name.to_string(),
+            actions,
+        })
+    }
+
+    /// Returns whether there is nothing to do.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Render the plan as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Create and delete the snapshots in the plan.
+    ///
+    /// Snapshots are created before any are deleted, so a failure to create
+    /// never leaves a disk with fewer snapshots than before.
+    pub async fn apply(&self, client: &crate::Client) -> RetentionReport {
+        let (o, p) = (self.organization_name.as_str(), self.project_name.as_str());
+        let mut results = Vec::with_capacity(self.actions.len());
+        let creates = self
+            .actions
+            .iter()
+            .filter(|a| matches!(a, RetentionAction::Create { .. }));
+        let deletes = self
+            .actions
+            .iter()
+            .filter(|a| matches!(a, RetentionAction::Delete { .. }));
+        for action in creates.chain(deletes) {
+            let result = match action {
+                RetentionAction::Create {
+                    disk,
+                    snapshot,
+                    frequency,
+                } => client
+                    .snapshots()
+                    .post(
+                        o,
+                        p,
+                        &SnapshotCreate {
+                            name: snapshot.to_string(),
+                            description: format!("{} snapshot of {}", frequency, disk),
+                            disk: disk.to_string(),
+                        },
+                    )
+                    .await
+                    .map(|_| ()),
+                RetentionAction::Delete { snapshot, .. } => {
+                    client.snapshots().delete(o, p, snapshot).await
+                }
+            };
+
+            results.push(RetentionResult {
+                action: action.clone(),
+                outcome: match result {
+                    Ok(()) => Outcome::Applied,
+                    Err(e) => Outcome::Failed {
+                        error: format!("{:#}", e),
+                    },
+                },
+            });
+        }
+
+        RetentionReport { results }
+    }
+}
+
+impl fmt::Display for RetentionPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for action in &self.actions {
+            writeln!(f, "{}", action)?;
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of a single action.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Tabled)]
+pub struct RetentionResult {
+    pub action: RetentionAction,
+    pub outcome: Outcome,
+}
+
+/// The outcome of enforcing a policy.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default)]
+pub struct RetentionReport {
+    pub results: Vec<RetentionResult>,
+}
+
+impl RetentionReport {
+    /// Returns whether every action was applied.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|r| r.outcome == Outcome::Applied)
+    }
+
+    /// Render the report as a table.
+    pub fn to_table(&self) -> String {
+        tabled::Table::new(&self.results).to_string()
+    }
+
+    /// Render the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl RetentionPolicy {
+    /// Compute the snapshots that are due to be created or deleted, without
+    /// changing anything.
+    pub async fn plan(&self, client: &crate::Client) -> Result<RetentionPlan> {
+        let (o, p) = (self.organization_name.as_str(), self.project_name.as_str());
+        let disks = client
+            .disks()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?;
+        let snapshots = client
+            .snapshots()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?;
+
+        RetentionPlan::new(self, &disks, &snapshots, Utc::now())
+    }
+
+    /// Create the snapshots that are due and delete the ones that have
+    /// expired.
+    pub async fn enforce(&self, client: &crate::Client) -> Result<RetentionReport> {
+        Ok(self.plan(client).await?.apply(client).await)
+    }
+}
@@ -22,6 +22,23 @@ impl Roles {
      * * `limit: u32` -- Maximum number of items returned by a single call.
      * * `page_token: &str` -- Token returned by previous call to retreive the subsequent page.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "roles_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "roles_get",
+                tag = "roles",
+                method = "GET",
+                path = "/roles",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(&self, limit: u32, page_token: &str) -> Result<Vec<crate::types::Role>> {
         let mut query_args: Vec<(String, String)> = Default::default();
         if !limit.to_string().is_empty() {
@@ -33,7 +50,7 @@ impl Roles {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/roles?{}", query_);
 
-        let resp: crate::types::RoleResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::RoleResultsPage = self.client.get("roles_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -46,9 +63,27 @@ impl Roles {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "roles_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "roles_get",
+                tag = "roles",
+                method = "GET",
+                path = "/roles",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(&self) -> Result<Vec<crate::types::Role>> {
         let url = "/roles".to_string();
-        let mut resp: crate::types::RoleResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::RoleResultsPage =
+            self.client.get("roles_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -58,12 +93,12 @@ impl Roles {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("roles_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("roles_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -89,12 +124,29 @@ impl Roles {
      *
      * * `role_name: &str` -- The built-in role's unique name.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "roles_get_role",
+            skip_all,
+            err,
+            fields(
+                operation_id = "roles_get_role",
+                tag = "roles",
+                method = "GET",
+                path = "/roles/{role_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(&self, role_name: &str) -> Result<crate::types::Role> {
         let url = format!(
             "/roles/{}",
             crate::progenitor_support::encode_path(role_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("roles_get_role", &url, None).await
     }
 }
@@ -28,6 +28,23 @@ impl Routers {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_routers_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_routers_get",
+                tag = "routers",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -56,7 +73,8 @@ impl Routers {
             query_
         );
 
-        let resp: crate::types::VpcRouterResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::VpcRouterResultsPage =
+            self.client.get("vpc_routers_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -69,6 +87,23 @@ impl Routers {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_routers_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_routers_get",
+                tag = "routers",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         organization_name: &str,
@@ -89,7 +124,8 @@ impl Routers {
             query_
         );
 
-        let mut resp: crate::types::VpcRouterResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::VpcRouterResultsPage =
+            self.client.get("vpc_routers_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -99,12 +135,12 @@ impl Routers {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("vpc_routers_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("vpc_routers_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -132,6 +168,23 @@ impl Routers {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_routers_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_routers_post",
+                tag = "routers",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(
         &self,
         organization_name: &str,
@@ -147,10 +200,84 @@ impl Routers {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "vpc_routers_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create a VPC Router.
+     *
+     * This function performs a `POST` to the `/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     *
+     * **Parameters:**
+     *
+     * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_routers_ensure",
+            skip_all,
+            err,
+            fields(
+                tag = "routers",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/\
+                        {vpc_name}/routers",
+            )
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        vpc_name: &str,
+        body: &crate::types::VpcRouterCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::VpcRouter>> {
+        match self
+            .post(organization_name, project_name, vpc_name, body)
+            .await
+        {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let router_name = body.name.as_str();
+                let url = format!(
+                    "/organizations/{}/projects/{}/vpcs/{}/routers/{}",
+                    crate::progenitor_support::encode_path(organization_name),
+                    crate::progenitor_support::encode_path(project_name),
+                    crate::progenitor_support::encode_path(vpc_name),
+                    crate::progenitor_support::encode_path(router_name),
+                );
+                let get = self.client.get("vpc_routers_get_router", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "vpc_routers_get_router",
+                        operation_id = "vpc_routers_get_router",
+                        tag = "routers",
+                        method = "GET",
+                        path = "/organizations/{organization_name}/projects/{project_name}/vpcs/\
+                                {vpc_name}/routers/{router_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Get a VPC Router.
      *
@@ -163,6 +290,23 @@ impl Routers {
      * * `router_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_routers_get_router",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_routers_get_router",
+                tag = "routers",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(
         &self,
         organization_name: &str,
@@ -178,7 +322,7 @@ impl Routers {
             crate::progenitor_support::encode_path(router_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("vpc_routers_get_router", &url, None).await
     }
 
     /**
@@ -193,6 +337,23 @@ impl Routers {
      * * `router_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_routers_put_router",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_routers_put_router",
+                tag = "routers",
+                method = "PUT",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn put(
         &self,
         organization_name: &str,
@@ -210,7 +371,11 @@ impl Routers {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "vpc_routers_put_router",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -226,6 +391,23 @@ impl Routers {
      * * `router_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_routers_delete_router",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_routers_delete_router",
+                tag = "routers",
+                method = "DELETE",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(
         &self,
         organization_name: &str,
@@ -241,6 +423,8 @@ impl Routers {
             crate::progenitor_support::encode_path(router_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("vpc_routers_delete_router", &url, None)
+            .await
     }
 }
@@ -29,6 +29,23 @@ impl Routes {
      * * `router_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "routers_routes_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "routers_routes_get",
+                tag = "routes",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}/routes",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -59,7 +76,8 @@ impl Routes {
             query_
         );
 
-        let resp: crate::types::RouterRouteResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::RouterRouteResultsPage =
+            self.client.get("routers_routes_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -72,6 +90,23 @@ impl Routes {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "routers_routes_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "routers_routes_get",
+                tag = "routes",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}/routes",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         organization_name: &str,
@@ -94,7 +129,8 @@ impl Routes {
             query_
         );
 
-        let mut resp: crate::types::RouterRouteResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::RouterRouteResultsPage =
+            self.client.get("routers_routes_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -104,12 +140,20 @@ impl Routes {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "routers_routes_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "routers_routes_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
@@ -138,6 +182,23 @@ impl Routes {
      * * `router_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "routers_routes_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "routers_routes_post",
+                tag = "routes",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}/routes",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(
         &self,
         organization_name: &str,
@@ -155,10 +216,87 @@ impl Routes {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "routers_routes_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create a VPC Router.
+     *
+     * This function performs a `POST` to the `/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}/routes` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}/routes/{route_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     *
+     * **Parameters:**
+     *
+     * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `router_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "routers_routes_ensure",
+            skip_all,
+            err,
+            fields(
+                tag = "routes",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/\
+                        {vpc_name}/routers/{router_name}/routes",
+            )
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        router_name: &str,
+        vpc_name: &str,
+        body: &crate::types::RouterRouteCreateParams,
+    ) -> Result<crate::utils::Ensured<crate::types::RouterRoute>> {
+        match self
+            .post(organization_name, project_name, router_name, vpc_name, body)
+            .await
+        {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let route_name = body.name.as_str();
+                let url = format!(
+                    "/organizations/{}/projects/{}/vpcs/{}/routers/{}/routes/{}",
+                    crate::progenitor_support::encode_path(organization_name),
+                    crate::progenitor_support::encode_path(project_name),
+                    crate::progenitor_support::encode_path(vpc_name),
+                    crate::progenitor_support::encode_path(router_name),
+                    crate::progenitor_support::encode_path(route_name),
+                );
+                let get = self.client.get("routers_routes_get_route", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "routers_routes_get_route",
+                        operation_id = "routers_routes_get_route",
+                        tag = "routes",
+                        method = "GET",
+                        path = "/organizations/{organization_name}/projects/{project_name}/vpcs/\
+                                {vpc_name}/routers/{router_name}/routes/{route_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Get a VPC Router route.
      *
@@ -172,6 +310,23 @@ impl Routes {
      * * `router_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "routers_routes_get_route",
+            skip_all,
+            err,
+            fields(
+                operation_id = "routers_routes_get_route",
+                tag = "routes",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}/routes/{route_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(
         &self,
         organization_name: &str,
@@ -189,7 +344,9 @@ impl Routes {
             crate::progenitor_support::encode_path(route_name),
         );
 
-        self.client.get(&url, None).await
+        self.client
+            .get("routers_routes_get_route", &url, None)
+            .await
     }
 
     /**
@@ -205,6 +362,23 @@ impl Routes {
      * * `router_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "routers_routes_put_route",
+            skip_all,
+            err,
+            fields(
+                operation_id = "routers_routes_put_route",
+                tag = "routes",
+                method = "PUT",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}/routes/{route_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn put(
         &self,
         organization_name: &str,
@@ -224,7 +398,11 @@ impl Routes {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "routers_routes_put_route",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -241,6 +419,23 @@ impl Routes {
      * * `router_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "routers_routes_delete_route",
+            skip_all,
+            err,
+            fields(
+                operation_id = "routers_routes_delete_route",
+                tag = "routes",
+                method = "DELETE",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/routers/{router_name}/routes/{route_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(
         &self,
         organization_name: &str,
@@ -258,6 +453,8 @@ impl Routes {
             crate::progenitor_support::encode_path(route_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("routers_routes_delete_route", &url, None)
+            .await
     }
 }
@@ -25,6 +25,23 @@ impl Sagas {
      *  
      *  Currently, we only support scanning in ascending order.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sagas_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "sagas_get",
+                tag = "sagas",
+                method = "GET",
+                path = "/sagas",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -44,7 +61,7 @@ impl Sagas {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/sagas?{}", query_);
 
-        let resp: crate::types::SagaResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::SagaResultsPage = self.client.get("sagas_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -57,6 +74,23 @@ impl Sagas {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sagas_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "sagas_get",
+                tag = "sagas",
+                method = "GET",
+                path = "/sagas",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         sort_by: crate::types::IdSortMode,
@@ -68,7 +102,8 @@ impl Sagas {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/sagas?{}", query_);
 
-        let mut resp: crate::types::SagaResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::SagaResultsPage =
+            self.client.get("sagas_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -78,12 +113,12 @@ impl Sagas {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("sagas_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("sagas_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -109,9 +144,26 @@ impl Sagas {
      *
      * * `saga_id: &str`
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sagas_get_saga",
+            skip_all,
+            err,
+            fields(
+                operation_id = "sagas_get_saga",
+                tag = "sagas",
+                method = "GET",
+                path = "/sagas/{saga_id}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(&self, saga_id: &str) -> Result<crate::types::Saga> {
         let url = format!("/sagas/{}", crate::progenitor_support::encode_path(saga_id),);
 
-        self.client.get(&url, None).await
+        self.client.get("sagas_get_saga", &url, None).await
     }
 }
@@ -0,0 +1,307 @@
+//! Find resources by name or id across every organization and project.
+use std::fmt;
+
+use anyhow::Result;
+use futures::{
+    future::{self, Either},
+    stream, Stream, StreamExt,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    declarative::ResourceKind,
+    resolver::ResourcePath,
+    types::{NameOrIdSortMode, NameSortMode},
+};
+
+/// The kinds of resource listed within each project.
+const PROJECT_KINDS: &[ResourceKind] = &[
+    ResourceKind::Vpc,
+    ResourceKind::Disk,
+    ResourceKind::Snapshot,
+    ResourceKind::Image,
+    ResourceKind::Instance,
+];
+
+/// What to search for.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "mode", content = "value")]
+pub enum Query {
+    /// Names equal to the value.
+    Exact(String),
+    /// Names starting with the value.
+    Prefix(String),
+    /// Names containing the value, or within a small edit distance of it,
+    /// ignoring case.
+    Fuzzy(String),
+    /// The resource with the given id.
+    Id(String),
+}
+
+impl Query {
+    /// Returns whether a resource matches the query.
+    pub fn matches(&self, id: &str, name: &str) -> bool {
+        match self {
+            Query::Exact(value) => name == value,
+            Query::Prefix(value) => name.starts_with(value.as_str()),
+            Query::Fuzzy(value) => {
+                let value = value.to_lowercase();
+                let name = name.to_lowercase();
+                let max_distance = (value.chars().count() / 3).clamp(1, 2);
+                name.contains(&value) || edit_distance(&name, &value) <= max_distance
+            }
+            Query::Id(value) => id == value,
+        }
+    }
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + if ca == *cb { 0 } else { 1 };
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Options for a search.
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    /// The kinds of resource to search for. All of organizations, projects,
+    /// VPCs, disks, snapshots, images and instances are searched if empty.
+    pub kinds: Vec<ResourceKind>,
+    /// The maximum number of list requests to make at once.
+    pub concurrency: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            kinds: Vec::new(),
+            concurrency: 4,
+        }
+    }
+}
+
+impl SearchOptions {
+    fn wants(&self, kind: ResourceKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+}
+
+/// A resource that matched a search.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, JsonSchema)]
+pub struct SearchMatch {
+    pub id: String,
+    pub path: ResourcePath,
+}
+
+impl fmt::Display for SearchMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.path, self.id)
+    }
+}
+
+/// Search every organization and project visible to the client.
+///
+/// Organizations are listed first, then the projects in each organization,
+/// then the resources in each project. At most `options.concurrency` project
+/// listings, and as many resource listings, are in flight at once. Matches
+/// are yielded as they are found, in no particular order. A failed request
+/// yields an error but does not end the search, unless it is the listing of
+/// organizations.
+pub fn search<'a>(
+    client: &'a crate::Client,
+    query: &'a Query,
+    options: &'a SearchOptions,
+) -> impl Stream<Item = Result<SearchMatch>> + 'a {
+    let concurrency = options.concurrency.max(1);
+    let list_projects = options.wants(ResourceKind::Project)
+        || PROJECT_KINDS.iter().any(|kind| options.wants(*kind));
+
+    stream::once(async move {
+        client
+            .organizations()
+            .get_all(NameOrIdSortMode::NameAscending)
+            .await
+    })
+    .flat_map(move |organizations| {
+        let organizations = match organizations {
+            Ok(organizations) => organizations,
+            Err(e) => return Either::Left(stream::once(future::ready(Err(e)))),
+        };
+
+        let matches: Vec<Result<SearchMatch>> = organizations
+            .iter()
+            .filter(|o| options.wants(ResourceKind::Organization) && query.matches(&o.id, &o.name))
+            .map(|o| {
+                Ok(SearchMatch {
+                    id: o.id.to_string(),
+                    path: ResourcePath::Organization {
+                        organization: o.name.to_string(),
+                    },
+                })
+            })
+            .collect();
+        let organizations = if list_projects {
+            organizations
+        } else {
+            Vec::new()
+        };
+
+        let within = stream::iter(organizations)
+            .map(move |o| async move {
+                let projects = client
+                    .projects()
+                    .get_all(&o.name, NameOrIdSortMode::NameAscending)
+                    .await;
+                (o.name, projects)
+            })
+            .buffer_unordered(concurrency)
+            .flat_map(move |(o, projects)| {
+                // The matching projects are ready immediately, while the
+                // resources in each project still need to be listed.
+                let projects = match projects {
+                    Ok(projects) => projects,
+                    Err(e) => return stream::iter(vec![Either::Left(future::ready(Err(e)))]),
+                };
+
+                let mut tasks = Vec::new();
+                let matches: Vec<SearchMatch> = projects
+                    .iter()
+                    .filter(|p| {
+                        options.wants(ResourceKind::Project) && query.matches(&p.id, &p.name)
+                    })
+                    .map(|p| SearchMatch {
+                        id: p.id.to_string(),
+                        path: ResourcePath::Project {
+                            organization: o.to_string(),
+                            project: p.name.to_string(),
+                        },
+                    })
+                    .collect();
+                if !matches.is_empty() {
+                    tasks.push(Either::Left(future::ready(Ok(matches))));
+                }
+                for p in projects {
+                    for kind in PROJECT_KINDS.iter().filter(|kind| options.wants(**kind)) {
+                        tasks.push(Either::Right(list_matches(
+                            client,
+                            query,
+                            o.to_string(),
+                            p.name.to_string(),
+                            *kind,
+                        )));
+                    }
+                }
+                stream::iter(tasks)
+            })
+            .buffer_unordered(concurrency)
+            .flat_map(|matches| {
+                stream::iter(match matches {
+                    Ok(matches) => matches.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                })
+            });
+
+        Either::Right(stream::iter(matches).chain(within))
+    })
+}
+
+/// List the resources of one kind in a project, keeping those that match.
+async fn list_matches(
+    client: &crate::Client,
+    query: &Query,
+    o: String,
+    p: String,
+    kind: ResourceKind,
+) -> Result<Vec<SearchMatch>> {
+    let sort = NameSortMode::NameAscending;
+    let resources: Vec<(String, ResourcePath)> = match kind {
+        ResourceKind::Vpc => client
+            .vpcs()
+            .get_all(&o, &p, sort)
+            .await?
+            .into_iter()
+            .map(|r| {
+                let path = ResourcePath::Vpc {
+                    organization: o.to_string(),
+                    project: p.to_string(),
+                    vpc: r.name,
+                };
+                (r.id, path)
+            })
+            .collect(),
+        ResourceKind::Disk => client
+            .disks()
+            .get_all(&o, &p, sort)
+            .await?
+            .into_iter()
+            .map(|r| {
+                let path = ResourcePath::Disk {
+                    organization: o.to_string(),
+                    project: p.to_string(),
+                    disk: r.name,
+                };
+                (r.id, path)
+            })
+            .collect(),
+        ResourceKind::Snapshot => client
+            .snapshots()
+            .get_all(&o, &p, sort)
+            .await?
+            .into_iter()
+            .map(|r| {
+                let path = ResourcePath::Snapshot {
+                    organization: o.to_string(),
+                    project: p.to_string(),
+                    snapshot: r.name,
+                };
+                (r.id, path)
+            })
+            .collect(),
+        ResourceKind::Image => client
+            .images()
+            .get_all(&o, &p, sort)
+            .await?
+            .into_iter()
+            .map(|r| {
+                let path = ResourcePath::Image {
+                    organization: o.to_string(),
+                    project: p.to_string(),
+                    image: r.name,
+                };
+                (r.id, path)
+            })
+            .collect(),
+        ResourceKind::Instance => client
+            .instances()
+            .get_all(&o, &p, sort)
+            .await?
+            .into_iter()
+            .map(|r| {
+                let path = ResourcePath::Instance {
+                    organization: o.to_string(),
+                    project: p.to_string(),
+                    instance: r.name,
+                };
+                (r.id, path)
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(resources
+        .into_iter()
+        .filter(|(id, path)| query.matches(id, path.name()))
+        .map(|(id, path)| SearchMatch { id, path })
+        .collect())
+}
@@ -21,6 +21,23 @@ impl Silos {
      * * `page_token: &str` -- Token returned by previous call to retreive the subsequent page.
      * * `sort_by: crate::types::NameOrIdSortMode` -- Supported set of sort modes for scanning by name or id.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "silos_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "silos_get",
+                tag = "silos",
+                method = "GET",
+                path = "/silos",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -40,7 +57,7 @@ impl Silos {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/silos?{}", query_);
 
-        let resp: crate::types::SiloResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::SiloResultsPage = self.client.get("silos_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -51,6 +68,23 @@ impl Silos {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "silos_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "silos_get",
+                tag = "silos",
+                method = "GET",
+                path = "/silos",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         sort_by: crate::types::NameOrIdSortMode,
@@ -62,7 +96,8 @@ impl Silos {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/silos?{}", query_);
 
-        let mut resp: crate::types::SiloResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::SiloResultsPage =
+            self.client.get("silos_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -72,12 +107,12 @@ impl Silos {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("silos_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("silos_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -99,13 +134,84 @@ impl Silos {
      *
      * This function performs a `POST` to the `/silos` endpoint.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "silos_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "silos_post",
+                tag = "silos",
+                method = "POST",
+                path = "/silos",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(&self, body: &crate::types::SiloCreate) -> Result<crate::types::Silo> {
         let url = "/silos".to_string();
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "silos_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create a new silo.
+     *
+     * This function performs a `POST` to the `/silos` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/silos/{silo_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "silos_ensure",
+            skip_all,
+            err,
+            fields(tag = "silos", path = "/silos",)
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        body: &crate::types::SiloCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::Silo>> {
+        match self.post(body).await {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let silo_name = body.name.as_str();
+                let url = format!(
+                    "/silos/{}",
+                    crate::progenitor_support::encode_path(silo_name),
+                );
+                let get = self.client.get("silos_get_silo", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "silos_get_silo",
+                        operation_id = "silos_get_silo",
+                        tag = "silos",
+                        method = "GET",
+                        path = "/silos/{silo_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Fetch a specific silo.
      *
@@ -115,13 +221,30 @@ impl Silos {
      *
      * * `silo_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "silos_get_silo",
+            skip_all,
+            err,
+            fields(
+                operation_id = "silos_get_silo",
+                tag = "silos",
+                method = "GET",
+                path = "/silos/{silo_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(&self, silo_name: &str) -> Result<crate::types::Silo> {
         let url = format!(
             "/silos/{}",
             crate::progenitor_support::encode_path(silo_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("silos_get_silo", &url, None).await
     }
 
     /**
@@ -133,13 +256,30 @@ impl Silos {
      *
      * * `silo_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "silos_delete_silo",
+            skip_all,
+            err,
+            fields(
+                operation_id = "silos_delete_silo",
+                tag = "silos",
+                method = "DELETE",
+                path = "/silos/{silo_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(&self, silo_name: &str) -> Result<()> {
         let url = format!(
             "/silos/{}",
             crate::progenitor_support::encode_path(silo_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client.delete("silos_delete_silo", &url, None).await
     }
 
     /**
@@ -151,13 +291,30 @@ impl Silos {
      *
      * * `silo_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "silos_get_silo_policy",
+            skip_all,
+            err,
+            fields(
+                operation_id = "silos_get_silo_policy",
+                tag = "silos",
+                method = "GET",
+                path = "/silos/{silo_name}/policy",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_policy(&self, silo_name: &str) -> Result<crate::types::SiloRolesPolicy> {
         let url = format!(
             "/silos/{}/policy",
             crate::progenitor_support::encode_path(silo_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("silos_get_silo_policy", &url, None).await
     }
 
     /**
@@ -169,6 +326,23 @@ impl Silos {
      *
      * * `silo_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "silos_put_silo_policy",
+            skip_all,
+            err,
+            fields(
+                operation_id = "silos_put_silo_policy",
+                tag = "silos",
+                method = "PUT",
+                path = "/silos/{silo_name}/policy",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn put_policy(
         &self,
         silo_name: &str,
@@ -180,7 +354,11 @@ impl Silos {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "silos_put_silo_policy",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 }
@@ -25,6 +25,23 @@ impl Sleds {
      *  
      *  Currently, we only support scanning in ascending order.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "hardware_sleds_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "hardware_sleds_get",
+                tag = "sleds",
+                method = "GET",
+                path = "/hardware/sleds",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -44,7 +61,8 @@ impl Sleds {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/hardware/sleds?{}", query_);
 
-        let resp: crate::types::SledResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::SledResultsPage =
+            self.client.get("hardware_sleds_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -57,6 +75,23 @@ impl Sleds {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "hardware_sleds_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "hardware_sleds_get",
+                tag = "sleds",
+                method = "GET",
+                path = "/hardware/sleds",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         sort_by: crate::types::IdSortMode,
@@ -68,7 +103,8 @@ impl Sleds {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/hardware/sleds?{}", query_);
 
-        let mut resp: crate::types::SledResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::SledResultsPage =
+            self.client.get("hardware_sleds_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -78,12 +114,20 @@ impl Sleds {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "hardware_sleds_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "hardware_sleds_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
@@ -109,12 +153,29 @@ impl Sleds {
      *
      * * `sled_id: &str` -- The sled's unique ID.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "hardware_sleds_get_sled",
+            skip_all,
+            err,
+            fields(
+                operation_id = "hardware_sleds_get_sled",
+                tag = "sleds",
+                method = "GET",
+                path = "/hardware/sleds/{sled_id}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(&self, sled_id: &str) -> Result<crate::types::Sled> {
         let url = format!(
             "/hardware/sleds/{}",
             crate::progenitor_support::encode_path(sled_id),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("hardware_sleds_get_sled", &url, None).await
     }
 }
@@ -27,6 +27,23 @@ impl Snapshots {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_snapshots_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_snapshots_get",
+                tag = "snapshots",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/snapshots",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -53,7 +70,8 @@ impl Snapshots {
             query_
         );
 
-        let resp: crate::types::SnapshotResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::SnapshotResultsPage =
+            self.client.get("project_snapshots_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -66,6 +84,23 @@ impl Snapshots {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_snapshots_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_snapshots_get",
+                tag = "snapshots",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/snapshots",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         organization_name: &str,
@@ -84,7 +119,8 @@ impl Snapshots {
             query_
         );
 
-        let mut resp: crate::types::SnapshotResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::SnapshotResultsPage =
+            self.client.get("project_snapshots_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -94,12 +130,20 @@ impl Snapshots {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "project_snapshots_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "project_snapshots_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
@@ -126,6 +170,23 @@ impl Snapshots {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_snapshots_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_snapshots_post",
+                tag = "snapshots",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/snapshots",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(
         &self,
         organization_name: &str,
@@ -139,10 +200,79 @@ impl Snapshots {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "project_snapshots_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create a snapshot of a disk.
+     *
+     * This function performs a `POST` to the `/organizations/{organization_name}/projects/{project_name}/snapshots` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/organizations/{organization_name}/projects/{project_name}/snapshots/{snapshot_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     *
+     * **Parameters:**
+     *
+     * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_snapshots_ensure",
+            skip_all,
+            err,
+            fields(
+                tag = "snapshots",
+                path = "/organizations/{organization_name}/projects/{project_name}/snapshots",
+            )
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        body: &crate::types::SnapshotCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::Snapshot>> {
+        match self.post(organization_name, project_name, body).await {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let snapshot_name = body.name.as_str();
+                let url = format!(
+                    "/organizations/{}/projects/{}/snapshots/{}",
+                    crate::progenitor_support::encode_path(organization_name),
+                    crate::progenitor_support::encode_path(project_name),
+                    crate::progenitor_support::encode_path(snapshot_name),
+                );
+                let get = self
+                    .client
+                    .get("project_snapshots_get_snapshot", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "project_snapshots_get_snapshot",
+                        operation_id = "project_snapshots_get_snapshot",
+                        tag = "snapshots",
+                        method = "GET",
+                        path = "/organizations/{organization_name}/projects/{project_name}/\
+                                snapshots/{snapshot_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Get a snapshot in a project.
      *
@@ -154,6 +284,23 @@ impl Snapshots {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `snapshot_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_snapshots_get_snapshot",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_snapshots_get_snapshot",
+                tag = "snapshots",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/snapshots/{snapshot_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(
         &self,
         organization_name: &str,
@@ -167,7 +314,9 @@ impl Snapshots {
             crate::progenitor_support::encode_path(snapshot_name),
         );
 
-        self.client.get(&url, None).await
+        self.client
+            .get("project_snapshots_get_snapshot", &url, None)
+            .await
     }
 
     /**
@@ -181,6 +330,23 @@ impl Snapshots {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `snapshot_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_snapshots_delete_snapshot",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_snapshots_delete_snapshot",
+                tag = "snapshots",
+                method = "DELETE",
+                path = "/organizations/{organization_name}/projects/{project_name}/snapshots/{snapshot_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(
         &self,
         organization_name: &str,
@@ -194,6 +360,8 @@ impl Snapshots {
             crate::progenitor_support::encode_path(snapshot_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("project_snapshots_delete_snapshot", &url, None)
+            .await
     }
 }
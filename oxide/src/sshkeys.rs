@@ -25,6 +25,23 @@ impl Sshkeys {
      *  
      *  Currently, we only support scanning in ascending order.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sshkeys_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "sshkeys_get",
+                tag = "sshkeys",
+                method = "GET",
+                path = "/session/me/sshkeys",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -44,7 +61,8 @@ impl Sshkeys {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/session/me/sshkeys?{}", query_);
 
-        let resp: crate::types::SshKeyResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::SshKeyResultsPage =
+            self.client.get("sshkeys_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -57,6 +75,23 @@ impl Sshkeys {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sshkeys_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "sshkeys_get",
+                tag = "sshkeys",
+                method = "GET",
+                path = "/session/me/sshkeys",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         sort_by: crate::types::NameSortMode,
@@ -68,7 +103,8 @@ impl Sshkeys {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/session/me/sshkeys?{}", query_);
 
-        let mut resp: crate::types::SshKeyResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::SshKeyResultsPage =
+            self.client.get("sshkeys_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -78,12 +114,12 @@ impl Sshkeys {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("sshkeys_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("sshkeys_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -105,13 +141,84 @@ impl Sshkeys {
      *
      * This function performs a `POST` to the `/session/me/sshkeys` endpoint.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sshkeys_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "sshkeys_post",
+                tag = "sshkeys",
+                method = "POST",
+                path = "/session/me/sshkeys",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(&self, body: &crate::types::SshKeyCreate) -> Result<crate::types::SshKey> {
         let url = "/session/me/sshkeys".to_string();
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "sshkeys_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create a new SSH public key for the current user.
+     *
+     * This function performs a `POST` to the `/session/me/sshkeys` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/session/me/sshkeys/{ssh_key_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sshkeys_ensure",
+            skip_all,
+            err,
+            fields(tag = "sshkeys", path = "/session/me/sshkeys",)
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        body: &crate::types::SshKeyCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::SshKey>> {
+        match self.post(body).await {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let ssh_key_name = body.name.as_str();
+                let url = format!(
+                    "/session/me/sshkeys/{}",
+                    crate::progenitor_support::encode_path(ssh_key_name),
+                );
+                let get = self.client.get("sshkeys_get_key", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "sshkeys_get_key",
+                        operation_id = "sshkeys_get_key",
+                        tag = "sshkeys",
+                        method = "GET",
+                        path = "/session/me/sshkeys/{ssh_key_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Get (by name) an SSH public key belonging to the current user.
      *
@@ -121,13 +228,30 @@ impl Sshkeys {
      *
      * * `ssh_key_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sshkeys_get_key",
+            skip_all,
+            err,
+            fields(
+                operation_id = "sshkeys_get_key",
+                tag = "sshkeys",
+                method = "GET",
+                path = "/session/me/sshkeys/{ssh_key_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_key(&self, ssh_key_name: &str) -> Result<crate::types::SshKey> {
         let url = format!(
             "/session/me/sshkeys/{}",
             crate::progenitor_support::encode_path(ssh_key_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("sshkeys_get_key", &url, None).await
     }
 
     /**
@@ -139,12 +263,29 @@ impl Sshkeys {
      *
      * * `ssh_key_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sshkeys_delete_key",
+            skip_all,
+            err,
+            fields(
+                operation_id = "sshkeys_delete_key",
+                tag = "sshkeys",
+                method = "DELETE",
+                path = "/session/me/sshkeys/{ssh_key_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete_key(&self, ssh_key_name: &str) -> Result<()> {
         let url = format!(
             "/session/me/sshkeys/{}",
             crate::progenitor_support::encode_path(ssh_key_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client.delete("sshkeys_delete_key", &url, None).await
     }
 }
@@ -28,6 +28,23 @@ impl Subnets {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_subnets_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_subnets_get",
+                tag = "subnets",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/subnets",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -56,7 +73,8 @@ impl Subnets {
             query_
         );
 
-        let resp: crate::types::VpcSubnetResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::VpcSubnetResultsPage =
+            self.client.get("vpc_subnets_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -69,6 +87,23 @@ impl Subnets {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_subnets_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_subnets_get",
+                tag = "subnets",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/subnets",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         organization_name: &str,
@@ -89,7 +124,8 @@ impl Subnets {
             query_
         );
 
-        let mut resp: crate::types::VpcSubnetResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::VpcSubnetResultsPage =
+            self.client.get("vpc_subnets_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -99,12 +135,12 @@ impl Subnets {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("vpc_subnets_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("vpc_subnets_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -132,6 +168,23 @@ impl Subnets {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_subnets_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_subnets_post",
+                tag = "subnets",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/subnets",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(
         &self,
         organization_name: &str,
@@ -147,10 +200,84 @@ impl Subnets {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "vpc_subnets_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create a subnet in a VPC.
+     *
+     * This function performs a `POST` to the `/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/subnets` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/subnets/{subnet_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     *
+     * **Parameters:**
+     *
+     * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_subnets_ensure",
+            skip_all,
+            err,
+            fields(
+                tag = "subnets",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/\
+                        {vpc_name}/subnets",
+            )
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        vpc_name: &str,
+        body: &crate::types::VpcSubnetCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::VpcSubnet>> {
+        match self
+            .post(organization_name, project_name, vpc_name, body)
+            .await
+        {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let subnet_name = body.name.as_str();
+                let url = format!(
+                    "/organizations/{}/projects/{}/vpcs/{}/subnets/{}",
+                    crate::progenitor_support::encode_path(organization_name),
+                    crate::progenitor_support::encode_path(project_name),
+                    crate::progenitor_support::encode_path(vpc_name),
+                    crate::progenitor_support::encode_path(subnet_name),
+                );
+                let get = self.client.get("vpc_subnets_get_subnet", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "vpc_subnets_get_subnet",
+                        operation_id = "vpc_subnets_get_subnet",
+                        tag = "subnets",
+                        method = "GET",
+                        path = "/organizations/{organization_name}/projects/{project_name}/vpcs/\
+                                {vpc_name}/subnets/{subnet_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Get subnet in a VPC.
      *
@@ -163,6 +290,23 @@ impl Subnets {
      * * `subnet_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_subnets_get_subnet",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_subnets_get_subnet",
+                tag = "subnets",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/subnets/{subnet_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(
         &self,
         organization_name: &str,
@@ -178,7 +322,7 @@ impl Subnets {
             crate::progenitor_support::encode_path(subnet_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("vpc_subnets_get_subnet", &url, None).await
     }
 
     /**
@@ -193,6 +337,23 @@ impl Subnets {
      * * `subnet_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_subnets_put_subnet",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_subnets_put_subnet",
+                tag = "subnets",
+                method = "PUT",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/subnets/{subnet_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn put(
         &self,
         organization_name: &str,
@@ -210,7 +371,11 @@ impl Subnets {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "vpc_subnets_put_subnet",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -226,6 +391,23 @@ impl Subnets {
      * * `subnet_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "vpc_subnets_delete_subnet",
+            skip_all,
+            err,
+            fields(
+                operation_id = "vpc_subnets_delete_subnet",
+                tag = "subnets",
+                method = "DELETE",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/subnets/{subnet_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(
         &self,
         organization_name: &str,
@@ -241,7 +423,9 @@ impl Subnets {
             crate::progenitor_support::encode_path(subnet_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("vpc_subnets_delete_subnet", &url, None)
+            .await
     }
 
     /**
@@ -261,6 +445,23 @@ impl Subnets {
      * * `subnet_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "subnet_network_interfaces_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "subnet_network_interfaces_get",
+                tag = "subnets",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/subnets/{subnet_name}/network-interfaces",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn network_interfaces_get(
         &self,
         limit: u32,
@@ -291,7 +492,10 @@ impl Subnets {
             query_
         );
 
-        let resp: crate::types::NetworkInterfaceResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::NetworkInterfaceResultsPage = self
+            .client
+            .get("subnet_network_interfaces_get", &url, None)
+            .await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -304,6 +508,23 @@ impl Subnets {
      *
      * As opposed to `network_interfaces_get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "subnet_network_interfaces_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "subnet_network_interfaces_get",
+                tag = "subnets",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}/subnets/{subnet_name}/network-interfaces",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn network_interfaces_get_all(
         &self,
         organization_name: &str,
@@ -326,8 +547,10 @@ impl Subnets {
             query_
         );
 
-        let mut resp: crate::types::NetworkInterfaceResultsPage =
-            self.client.get(&url, None).await?;
+        let mut resp: crate::types::NetworkInterfaceResultsPage = self
+            .client
+            .get("subnet_network_interfaces_get", &url, None)
+            .await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -337,12 +560,20 @@ impl Subnets {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get(
+                        "subnet_network_interfaces_get",
+                        &format!("{}?page={}", url, page),
+                        None,
+                    )
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get(
+                        "subnet_network_interfaces_get",
+                        &format!("{}&page={}", url, page),
+                        None,
+                    )
                     .await?;
             }
 
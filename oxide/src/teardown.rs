@@ -0,0 +1,515 @@
+//! Cascading deletion of a project and everything in it.
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tabled::Tabled;
+
+use crate::{
+    declarative::Outcome,
+    types::{DiskIdentifier, InstanceState, NameSortMode, RouterRouteKind, VpcRouterKind},
+};
+
+/// How often to check whether a stopping instance has stopped.
+const STOP_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A single step in tearing down a project.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "step")]
+pub enum Step {
+    StopInstance {
+        instance: String,
+    },
+    DetachDisk {
+        instance: String,
+        disk: String,
+    },
+    DeleteNetworkInterface {
+        instance: String,
+        interface: String,
+    },
+    DeleteInstance {
+        instance: String,
+    },
+    DeleteDisk {
+        disk: String,
+    },
+    DeleteSnapshot {
+        snapshot: String,
+    },
+    DeleteImage {
+        image: String,
+    },
+    DeleteSubnet {
+        vpc: String,
+        subnet: String,
+    },
+    DeleteRoute {
+        vpc: String,
+        router: String,
+        route: String,
+    },
+    DeleteRouter {
+        vpc: String,
+        router: String,
+    },
+    DeleteVpc {
+        vpc: String,
+    },
+    DeleteProject,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::StopInstance { instance } => write!(f, "stop instance {}", instance),
+            Step::DetachDisk { instance, disk } => {
+                write!(f, "detach disk {} from instance {}", disk, instance)
+            }
+            Step::DeleteNetworkInterface {
+                instance,
+                interface,
+            } => write!(f, "delete network interface {}/{}", instance, interface),
+            Step::DeleteInstance { instance } => write!(f, "delete instance {}", instance),
+            Step::DeleteDisk { disk } => write!(f, "delete disk {}", disk),
+            Step::DeleteSnapshot { snapshot } => write!(f, "delete snapshot {}", snapshot),
+            Step::DeleteImage { image } => write!(f, "delete image {}", image),
+            Step::DeleteSubnet { vpc, subnet } => write!(f, "delete subnet {}/{}", vpc, subnet),
+            Step::DeleteRoute { vpc, router, route } => {
+                write!(f, "delete route {}/{}/{}", vpc, router, route)
+            }
+            Step::DeleteRouter { vpc, router } => write!(f, "delete router {}/{}", vpc, router),
+            Step::DeleteVpc { vpc } => write!(f, "delete vpc {}", vpc),
+            Step::DeleteProject => write!(f, "delete project"),
+        }
+    }
+}
+
+impl Step {
+    pub(crate) async fn execute(
+        &self,
+        client: &crate::Client,
+        organization_name: &str,
+        project_name: &str,
+        stop_timeout: Duration,
+    ) -> Result<()> {
+        let (o, p) = (organization_name, project_name);
+        match self {
+            Step::StopInstance { instance } => {
+                client.instances().stop(instance, o, p).await?;
+                let deadline = Instant::now() + stop_timeout;
+                loop {
+                    let state = client.instances().get(instance, o, p).await?.run_state;
+                    match state {
+                        InstanceState::Stopped => break,
+                        InstanceState::Failed | InstanceState::Destroyed => {
+                            return Err(anyhow!("instance is {} and cannot be stopped", state))
+                        }
+                        _ if Instant::now() >= deadline => {
+                            return Err(anyhow!(
+                                "instance is still {} after {:?}",
+                                state,
+                                stop_timeout
+                            ))
+                        }
+                        _ => tokio::time::sleep(STOP_POLL_INTERVAL).await,
+                    }
+                }
+            }
+            Step::DetachDisk { instance, disk } => {
+                client
+                    .instances()
+                    .disks_detach(
+                        instance,
+                        o,
+                        p,
+                        &DiskIdentifier {
+                            name: disk.to_string(),
+                        },
+                    )
+                    .await?;
+            }
+            Step::DeleteNetworkInterface {
+                instance,
+                interface,
+            } => {
+                client
+                    .instances()
+                    .network_interfaces_delete_interface(instance, interface, o, p)
+                    .await?
+            }
+            Step::DeleteInstance { instance } => client.instances().delete(instance, o, p).await?,
+            Step::DeleteDisk { disk } => client.disks().delete(disk, o, p).await?,
+            Step::DeleteSnapshot { snapshot } => client.snapshots().delete(o, p, snapshot).await?,
+            Step::DeleteImage { image } => client.images().delete(image, o, p).await?,
+            Step::DeleteSubnet { vpc, subnet } => {
+                client.subnets().delete(o, p, subnet, vpc).await?
+            }
+            Step::DeleteRoute { vpc, router, route } => {
+                client.routes().delete(o, p, route, router, vpc).await?
+            }
+            Step::DeleteRouter { vpc, router } => {
+                client.routers().delete(o, p, router, vpc).await?
+            }
+            Step::DeleteVpc { vpc } => client.vpcs().delete(o, p, vpc).await?,
+            Step::DeleteProject => client.projects().delete(o, p).await?,
+        }
+
+        Ok(())
+    }
+}
+
+/// The steps needed to delete a project, grouped into stages.
+///
+/// The steps within a stage are independent of one another and may run
+/// concurrently. Each stage depends on every stage before it.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct TeardownPlan {
+    pub organization_name: String,
+    pub project_name: String,
+    pub stages: Vec<Vec<Step>>,
+}
+
+impl TeardownPlan {
+    /// Every step in the plan, in order.
+    pub fn steps(&self) -> impl Iterator<Item = &Step> {
+        self.stages.iter().flatten()
+    }
+
+    /// Render the plan as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Run the plan one stage at a time.
+    ///
+    /// If any step in a stage fails, the remaining stages are skipped, since
+    /// they depend on it.
+    pub async fn execute(
+        &self,
+        client: &crate::Client,
+        options: &TeardownOptions,
+    ) -> TeardownReport {
+        let mut results = Vec::new();
+        let mut blocked = false;
+        for stage in &self.stages {
+            if blocked {
+                results.extend(stage.iter().map(|step| StepResult {
+                    step: step.clone(),
+                    outcome: Outcome::Skipped {
+                        reason: "an earlier step failed".to_string(),
+                    },
+                }));
+                continue;
+            }
+
+            let outcomes: Vec<StepResult> = stream::iter(stage)
+                .map(|step| async move {
+                    let outcome = match step
+                        .execute(
+                            client,
+                            &self.organization_name,
+                            &self.project_name,
+                            options.stop_timeout,
+                        )
+                        .await
+                    {
+                        Ok(()) => Outcome::Applied,
+                        Err(e) => Outcome::Failed {
+                            error: format!("{:#}", e),
+                        },
+                    };
+                    StepResult {
+                        step: step.clone(),
+                        outcome,
+                    }
+                })
+                .buffered(options.concurrency.max(1))
+                .collect()
+                .await;
+
+            blocked = outcomes.iter().any(|r| r.outcome != Outcome::Applied);
+            results.extend(outcomes);
+        }
+
+        TeardownReport {
+            plan: self.clone(),
+            dry_run: false,
+            results,
+        }
+    }
+}
+
+impl fmt::Display for TeardownPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "teardown of project {}/{}:",
+            self.organization_name, self.project_name
+        )?;
+        for (i, stage) in self.stages.iter().enumerate() {
+            writeln!(f, "  stage {}:", i + 1)?;
+            for step in stage {
+                writeln!(f, "    {}", step)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Options for tearing down a project.
+#[derive(Debug, Clone)]
+pub struct TeardownOptions {
+    /// Only compute the plan; do not change anything.
+    pub dry_run: bool,
+    /// The maximum number of steps to run at once.
+    pub concurrency: usize,
+    /// How long to wait for each instance to stop.
+    pub stop_timeout: Duration,
+}
+
+impl Default for TeardownOptions {
+    fn default() -> Self {
+        TeardownOptions {
+            dry_run: false,
+            concurrency: 4,
+            stop_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// The outcome of a single step.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Tabled)]
+pub struct StepResult {
+    pub step: Step,
+    pub outcome: Outcome,
+}
+
+/// The outcome of tearing down a project.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct TeardownReport {
+    pub plan: TeardownPlan,
+    /// Whether this was a dry run, in which case `results` is empty.
+    pub dry_run: bool,
+    pub results: Vec<StepResult>,
+}
+
+impl TeardownReport {
+    /// Returns whether the project was deleted.
+    pub fn is_complete(&self) -> bool {
+        !self.dry_run
+            && self.results.len() == self.plan.steps().count()
+            && self.results.iter().all(|r| r.outcome == Outcome::Applied)
+    }
+
+    /// The steps that failed and so kept the teardown from completing.
+    pub fn blocked(&self) -> Vec<&StepResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, Outcome::Failed { .. }))
+            .collect()
+    }
+
+    /// Render the results as a table.
+    pub fn to_table(&self) -> String {
+        tabled::Table::new(&self.results).to_string()
+    }
+
+    /// Render the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl crate::projects::Projects {
+    /// Compute the steps needed to delete a project and everything in it.
+    pub async fn teardown_plan(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+    ) -> Result<TeardownPlan> {
+        let (o, p) = (organization_name, project_name);
+        let client = &self.client;
+
+        let instances = client
+            .instances()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?;
+        let mut stop = Vec::new();
+        let mut detach = Vec::new();
+        let mut delete_instances = Vec::new();
+        for instance in &instances {
+            if !matches!(
+                instance.run_state,
+                InstanceState::Stopped | InstanceState::Destroyed
+            ) {
+                stop.push(Step::StopInstance {
+                    instance: instance.name.to_string(),
+                });
+            }
+            for disk in client
+                .instances()
+                .disks_get_all(&instance.name, o, p, NameSortMode::NameAscending)
+                .await?
+            {
+                detach.push(Step::DetachDisk {
+                    instance: instance.name.to_string(),
+                    disk: disk.name,
+                });
+            }
+            for interface in client
+                .instances()
+                .network_interfaces_get_all(&instance.name, o, p, NameSortMode::NameAscending)
+                .await?
+            {
+                detach.push(Step::DeleteNetworkInterface {
+                    instance: instance.name.to_string(),
+                    interface: interface.name,
+                });
+            }
+            delete_instances.push(Step::DeleteInstance {
+                instance: instance.name.to_string(),
+            });
+        }
+
+        let disks = client
+            .disks()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?
+            .into_iter()
+            .map(|d| Step::DeleteDisk { disk: d.name })
+            .collect();
+        let snapshots = client
+            .snapshots()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?
+            .into_iter()
+            .map(|s| Step::DeleteSnapshot { snapshot: s.name })
+            .collect();
+        let images = client
+            .images()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?
+            .into_iter()
+            .map(|i| Step::DeleteImage { image: i.name })
+            .collect();
+
+        let mut subnets_and_routes = Vec::new();
+        let mut routers = Vec::new();
+        let mut vpcs = Vec::new();
+        for vpc in client
+            .vpcs()
+            .get_all(o, p, NameSortMode::NameAscending)
+            .await?
+        {
+            let [s, r, v] = vpc_stages(client, o, p, &vpc.name).await?;
+            subnets_and_routes.extend(s);
+            routers.extend(r);
+            vpcs.extend(v);
+        }
+
+        let stages = vec![
+            stop,
+            detach,
+            delete_instances,
+            disks,
+            snapshots,
+            images,
+            subnets_and_routes,
+            routers,
+            vpcs,
+            vec![Step::DeleteProject],
+        ];
+
+        Ok(TeardownPlan {
+            organization_name: o.to_string(),
+            project_name: p.to_string(),
+            stages: stages.into_iter().filter(|s| !s.is_empty()).collect(),
+        })
+    }
+
+    /// Delete a project and everything in it.
+    ///
+    /// Running instances are stopped and their disks detached, then the
+    /// project's resources are deleted in dependency order. With
+    /// `options.dry_run` set, only the plan is computed.
+    pub async fn teardown(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        options: &TeardownOptions,
+    ) -> Result<TeardownReport> {
+        let plan = self.teardown_plan(organization_name, project_name).await?;
+        if options.dry_run {
+            return Ok(TeardownReport {
+                plan,
+                dry_run: true,
+                results: Vec::new(),
+            });
+        }
+
+        Ok(plan.execute(&self.client, options).await)
+    }
+}
+
+/// The steps needed to delete a VPC: its subnets and custom routes, then its
+/// custom routers, then the VPC itself.
+pub(crate) async fn vpc_stages(
+    client: &crate::Client,
+    organization_name: &str,
+    project_name: &str,
+    vpc_name: &str,
+) -> Result<[Vec<Step>; 3]> {
+    let (o, p) = (organization_name, project_name);
+    let mut subnets_and_routes = Vec::new();
+    let mut routers = Vec::new();
+    for subnet in client
+        .subnets()
+        .get_all(o, p, NameSortMode::NameAscending, vpc_name)
+        .await?
+    {
+        subnets_and_routes.push(Step::DeleteSubnet {
+            vpc: vpc_name.to_string(),
+            subnet: subnet.name,
+        });
+    }
+    for router in client
+        .routers()
+        .get_all(o, p, NameSortMode::NameAscending, vpc_name)
+        .await?
+    {
+        // System routers and routes go away with their VPC.
+        for route in client
+            .routes()
+            .get_all(o, p, &router.name, NameSortMode::NameAscending, vpc_name)
+            .await?
+            .into_iter()
+            .filter(|r| r.kind == RouterRouteKind::Custom)
+        {
+            subnets_and_routes.push(Step::DeleteRoute {
+                vpc: vpc_name.to_string(),
+                router: router.name.to_string(),
+                route: route.name,
+            });
+        }
+        if router.kind != VpcRouterKind::System {
+            routers.push(Step::DeleteRouter {
+                vpc: vpc_name.to_string(),
+                router: router.name,
+            });
+        }
+    }
+
+    Ok([
+        subnets_and_routes,
+        routers,
+        vec![Step::DeleteVpc {
+            vpc: vpc_name.to_string(),
+        }],
+    ])
+}
@@ -11,7 +11,7 @@ fn test_route_target() {
     let mut route_target_from_str = crate::types::RouteTarget::from_str("instance=test").unwrap();
     assert_eq!(route_target_from_str, route_target);
 
-    route_target = crate::types::RouteTarget::Ip("192.1.13.2".to_string());
+    route_target = crate::types::RouteTarget::Ip("192.1.13.2".parse().unwrap());
     route_target_str = format!("{}", route_target);
     assert_eq!(route_target_str, "ip=192.1.13.2");
 
@@ -56,7 +56,7 @@ fn test_route_destination() {
         crate::types::RouteDestination::from_str("vpc=test").unwrap();
     assert_eq!(route_destination_from_str, route_destination);
 
-    route_destination = crate::types::RouteDestination::Ip("192.1.13.2".to_string());
+    route_destination = crate::types::RouteDestination::Ip("192.1.13.2".parse().unwrap());
     route_destination_str = format!("{}", route_destination);
     assert_eq!(route_destination_str, "ip=192.1.13.2");
 
@@ -149,3 +149,1910 @@ fn test_disk_source_type() {
     disk_source_type_from_str = crate::types::DiskSourceType::from_str("global_image").unwrap();
     assert_eq!(disk_source_type_from_str, disk_source_type);
 }
+
+#[test]
+fn test_subnet_utilization() {
+    let subnet: crate::types::VpcSubnet = serde_json::from_value(serde_json::json!({
+        "name": "frontend",
+        "ipv4_block": "172.30.0.0/28",
+        "ipv6_block": "fd12:3456::/64",
+        "time_created": "2022-05-01T00:00:00Z",
+        "time_modified": "2022-05-01T00:00:00Z",
+    }))
+    .unwrap();
+    let nic = |ip: &str| -> crate::types::NetworkInterface {
+        serde_json::from_value(serde_json::json!({
+            "ip": ip,
+            "mac": "a8:40:25:ff:00:01",
+            "time_created": "2022-05-01T00:00:00Z",
+            "time_modified": "2022-05-01T00:00:00Z",
+        }))
+        .unwrap()
+    };
+
+    // A /28 has 16 addresses, the first 5 and the broadcast are reserved.
+    let mut interfaces = vec![nic("172.30.0.5"), nic("172.30.0.7")];
+    let utilization = crate::utilization::SubnetUtilization::new(&subnet, &interfaces);
+    assert_eq!(utilization.capacity, 10);
+    assert_eq!(utilization.used, 2);
+    assert_eq!(utilization.free, 8);
+    assert!(!utilization.is_near_exhaustion(50.0));
+
+    let free = crate::utilization::free_ipv4(&subnet, &interfaces);
+    assert_eq!(free, Some(std::net::Ipv4Addr::new(172, 30, 0, 6)));
+
+    // Addresses outside of the block are not counted.
+    interfaces.push(nic("10.0.0.6"));
+    let utilization = crate::utilization::SubnetUtilization::new(&subnet, &interfaces);
+    assert_eq!(utilization.used, 2);
+
+    interfaces = (5..15).map(|i| nic(&format!("172.30.0.{}", i))).collect();
+    let utilization = crate::utilization::SubnetUtilization::new(&subnet, &interfaces);
+    assert_eq!(utilization.free, 0);
+    assert!(utilization.is_near_exhaustion(100.0));
+    assert_eq!(crate::utilization::free_ipv4(&subnet, &interfaces), None);
+}
+
+#[test]
+fn test_network_address_types() {
+    let mac = crate::types::MacAddr::from_str("a8:40:25:FF:00:01").unwrap();
+    assert_eq!(mac.0, [0xa8, 0x40, 0x25, 0xff, 0x00, 0x01]);
+    assert_eq!(format!("{}", mac), "a8:40:25:ff:00:01");
+    assert!(crate::types::MacAddr::from_str("a8:40:25:ff:00").is_err());
+    assert!(crate::types::MacAddr::from_str("a8:40:25:ff:00:01:02").is_err());
+    assert!(crate::types::MacAddr::from_str("a8:40:25:ff:00:zz").is_err());
+    assert!(crate::types::MacAddr::from_str("a8:40:25:ff:00:+f").is_err());
+
+    let nic = serde_json::json!({
+        "ip": "172.30.0.5",
+        "mac": "a8:40:25:ff:00:01",
+        "time_created": "2022-05-01T00:00:00Z",
+        "time_modified": "2022-05-01T00:00:00Z",
+    });
+    let parsed: crate::types::NetworkInterface = serde_json::from_value(nic.clone()).unwrap();
+    assert_eq!(parsed.ip, std::net::Ipv4Addr::new(172, 30, 0, 5));
+    assert_eq!(parsed.mac, mac);
+
+    // Invalid addresses are rejected when deserializing.
+    let mut invalid = nic.clone();
+    invalid["ip"] = "172.30.0".into();
+    assert!(serde_json::from_value::<crate::types::NetworkInterface>(invalid).is_err());
+    let mut invalid = nic;
+    invalid["mac"] = "not-a-mac".into();
+    assert!(serde_json::from_value::<crate::types::NetworkInterface>(invalid).is_err());
+
+    let create = crate::types::VpcSubnetCreate {
+        name: "frontend".to_string(),
+        description: String::new(),
+        ipv4_block: crate::types::Ipv4Net::from_str("172.30.0.0/22").unwrap(),
+        ipv6_block: None,
+    };
+    assert_eq!(
+        serde_json::to_value(&create).unwrap(),
+        serde_json::json!({"name": "frontend", "ipv4_block": "172.30.0.0/22"})
+    );
+}
+
+#[test]
+fn test_vpc_topology() {
+    let time = "2022-05-01T00:00:00Z";
+    let resources = crate::topology::VpcResources {
+        vpc: serde_json::from_value(serde_json::json!({
+            "id": "vpc-1", "name": "default", "ipv6_prefix": "fd12:3456::/48",
+            "time_created": time, "time_modified": time,
+        }))
+        .unwrap(),
+        subnets: vec![serde_json::from_value(serde_json::json!({
+            "id": "subnet-1", "name": "frontend", "ipv4_block": "172.30.0.0/22",
+            "ipv6_block": "fd12:3456::/64", "time_created": time, "time_modified": time,
+        }))
+        .unwrap()],
+        routers: vec![serde_json::from_value(serde_json::json!({
+            "id": "router-1", "name": "system", "kind": "system",
+            "time_created": time, "time_modified": time,
+        }))
+        .unwrap()],
+        routes: vec![serde_json::from_value(serde_json::json!({
+            "id": "route-1", "name": "default", "kind": "default", "vpc_router_id": "router-1",
+            "destination": {"type": "vpc", "value": "default"},
+            "target": {"type": "internet_gateway", "value": "outbound"},
+            "time_created": time, "time_modified": time,
+        }))
+        .unwrap()],
+        network_interfaces: vec![serde_json::from_value(serde_json::json!({
+            "id": "nic-1", "name": "net0", "instance_id": "instance-1", "subnet_id": "subnet-1",
+            "ip": "172.30.0.5", "mac": "a8:40:25:ff:00:01", "time_created": time,
+            "time_modified": time,
+        }))
+        .unwrap()],
+        instances: vec![
+            serde_json::from_value(serde_json::json!({
+                "id": "instance-1", "name": "db-1", "ncpus": 2, "run_state": "running",
+                "time_created": time, "time_modified": time, "time_run_state_updated": time,
+            }))
+            .unwrap(),
+            serde_json::from_value(serde_json::json!({
+                "id": "instance-2", "name": "detached", "ncpus": 2, "run_state": "stopped",
+                "time_created": time, "time_modified": time, "time_run_state_updated": time,
+            }))
+            .unwrap(),
+        ],
+        firewall_rules: vec![serde_json::from_value(serde_json::json!({
+            "id": "rule-1", "name": "allow-ssh", "action": "allow", "direction": "inbound",
+            "priority": 65534, "status": "enabled",
+            "filters": {"ports": ["22"], "protocols": ["tcp"]},
+            "targets": [{"type": "instance", "value": "db-1"}, {"type": "ip", "value": "172.30.0.5"}],
+            "time_created": time, "time_modified": time,
+        }))
+        .unwrap()],
+    };
+
+    let topology = crate::topology::Topology::new(&resources);
+    let ids: Vec<&str> = topology.nodes.iter().map(|n| n.id.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "vpc-1",
+            "subnet-1",
+            "nic-1",
+            "instance-1",
+            "router-1",
+            "route-1",
+            "external:inetgw=outbound"
+        ]
+    );
+    let instance = topology
+        .nodes
+        .iter()
+        .find(|n| n.id == "instance-1")
+        .unwrap();
+    assert_eq!(
+        instance.firewall_rules,
+        vec!["allow-ssh: allow inbound tcp ports 22".to_string()]
+    );
+    let nic = topology.nodes.iter().find(|n| n.id == "nic-1").unwrap();
+    assert_eq!(nic.firewall_rules, instance.firewall_rules);
+
+    let dot = topology.to_dot();
+    assert!(dot.starts_with("digraph \"default\" {\n"));
+    assert!(dot.contains("    \"subnet-1\" -> \"nic-1\" [label=\"interface\"];\n"));
+    assert!(dot.contains("    \"route-1\" -> \"external:inetgw=outbound\" [label=\"target\"];\n"));
+    assert!(dot.contains(
+        "    \"instance-1\" [label=\"instance\\ndb-1\\nrunning\\n[allow-ssh: allow inbound tcp \
+         ports 22]\", shape=component];\n"
+    ));
+
+    let json: serde_json::Value = serde_json::from_str(&topology.to_json().unwrap()).unwrap();
+    assert_eq!(json["nodes"][0]["kind"], "vpc");
+    assert_eq!(
+        json["edges"].as_array().unwrap().len(),
+        topology.edges.len()
+    );
+}
+
+#[test]
+fn test_declarative_plan() {
+    let desired = crate::declarative::Manifest::from_yaml(
+        r#"
+organizations:
+  - name: acme
+    projects:
+      - name: web
+        description: Web tier
+        vpcs:
+          - name: default
+            dns_name: default
+            subnets:
+              - name: frontend
+                ipv4_block: 172.30.0.0/24
+              - name: backend
+                ipv4_block: 172.30.2.0/24
+            firewall_rules: []
+        disks:
+          - name: data
+            size: 1073741824
+            disk_source:
+              type: blank
+              block_size: 4096
+        instances:
+          - name: web-1
+            hostname: web-1
+            memory: 1073741824
+            ncpus: 2
+            disks: [data]
+"#,
+    )
+    .unwrap();
+    let live = crate::declarative::Manifest::from_yaml(
+        r#"
+organizations:
+  - name: acme
+    projects:
+      - name: web
+        description: Web
+        vpcs:
+          - name: default
+            dns_name: default
+            ipv6_prefix: fd00::/48
+            subnets:
+              - name: frontend
+                ipv4_block: 172.30.1.0/24
+                ipv6_block: fd00::/64
+              - name: old
+                ipv4_block: 172.30.9.0/24
+                ipv6_block: fd00:0:0:9::/64
+            routers:
+              - name: edge
+                routes:
+                  - name: outbound
+                    destination:
+                      type: vpc
+                      value: default
+                    target:
+                      type: internet_gateway
+                      value: outbound
+            firewall_rules: []
+      - name: legacy
+"#,
+    )
+    .unwrap();
+
+    let plan = crate::declarative::Plan::new(&desired, &live);
+    let changes: Vec<String> = plan
+        .changes
+        .iter()
+        .map(|c| format!("{} {} {}", c.action, c.kind, c.path))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            "delete route acme/web/default/edge/outbound",
+            "delete router acme/web/default/edge",
+            "delete subnet acme/web/default/old",
+            "delete project acme/legacy",
+            "update project acme/web",
+            "update subnet acme/web/default/frontend",
+            "create subnet acme/web/default/backend",
+            "create disk acme/web/data",
+            "create instance acme/web/web-1",
+        ]
+    );
+
+    let frontend = &plan.changes[5];
+    assert_eq!(frontend.diff.len(), 1);
+    assert_eq!(frontend.diff[0].field, "ipv4_block");
+    assert_eq!(
+        frontend.unsupported.as_deref(),
+        Some("subnet cannot be changed in place: ipv4_block")
+    );
+
+    let rendered = plan.to_string();
+    assert!(rendered.contains("~ project acme/web\n    description: \"Web\" -> \"Web tier\"\n"));
+    assert!(
+        rendered.contains("+ subnet acme/web/default/backend\n    ipv4_block: \"172.30.2.0/24\"\n")
+    );
+
+    assert!(crate::declarative::Plan::new(&desired, &desired).is_empty());
+}
+
+#[test]
+fn test_manifest_export() {
+    let disk: crate::types::Disk = serde_json::from_value(serde_json::json!({
+        "id": "disk-1",
+        "name": "data",
+        "block_size": 4096,
+        "project_id": "project-1",
+        "size": 1073741824,
+        "snapshot_id": "snapshot-1",
+        "state": {"state": "detached"},
+        "time_created": "2022-05-01T00:00:00Z",
+        "time_modified": "2022-05-01T00:00:00Z",
+    }))
+    .unwrap();
+    let spec = crate::declarative::DiskSpec::from(disk);
+    assert_eq!(
+        spec.disk_source,
+        crate::types::DiskSource::Snapshot {
+            snapshot_id: "snapshot-1".to_string()
+        }
+    );
+
+    let manifest = crate::declarative::Manifest {
+        organizations: vec![crate::declarative::OrganizationSpec {
+            name: "acme".to_string(),
+            projects: vec![crate::declarative::ProjectSpec {
+                name: "web".to_string(),
+                disks: vec![spec],
+                snapshots: vec![crate::declarative::SnapshotSpec {
+                    name: "nightly".to_string(),
+                    description: String::new(),
+                    disk: "data".to_string(),
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+
+    let yaml = manifest.to_yaml().unwrap();
+    assert!(!yaml.lines().any(|l| l.trim_start().starts_with("id:")));
+    assert!(!yaml.contains("project_id") && !yaml.contains("time_created"));
+    assert_eq!(
+        crate::declarative::Manifest::from_yaml(&yaml).unwrap(),
+        manifest
+    );
+    assert_eq!(
+        crate::declarative::Manifest::from_json(&manifest.to_json().unwrap()).unwrap(),
+        manifest
+    );
+}
+
+#[test]
+fn test_teardown_report() {
+    use crate::{declarative::Outcome, teardown::Step};
+
+    let plan = crate::teardown::TeardownPlan {
+        organization_name: "acme".to_string(),
+        project_name: "web".to_string(),
+        stages: vec![
+            vec![Step::StopInstance {
+                instance: "web-1".to_string(),
+            }],
+            vec![Step::DeleteInstance {
+                instance: "web-1".to_string(),
+            }],
+            vec![Step::DeleteProject],
+        ],
+    };
+    assert_eq!(
+        plan.to_string(),
+        "teardown of project acme/web:\n  stage 1:\n    stop instance web-1\n  stage 2:\n    \
+         delete instance web-1\n  stage 3:\n    delete project\n"
+    );
+
+    let report = crate::teardown::TeardownReport {
+        plan: plan.clone(),
+        dry_run: false,
+        results: vec![
+            crate::teardown::StepResult {
+                step: plan.stages[0][0].clone(),
+                outcome: Outcome::Applied,
+            },
+            crate::teardown::StepResult {
+                step: plan.stages[1][0].clone(),
+                outcome: Outcome::Failed {
+                    error: "Invalid Request: instance is running".to_string(),
+                },
+            },
+            crate::teardown::StepResult {
+                step: Step::DeleteProject,
+                outcome: Outcome::Skipped {
+                    reason: "an earlier step failed".to_string(),
+                },
+            },
+        ],
+    };
+    assert!(!report.is_complete());
+    let blocked: Vec<String> = report
+        .blocked()
+        .iter()
+        .map(|r| r.step.to_string())
+        .collect();
+    assert_eq!(blocked, vec!["delete instance web-1"]);
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["results"][1]["step"]["step"], "delete_instance");
+    assert_eq!(json["results"][1]["outcome"]["status"], "failed");
+}
+
+#[test]
+fn test_clone_spec() {
+    use crate::declarative::ResourceKind;
+
+    let source = crate::declarative::Manifest::from_yaml(
+        r#"
+organizations:
+  - name: acme
+    projects:
+      - name: prod
+        vpcs:
+          - name: default
+            dns_name: default
+            ipv6_prefix: fd00::/48
+            subnets:
+              - name: frontend
+                ipv4_block: 172.30.0.0/24
+                ipv6_block: fd00::/64
+            firewall_rules:
+              - name: allow-web
+                action: allow
+                direction: inbound
+                priority: 100
+                status: enabled
+                filters:
+                  ports: ["443"]
+                targets:
+                  - type: instance
+                    value: web-1
+        disks:
+          - name: data
+            size: 1073741824
+            disk_source:
+              type: blank
+              block_size: 4096
+        snapshots:
+          - name: nightly
+            disk: data
+        instances:
+          - name: web-1
+            hostname: web-1
+            memory: 1073741824
+            ncpus: 2
+            disks: [data]
+"#,
+    )
+    .unwrap();
+    let source = &source.organizations[0].projects[0];
+
+    let rename = |kind: ResourceKind, name: &str| match kind {
+        ResourceKind::Instance | ResourceKind::Disk => format!("staging-{}", name),
+        _ => name.to_string(),
+    };
+    let spec = crate::clone::clone_spec(source, "staging", &rename);
+
+    assert_eq!(spec.name, "staging");
+    assert!(spec.snapshots.is_empty());
+    assert_eq!(spec.vpcs[0].ipv6_prefix, None);
+    assert_eq!(spec.vpcs[0].subnets[0].ipv6_block, None);
+    assert_eq!(
+        spec.vpcs[0].firewall_rules.as_ref().unwrap()[0].targets,
+        vec![crate::types::VpcFirewallRuleTarget::Instance(
+            "staging-web-1".to_string()
+        )]
+    );
+    assert_eq!(spec.disks[0].name, "staging-data");
+    assert_eq!(spec.instances[0].name, "staging-web-1");
+    assert_eq!(spec.instances[0].disks, vec!["staging-data".to_string()]);
+
+    let plan = crate::declarative::Plan::for_project("acme", &spec, None);
+    let changes: Vec<String> = plan
+        .changes
+        .iter()
+        .map(|c| format!("{} {} {}", c.action, c.kind, c.path))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            "create project acme/staging",
+            "create vpc acme/staging/default",
+            "create subnet acme/staging/default/frontend",
+            "update firewall rules acme/staging/default",
+            "create disk acme/staging/staging-data",
+            "create instance acme/staging/staging-web-1",
+        ]
+    );
+}
+
+#[test]
+fn test_retention_plan() {
+    use crate::retention::{Frequency, RetentionAction};
+
+    let disk: crate::types::Disk = serde_json::from_value(serde_json::json!({
+        "id": "disk-1",
+        "name": "data",
+        "size": 1073741824,
+        "state": {"state": "detached"},
+        "time_created": "2022-05-01T00:00:00Z",
+        "time_modified": "2022-05-01T00:00:00Z",
+    }))
+    .unwrap();
+    let snapshot = |name: &str, created: &str| crate::types::Snapshot {
+        name: name.to_string(),
+        disk_id: "disk-1".to_string(),
+        time_created: crate::utils::DisplayOptionDateTime(Some(created.parse().unwrap())),
+        ..Default::default()
+    };
+    let snapshots = vec![
+        snapshot("data-hourly-202210181300", "2022-10-18T13:00:05Z"),
+        snapshot("data-hourly-202210171400", "2022-10-17T14:00:05Z"),
+        snapshot("data-manual", "2022-01-01T00:00:00Z"),
+    ];
+
+    let policy: crate::retention::RetentionPolicy = serde_json::from_value(serde_json::json!({
+        "organization_name": "acme",
+        "project_name": "web",
+        "disks": [{
+            "disk": "data",
+            "rules": [
+                {"frequency": "hourly", "keep": 24},
+                {"frequency": "weekly", "keep": 4},
+            ],
+        }],
+    }))
+    .unwrap();
+
+    let now = "2022-10-18T14:30:00Z".parse().unwrap();
+    let plan =
+        crate::retention::RetentionPlan::new(&policy, std::slice::from_ref(&disk), &snapshots, now)
+            .unwrap();
+    assert_eq!(
+        plan.actions,
+        vec![
+            RetentionAction::Create {
+                disk: "data".to_string(),
+                snapshot: "data-hourly-202210181400".to_string(),
+                frequency: Frequency::Hourly,
+            },
+            RetentionAction::Delete {
+                disk: "data".to_string(),
+                snapshot: "data-hourly-202210171400".to_string(),
+                frequency: Frequency::Hourly,
+                expired: "2022-10-18T14:00:05Z".parse().unwrap(),
+            },
+            RetentionAction::Create {
+                disk: "data".to_string(),
+                snapshot: "data-weekly-202210170000".to_string(),
+                frequency: Frequency::Weekly,
+            },
+        ]
+    );
+    assert_eq!(
+        plan.to_string().lines().next().unwrap(),
+        "+ snapshot data-hourly-202210181400 (hourly snapshot of disk data)"
+    );
+
+    // Keeping snapshots for longer than can be represented is an error, not
+    // an overflow.
+    let mut policy = policy;
+    policy.disks[0].rules[1].keep = u32::MAX;
+    let e = crate::retention::RetentionPlan::new(&policy, &[disk], &snapshots, now).unwrap_err();
+    assert!(e.to_string().contains("4294967295"), "{}", e);
+}
+
+#[test]
+fn test_ensure_drift() {
+    let request: crate::types::VpcCreate = serde_json::from_value(serde_json::json!({
+        "name": "web",
+        "description": "Web tier",
+        "dns_name": "web",
+    }))
+    .unwrap();
+    let vpc: crate::types::Vpc = serde_json::from_value(serde_json::json!({
+        "id": "vpc-1",
+        "name": "web",
+        "description": "Old web tier",
+        "dns_name": "web",
+        "ipv6_prefix": "fd12:3456:789a::/48",
+        "project_id": "project-1",
+        "system_router_id": "router-1",
+        "time_created": "2022-05-01T00:00:00Z",
+        "time_modified": "2022-05-01T00:00:00Z",
+    }))
+    .unwrap();
+
+    let created = crate::utils::Ensured::created(vpc.clone());
+    assert!(created.created);
+    assert_eq!(created.without_drift().unwrap(), vpc);
+
+    let existing = crate::utils::Ensured::existing(&request, vpc).unwrap();
+    assert!(!existing.created);
+    assert_eq!(
+        existing.drift,
+        vec![crate::utils::FieldDrift {
+            field: "description".to_string(),
+            requested: serde_json::json!("Web tier"),
+            actual: serde_json::json!("Old web tier"),
+        }]
+    );
+    assert_eq!(
+        existing.without_drift().unwrap_err().to_string(),
+        "existing resource does not match the request: description: requested \"Web tier\", found \
+         \"Old web tier\""
+    );
+}
+
+#[test]
+fn test_resource_index() {
+    use crate::resolver::{ResourceIndex, ResourcePath};
+
+    let mut index = ResourceIndex::default();
+    index.insert(
+        "instance-1",
+        ResourcePath::Instance {
+            organization: "acme".to_string(),
+            project: "web".to_string(),
+            instance: "db-1".to_string(),
+        },
+    );
+    index.insert(
+        "route-1",
+        ResourcePath::Route {
+            organization: "acme".to_string(),
+            project: "web".to_string(),
+            vpc: "main".to_string(),
+            router: "edge".to_string(),
+            route: "default".to_string(),
+        },
+    );
+    index.insert(
+        "key-1",
+        ResourcePath::SshKey {
+            ssh_key: "laptop".to_string(),
+        },
+    );
+
+    assert_eq!(index.len(), 3);
+    assert_eq!(index.display("instance-1"), "acme/web/instances/db-1");
+    assert_eq!(
+        index.display("route-1"),
+        "acme/web/vpcs/main/routers/edge/routes/default"
+    );
+    assert_eq!(index.display("key-1"), "sshkeys/laptop");
+    assert_eq!(index.display("unknown"), "unknown");
+
+    let route = index.resolve("route-1").unwrap();
+    assert_eq!(route.organization_name(), Some("acme"));
+    assert_eq!(route.project_name(), Some("web"));
+    assert_eq!(route.name(), "default");
+    assert_eq!(index.resolve("key-1").unwrap().project_name(), None);
+    assert_eq!(
+        serde_json::to_value(route).unwrap(),
+        serde_json::json!({
+            "kind": "route",
+            "organization": "acme",
+            "project": "web",
+            "vpc": "main",
+            "router": "edge",
+            "route": "default",
+        })
+    );
+}
+
+#[test]
+fn test_search_query() {
+    use crate::search::Query;
+
+    assert!(Query::Exact("db-7".to_string()).matches("id", "db-7"));
+    assert!(!Query::Exact("db".to_string()).matches("id", "db-7"));
+    assert!(Query::Prefix("db".to_string()).matches("id", "db-7"));
+    assert!(!Query::Prefix("7".to_string()).matches("id", "db-7"));
+    assert!(Query::Fuzzy("DB".to_string()).matches("id", "db-7"));
+    assert!(Query::Fuzzy("db7".to_string()).matches("id", "db-7"));
+    assert!(Query::Fuzzy("web-db-7".to_string()).matches("id", "web-db-9"));
+    assert!(!Query::Fuzzy("web".to_string()).matches("id", "db-7"));
+    assert!(Query::Id("id".to_string()).matches("id", "db-7"));
+    assert!(!Query::Id("db-7".to_string()).matches("id", "db-7"));
+}
+
+#[test]
+fn test_watcher_events() {
+    use crate::watch::{Event, Watcher};
+
+    let instance = |id: &str, name: &str, modified: &str| crate::types::Instance {
+        id: id.to_string(),
+        name: name.to_string(),
+        time_modified: crate::utils::DisplayOptionDateTime(Some(modified.parse().unwrap())),
+        ..Default::default()
+    };
+    let a = instance("a", "db-1", "2022-05-01T00:00:00Z");
+    let b = instance("b", "db-2", "2022-05-01T00:00:00Z");
+    let c = instance("c", "db-3", "2022-05-01T00:00:00Z");
+
+    let mut watcher = Watcher::default();
+    assert_eq!(
+        watcher.observe(vec![b.clone(), a.clone()], false),
+        vec![Event::Created(a.clone()), Event::Created(b.clone())]
+    );
+    assert_eq!(watcher.observe(vec![a.clone(), b.clone()], false), vec![]);
+
+    // Only the modification time is compared when there is one.
+    let renamed = crate::types::Instance {
+        hostname: "db-1.local".to_string(),
+        ..a.clone()
+    };
+    assert_eq!(
+        watcher.observe(vec![renamed.clone(), b.clone()], false),
+        vec![]
+    );
+
+    let updated = instance("a", "db-1", "2022-05-02T00:00:00Z");
+    assert_eq!(
+        watcher.observe(vec![updated.clone(), c.clone()], false),
+        vec![
+            Event::Updated {
+                old: renamed,
+                new: updated.clone(),
+            },
+            Event::Created(c.clone()),
+            Event::Deleted(b),
+        ]
+    );
+    assert_eq!(
+        watcher.observe(vec![updated.clone(), c.clone()], true),
+        vec![
+            Event::Updated {
+                old: updated.clone(),
+                new: updated,
+            },
+            Event::Updated {
+                old: c.clone(),
+                new: c,
+            },
+        ]
+    );
+
+    // Sagas have no modification time, so they are compared in full.
+    let saga = |state| crate::types::Saga {
+        id: "saga-1".to_string(),
+        state,
+    };
+    let mut watcher = Watcher::default();
+    watcher.observe(vec![saga(crate::types::SagaState::Running)], false);
+    assert_eq!(
+        watcher.observe(vec![saga(crate::types::SagaState::Succeeded)], false),
+        vec![Event::Updated {
+            old: saga(crate::types::SagaState::Running),
+            new: saga(crate::types::SagaState::Succeeded),
+        }]
+    );
+}
+
+#[test]
+fn test_usage_report() {
+    use crate::usage::{ProjectResources, UsageReport};
+
+    let instance = |ncpus: u16, memory: u64, run_state| crate::types::Instance {
+        ncpus,
+        memory,
+        run_state,
+        ..Default::default()
+    };
+    let disk: crate::types::Disk = serde_json::from_value(serde_json::json!({
+        "id": "disk-1",
+        "name": "data",
+        "size": 1024,
+        "state": {"state": "detached"},
+        "time_created": "2022-05-01T00:00:00Z",
+        "time_modified": "2022-05-01T00:00:00Z",
+    }))
+    .unwrap();
+    let project = |organization: &str, project: &str| ProjectResources {
+        organization_name: organization.to_string(),
+        project_name: project.to_string(),
+        instances: Vec::new(),
+        disks: Vec::new(),
+        snapshots: Vec::new(),
+        images: Vec::new(),
+    };
+
+    let mut web = project("acme", "web");
+    web.instances = vec![
+        instance(2, 4096, crate::types::InstanceState::Running),
+        instance(4, 8192, crate::types::InstanceState::Stopped),
+    ];
+    web.disks = vec![disk];
+    let mut db = project("acme", "db");
+    db.instances = vec![instance(8, 16384, crate::types::InstanceState::Running)];
+    db.snapshots = vec![crate::types::Snapshot {
+        size: 512,
+        ..Default::default()
+    }];
+    let mut ci = project("build", "ci");
+    ci.images = vec![crate::types::Image {
+        size: 256,
+        ..Default::default()
+    }];
+
+    let report = UsageReport::new(&[web.clone(), db, ci], false);
+    let scopes: Vec<(&str, &str)> = report
+        .rows
+        .iter()
+        .map(|r| (r.organization.as_str(), r.project.as_str()))
+        .collect();
+    assert_eq!(
+        scopes,
+        vec![
+            ("acme", "db"),
+            ("acme", "web"),
+            ("acme", ""),
+            ("build", "ci"),
+            ("build", ""),
+            ("", ""),
+        ]
+    );
+    assert_eq!(
+        report.fleet().unwrap(),
+        &crate::usage::Usage {
+            instances: 3,
+            ncpus: 14,
+            memory: 28672,
+            disks: 1,
+            disk_bytes: 1024,
+            snapshots: 1,
+            snapshot_bytes: 512,
+            images: 1,
+            image_bytes: 256,
+        }
+    );
+    assert_eq!(
+        report.to_csv().lines().nth(3).unwrap(),
+        "acme,,,3,14,28672,1,1024,1,512,0,0"
+    );
+
+    let report = UsageReport::new(&[web], true);
+    let states: Vec<(&str, u64)> = report
+        .rows
+        .iter()
+        .filter(|r| r.organization.is_empty())
+        .map(|r| (r.instance_state.as_str(), r.usage.ncpus))
+        .collect();
+    assert_eq!(states, vec![("", 6), ("running", 2), ("stopped", 4)]);
+}
+
+#[test]
+fn test_find_orphans() {
+    use crate::orphans::{FindingKind, OrphanOptions, ProjectContents};
+
+    let time = |s: &str| crate::utils::DisplayOptionDateTime(Some(s.parse().unwrap()));
+    let disk = |id: &str, name: &str, state, image_id: &str| -> crate::types::Disk {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "image_id": image_id,
+            "size": 1024,
+            "state": state,
+            "time_created": "2022-01-01T00:00:00Z",
+            "time_modified": "2022-01-01T00:00:00Z",
+        }))
+        .unwrap()
+    };
+    let vpc = |id: &str, name: &str| -> crate::types::Vpc {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "dns_name": name,
+            "ipv6_prefix": "fd12:3456:789a::/48",
+            "time_created": "2022-01-01T00:00:00Z",
+            "time_modified": "2022-01-01T00:00:00Z",
+        }))
+        .unwrap()
+    };
+    let interface: crate::types::NetworkInterface = serde_json::from_value(serde_json::json!({
+        "id": "nic-1",
+        "name": "net0",
+        "instance_id": "instance-1",
+        "ip": "172.30.0.5",
+        "mac": "A8:40:25:F0:00:01",
+        "subnet_id": "subnet-1",
+        "vpc_id": "vpc-1",
+        "time_created": "2022-01-01T00:00:00Z",
+        "time_modified": "2022-01-01T00:00:00Z",
+    }))
+    .unwrap();
+
+    let mut contents = ProjectContents {
+        organization_name: "acme".to_string(),
+        project: crate::types::Project {
+            id: "project-1".to_string(),
+            name: "web".to_string(),
+            time_created: time("2022-01-01T00:00:00Z"),
+            ..Default::default()
+        },
+        vpcs: vec![vpc("vpc-1", "default"), vpc("vpc-2", "unused")],
+        instances: vec![
+            crate::types::Instance {
+                id: "instance-1".to_string(),
+                name: "db-1".to_string(),
+                run_state: crate::types::InstanceState::Stopped,
+                time_run_state_updated: time("2022-05-01T00:00:00Z"),
+                ..Default::default()
+            },
+            crate::types::Instance {
+                id: "instance-2".to_string(),
+                name: "db-2".to_string(),
+                run_state: crate::types::InstanceState::Stopped,
+                time_run_state_updated: time("2022-05-30T00:00:00Z"),
+                ..Default::default()
+            },
+        ],
+        network_interfaces: vec![interface],
+        disks: vec![
+            disk(
+                "disk-1",
+                "boot",
+                serde_json::json!({"state": "attached", "instance": "instance-1"}),
+                "image-1",
+            ),
+            disk(
+                "disk-2",
+                "scratch",
+                serde_json::json!({"state": "detached"}),
+                "",
+            ),
+        ],
+        snapshots: vec![
+            crate::types::Snapshot {
+                id: "snapshot-1".to_string(),
+                name: "boot-backup".to_string(),
+                disk_id: "disk-1".to_string(),
+                time_created: time("2022-01-01T00:00:00Z"),
+                ..Default::default()
+            },
+            crate::types::Snapshot {
+                id: "snapshot-2".to_string(),
+                name: "old-backup".to_string(),
+                disk_id: "disk-0".to_string(),
+                time_created: time("2022-01-01T00:00:00Z"),
+                ..Default::default()
+            },
+        ],
+        images: vec![
+            crate::types::Image {
+                id: "image-1".to_string(),
+                name: "ubuntu".to_string(),
+                time_created: time("2022-01-01T00:00:00Z"),
+                ..Default::default()
+            },
+            crate::types::Image {
+                id: "image-2".to_string(),
+                name: "debian".to_string(),
+                time_created: time("2022-01-01T00:00:00Z"),
+                ..Default::default()
+            },
+        ],
+    };
+
+    let now = "2022-06-01T00:00:00Z".parse().unwrap();
+    let findings = contents.find_orphans(now, &OrphanOptions::default());
+    let found: Vec<(FindingKind, String, i64)> = findings
+        .iter()
+        .map(|f| (f.kind, f.path.to_string(), f.age_days))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (
+                FindingKind::StoppedInstance,
+                "acme/web/instances/db-1".to_string(),
+                31
+            ),
+            (
+                FindingKind::DetachedDisk,
+                "acme/web/disks/scratch".to_string(),
+                151
+            ),
+            (
+                FindingKind::OrphanedSnapshot,
+                "acme/web/snapshots/old-backup".to_string(),
+                151
+            ),
+            (
+                FindingKind::UnusedImage,
+                "acme/web/images/debian".to_string(),
+                151
+            ),
+            (
+                FindingKind::EmptyVpc,
+                "acme/web/vpcs/unused".to_string(),
+                151
+            ),
+        ]
+    );
+
+    contents.instances.clear();
+    contents.disks.clear();
+    contents.snapshots.clear();
+    contents.images.clear();
+    let findings = contents.find_orphans(now, &OrphanOptions::default());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].kind, FindingKind::EmptyProject);
+    assert_eq!(findings[0].path.to_string(), "acme/web");
+}
+
+#[tokio::test]
+async fn test_bulk_checkpoint() {
+    use crate::{
+        bulk::{BulkAction, BulkOptions, BulkReport, BulkResult, InstanceSelector},
+        declarative::Outcome,
+    };
+
+    let selector = InstanceSelector::from_str("acme/web/db-1").unwrap();
+    assert_eq!(selector, InstanceSelector::new("acme", "web", "db-1"));
+    assert_eq!(selector.to_string(), "acme/web/instances/db-1");
+    assert_eq!(
+        InstanceSelector::from_str(&selector.to_string()).unwrap(),
+        selector
+    );
+    assert!(InstanceSelector::from_str("acme/db-1").is_err());
+    assert!(InstanceSelector::from_str("acme//db-1").is_err());
+
+    // Everything recorded as done is skipped, so no requests are made.
+    let path = std::env::temp_dir().join(format!("bulk-{}.json", uuid::Uuid::new_v4()));
+    let checkpoint = BulkReport {
+        action: BulkAction::Reboot,
+        results: vec![BulkResult {
+            instance: selector.clone(),
+            outcome: Outcome::Applied,
+        }],
+    };
+    std::fs::write(&path, checkpoint.to_json().unwrap()).unwrap();
+
+    let client = crate::Client::new("token", "http://127.0.0.1:1");
+    let options = BulkOptions {
+        checkpoint: Some(path.clone()),
+        ..Default::default()
+    };
+    let report = client
+        .instances()
+        .bulk(
+            BulkAction::Reboot,
+            std::slice::from_ref(&selector),
+            &options,
+        )
+        .await
+        .unwrap();
+    assert_eq!(report, checkpoint);
+    assert!(report.is_success());
+
+    // A checkpoint for a different action is refused.
+    assert!(client
+        .instances()
+        .bulk(BulkAction::Delete, &[selector], &options)
+        .await
+        .is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_cassette_replay() {
+    use crate::{
+        cassette::{Cassette, Interaction, NoMatch, RecordedRequest, RecordedResponse, Tape},
+        types::{NameOrIdSortMode, OrganizationCreate},
+    };
+
+    let organization = r#"{
+        "id": "a6f3f0fc-0d5b-4a0c-8a6e-3b0c8d1c2f11",
+        "name": "acme",
+        "description": "",
+        "time_created": "2022-05-01T00:00:00Z",
+        "time_modified": "2022-05-01T00:00:00Z"
+    }"#;
+    let interaction =
+        |method: &str, path: &str, query: Option<&str>, body: Option<&str>| Interaction {
+            request: RecordedRequest {
+                method: method.to_string(),
+                path: path.to_string(),
+                query: query.map(str::to_string),
+                headers: Default::default(),
+                body: body.map(str::to_string),
+            },
+            response: RecordedResponse {
+                status: 200,
+                headers: Default::default(),
+                body: if path == "/organizations" && method == "GET" {
+                    format!(r#"{{"items": [{}]}}"#, organization)
+                } else {
+                    organization.to_string()
+                },
+            },
+        };
+    let tape = Tape {
+        interactions: vec![
+            interaction(
+                "GET",
+                "/organizations",
+                Some("sort_by=name_ascending&limit=10"),
+                None,
+            ),
+            // Bodies are compared as JSON, not byte for byte.
+            interaction(
+                "POST",
+                "/organizations",
+                None,
+                Some(r#"{ "name": "acme", "description": "a" }"#),
+            ),
+        ],
+    };
+
+    // Nothing listens on this port, so every response must come from the
+    // cassette.
+    let client = crate::Client::new("token", "http://127.0.0.1:1")
+        .with_cassette(Cassette::from_tape("organizations.json", tape));
+
+    let organizations = client
+        .organizations()
+        .get_page(10, "", NameOrIdSortMode::NameAscending)
+        .await
+        .unwrap();
+    assert_eq!(organizations.len(), 1);
+    assert_eq!(organizations[0].name, "acme");
+
+    let e = client
+        .organizations()
+        .post(&OrganizationCreate {
+            name: "acme".to_string(),
+            description: "b".to_string(),
+        })
+        .await
+        .unwrap_err();
+    let no_match = e.downcast_ref::<NoMatch>().unwrap();
+    assert_eq!(no_match.request.to_string(), "POST /organizations");
+    assert_eq!(
+        no_match
+            .candidates
+            .iter()
+            .map(|(index, _, reason)| (*index, reason.as_str()))
+            .collect::<Vec<_>>(),
+        vec![(0, "method differs"), (1, "body differs")]
+    );
+
+    let organization = client
+        .organizations()
+        .post(&OrganizationCreate {
+            name: "acme".to_string(),
+            description: "a".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(organization.name, "acme");
+
+    // Each interaction is replayed once.
+    let e = client
+        .organizations()
+        .get_page(10, "", NameOrIdSortMode::NameAscending)
+        .await
+        .unwrap_err();
+    assert!(e
+        .to_string()
+        .starts_with("no interaction in cassette organizations.json matches GET /organizations?"));
+    assert!(e
+        .to_string()
+        .contains("\n  #0 GET /organizations?sort_by=name_ascending&limit=10: already replayed\n"));
+}
+
+#[tokio::test]
+async fn test_cassette_repeated_headers() {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+
+    use crate::{
+        cassette::{Cassette, CassetteTransport},
+        transport::{BoxFuture, Transport},
+    };
+
+    struct Links;
+
+    impl Transport for Links {
+        fn send(
+            &self,
+            _request: http::Request<Bytes>,
+        ) -> BoxFuture<'_, anyhow::Result<http::Response<Bytes>>> {
+            Box::pin(async move {
+                Ok(http::Response::builder()
+                    .status(200)
+                    .header("link", "</a>; rel=\"next\"")
+                    .header("link", "</b>; rel=\"last\"")
+                    .header("set-cookie", "a=1")
+                    .header("set-cookie", "b=2")
+                    .body(Bytes::new())?)
+            })
+        }
+    }
+
+    let request = || {
+        http::Request::builder()
+            .uri("http://oxide.test/organizations")
+            .header("proxy-authorization", "Basic dXNlcjpwYXNz")
+            .body(Bytes::new())
+            .unwrap()
+    };
+    let path = std::env::temp_dir().join(format!("cassette-{}.json", uuid::Uuid::new_v4()));
+    let recorder = CassetteTransport {
+        cassette: Cassette::record(&path),
+        inner: Arc::new(Links),
+    };
+    recorder.send(request()).await.unwrap();
+
+    // Every value is kept, redacted or not.
+    let tape = recorder.cassette.tape();
+    assert_eq!(
+        tape.interactions[0].request.headers["proxy-authorization"],
+        vec![crate::cassette::REDACTED]
+    );
+    let headers = &tape.interactions[0].response.headers;
+    assert_eq!(
+        headers["link"],
+        vec!["</a>; rel=\"next\"", "</b>; rel=\"last\""]
+    );
+    assert_eq!(headers["set-cookie"].len(), 2);
+
+    let player = CassetteTransport {
+        cassette: Cassette::replay(&path).unwrap(),
+        inner: Arc::new(Links),
+    };
+    let response = player.send(request()).await.unwrap();
+    assert_eq!(response.headers().get_all("link").iter().count(), 2);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_custom_transport() {
+    use std::sync::{Arc, Mutex};
+
+    use bytes::Bytes;
+
+    use crate::{
+        transport::{BoxFuture, Transport},
+        types::ProjectCreate,
+    };
+
+    #[derive(Default)]
+    struct Fake {
+        requests: Mutex<Vec<http::Request<Bytes>>>,
+    }
+
+    impl Transport for Fake {
+        fn send(
+            &self,
+            request: http::Request<Bytes>,
+        ) -> BoxFuture<'_, anyhow::Result<http::Response<Bytes>>> {
+            let body = request.body().clone();
+            self.requests.lock().unwrap().push(request);
+            Box::pin(async move {
+                let mut project: serde_json::Value = serde_json::from_slice(&body)?;
+                project["id"] = "e9b0c5a4-4d27-4fb3-9b3e-6a1e6b9d2a10".into();
+                project["organization_id"] = "a6f3f0fc-0d5b-4a0c-8a6e-3b0c8d1c2f11".into();
+                project["time_created"] = "2022-05-01T00:00:00Z".into();
+                project["time_modified"] = "2022-05-01T00:00:00Z".into();
+                Ok(http::Response::builder()
+                    .status(http::StatusCode::CREATED)
+                    .body(Bytes::from(serde_json::to_vec(&project)?))?)
+            })
+        }
+    }
+
+    let fake = Arc::new(Fake::default());
+    let client = crate::Client::new("token", "http://oxide.test").with_transport(fake.clone());
+    let project = client
+        .projects()
+        .post(
+            "acme",
+            &ProjectCreate {
+                name: "web".to_string(),
+                description: "".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(project.name, "web");
+
+    let requests = fake.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method(), http::Method::POST);
+    assert_eq!(
+        requests[0].uri().to_string(),
+        "http://oxide.test/organizations/acme/projects"
+    );
+    assert_eq!(requests[0].headers()["authorization"], "Bearer token");
+}
+
+#[tokio::test]
+async fn test_middleware_chain() {
+    use std::sync::{Arc, Mutex};
+
+    use bytes::Bytes;
+
+    use crate::{
+        middleware::{operation_id, Middleware, Next, ReadOnly},
+        transport::BoxFuture,
+        types::OrganizationCreate,
+    };
+
+    /// Records what passes through, and answers every request itself.
+    #[derive(Default)]
+    struct Stub {
+        seen: Mutex<Vec<(String, String, String)>>,
+    }
+
+    impl Middleware for Stub {
+        fn handle<'a>(
+            &'a self,
+            request: http::Request<Bytes>,
+            _next: Next<'a>,
+        ) -> BoxFuture<'a, anyhow::Result<http::Response<Bytes>>> {
+            self.seen.lock().unwrap().push((
+                operation_id(&request).unwrap_or_default().to_string(),
+                request.uri().path().to_string(),
+                request.headers()["x-audit"].to_str().unwrap().to_string(),
+            ));
+            Box::pin(async {
+                Ok(http::Response::builder()
+                    .status(http::StatusCode::OK)
+                    .body(Bytes::from_static(
+                        br#"{"name": "acme", "time_created": null, "time_modified": null}"#,
+                    ))?)
+            })
+        }
+    }
+
+    struct Audit;
+
+    impl Middleware for Audit {
+        fn handle<'a>(
+            &'a self,
+            mut request: http::Request<Bytes>,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, anyhow::Result<http::Response<Bytes>>> {
+            request
+                .headers_mut()
+                .insert("x-audit", http::HeaderValue::from_static("yes"));
+            next.run(request)
+        }
+    }
+
+    let stub = Arc::new(Stub::default());
+    // Nothing listens on this port, so the stub must answer every request.
+    let client = crate::Client::new("token", "http://127.0.0.1:1")
+        .with_middleware(Audit)
+        .with_middleware(ReadOnly)
+        .with_middleware(stub.clone());
+
+    let organization = client.organizations().get("acme").await.unwrap();
+    assert_eq!(organization.name, "acme");
+    assert_eq!(
+        *stub.seen.lock().unwrap(),
+        vec![(
+            "organizations_get_organization".to_string(),
+            "/organizations/acme".to_string(),
+            "yes".to_string()
+        )]
+    );
+
+    let e = client
+        .organizations()
+        .post(&OrganizationCreate {
+            name: "acme".to_string(),
+            description: "".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "POST /organizations (organizations_post) is not allowed by a read-only client"
+    );
+    assert_eq!(stub.seen.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_log_redaction() {
+    use crate::redact::Redaction;
+
+    let client = crate::Client::new("secret-token", "http://oxide.test");
+    assert!(!format!("{:?}", client).contains("secret-token"));
+
+    let request = client
+        .request_raw(
+            http::Method::POST,
+            "/organizations/acme/projects/web/instances",
+            Some(bytes::Bytes::from_static(
+                br#"{"name": "db", "user_data": "c2VjcmV0"}"#,
+            )),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        Redaction::default().request(&request),
+        "POST http://oxide.test/organizations/acme/projects/web/instances headers: {\"accept\": \
+         \"application/json\", \"content-type\": \"application/json\", \"authorization\": \
+         \"Bearer <redacted>\"} body: {\"name\":\"db\",\"user_data\":\"<redacted>\"}"
+    );
+
+    let redaction = Redaction::none().path("$.items.*.password").path("token");
+    assert_eq!(
+        redaction.body(
+            br#"{"items": [{"a": {"password": "x"}, "b": 1}, {"c": {"password": "y"}}], "token": "t", "user_data": "u"}"#
+        ),
+        r#"{"items":[{"a":{"password":"<redacted>"},"b":1},{"c":{"password":"<redacted>"}}],"token":"<redacted>","user_data":"u"}"#
+    );
+    assert_eq!(redaction.body(b"secret"), "<6 bytes that are not JSON>");
+}
+
+#[test]
+fn test_config_profiles() {
+    use crate::config::Config;
+
+    let dir = std::env::temp_dir().join(format!("oxide-config-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("lab-token"), "lab-secret\n").unwrap();
+    std::fs::write(
+        dir.join("config.toml"),
+        r#"
+default_profile = "lab"
+
+[profiles.lab]
+host = "https://oxide.lab.example.com"
+token_file = "lab-token"
+timeout = 30
+organization = "acme"
+
+[profiles.prod]
+host = "https://oxide.example.com"
+token = "prod-secret"
+project = "web"
+"#,
+    )
+    .unwrap();
+
+    let config = Config::from_path(&dir.join("config.toml")).unwrap();
+    let no_env = |_: &str| None::<String>;
+
+    let lab = config.profile_with_env(None, &no_env).unwrap();
+    assert_eq!(lab.token_file, Some(dir.join("lab-token")));
+    assert_eq!(lab.token().unwrap(), "lab-secret");
+    assert_eq!(lab.timeout, Some(30));
+
+    let client = lab.client().unwrap();
+    assert_eq!(client.default_organization(), Some("acme"));
+    assert_eq!(client.default_project(), None);
+
+    let prod = config.profile_with_env(Some("prod"), &no_env).unwrap();
+    assert_eq!(prod.host.as_deref(), Some("https://oxide.example.com"));
+    assert_eq!(prod.token().unwrap(), "prod-secret");
+    assert!(!format!("{:?}", prod).contains("prod-secret"));
+
+    // The environment takes precedence over the profile, and an empty
+    // variable is the same as an unset one.
+    let env = |name: &str| match name {
+        "OXIDE_HOST" => Some("https://oxide.staging.example.com".to_string()),
+        "OXIDE_TOKEN" => Some("env-secret".to_string()),
+        "OXIDE_TIMEOUT" => Some("60".to_string()),
+        "OXIDE_PROJECT" => Some(String::new()),
+        _ => None,
+    };
+    let prod = config.profile_with_env(Some("prod"), &env).unwrap();
+    assert_eq!(
+        prod.host.as_deref(),
+        Some("https://oxide.staging.example.com")
+    );
+    assert_eq!(prod.token().unwrap(), "env-secret");
+    assert_eq!(prod.timeout, Some(60));
+    assert_eq!(prod.project.as_deref(), Some("web"));
+
+    let lab = config.profile_with_env(None, &env).unwrap();
+    assert_eq!(lab.token().unwrap(), "env-secret");
+    let e = config
+        .profile_with_env(None, &|name: &str| {
+            (name == "OXIDE_TIMEOUT").then(|| "soon".to_string())
+        })
+        .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "OXIDE_TIMEOUT must be a number of seconds, not \"soon\""
+    );
+
+    let e = config
+        .profile_with_env(Some("staging"), &no_env)
+        .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        format!(
+            "no profile \"staging\" in {}; the profiles are: lab, prod",
+            dir.join("config.toml").display()
+        )
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_token_refresh() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    };
+
+    use bytes::Bytes;
+
+    use crate::{
+        token::{FileToken, TokenProvider},
+        transport::{BoxFuture, Transport},
+    };
+
+    /// Hands out `old` until it is refreshed, then `new`.
+    #[derive(Default)]
+    struct Rotating {
+        refreshes: AtomicUsize,
+    }
+
+    impl TokenProvider for Rotating {
+        fn token(&self) -> BoxFuture<'_, anyhow::Result<String>> {
+            Box::pin(async move {
+                Ok(match self.refreshes.load(Ordering::SeqCst) {
+                    0 => "old".to_string(),
+                    _ => "new".to_string(),
+                })
+            })
+        }
+
+        fn refresh(&self) -> BoxFuture<'_, anyhow::Result<String>> {
+            self.refreshes.fetch_add(1, Ordering::SeqCst);
+            self.token()
+        }
+    }
+
+    /// Only accepts `new`.
+    #[derive(Default)]
+    struct Server {
+        seen: Mutex<Vec<String>>,
+    }
+
+    impl Transport for Server {
+        fn send(
+            &self,
+            request: http::Request<Bytes>,
+        ) -> BoxFuture<'_, anyhow::Result<http::Response<Bytes>>> {
+            let auth = request.headers()["authorization"]
+                .to_str()
+                .unwrap()
+                .to_string();
+            self.seen.lock().unwrap().push(auth.clone());
+            Box::pin(async move {
+                let response = if auth == "Bearer new" {
+                    http::Response::builder()
+                        .status(http::StatusCode::OK)
+                        .body(Bytes::from_static(br#"{"items": []}"#))?
+                } else {
+                    http::Response::builder()
+                        .status(http::StatusCode::UNAUTHORIZED)
+                        .body(Bytes::from_static(
+                            br#"{"error_code": "Unauthorized", "message": "", "request_id": "1"}"#,
+                        ))?
+                };
+                Ok(response)
+            })
+        }
+    }
+
+    let provider = Arc::new(Rotating::default());
+    let server = Arc::new(Server::default());
+    let client = crate::Client::new("", "http://oxide.test")
+        .with_token_provider(provider.clone())
+        .with_transport(server.clone());
+    let organizations = client
+        .organizations()
+        .get_page(0, "", crate::types::NameOrIdSortMode::NameAscending)
+        .await
+        .unwrap();
+    assert!(organizations.is_empty());
+    assert_eq!(provider.refreshes.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        *server.seen.lock().unwrap(),
+        vec!["Bearer old".to_string(), "Bearer new".to_string()]
+    );
+
+    // A token that refreshing does not change is not sent again.
+    let server = Arc::new(Server::default());
+    let rejected = crate::Client::new("stale", "http://oxide.test").with_transport(server.clone());
+    let e = rejected
+        .organizations()
+        .get_page(0, "", crate::types::NameOrIdSortMode::NameAscending)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        e.downcast_ref::<crate::types::Error>(),
+        Some(&crate::types::Error::Unauthenticated {
+            internal_message: "".to_string()
+        })
+    );
+    assert_eq!(
+        *server.seen.lock().unwrap(),
+        vec!["Bearer stale".to_string()]
+    );
+
+    let path = std::env::temp_dir().join(format!("token-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&path, "first\n").unwrap();
+    let file = FileToken::new(&path);
+    assert_eq!(file.token().await.unwrap(), "first");
+    std::fs::write(&path, "second\n").unwrap();
+    assert_eq!(file.refresh().await.unwrap(), "second");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_transport_no_proxy() {
+    use crate::transport::TransportOptions;
+
+    let e = TransportOptions::new()
+        .no_proxy(&["localhost"])
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "no_proxy only applies to a proxy set with proxy()"
+    );
+    TransportOptions::new()
+        .proxy("http://proxy.example.com:3128")
+        .no_proxy(&["localhost"])
+        .build()
+        .unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_unix_socket_transport() {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::UnixListener,
+    };
+
+    use crate::transport::UnixSocketTransport;
+
+    let path = std::env::temp_dir().join(format!("oxide-{}.sock", uuid::Uuid::new_v4()));
+    let listener = UnixListener::bind(&path).unwrap();
+    let server = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let n = stream.read(&mut buf).await.unwrap();
+            assert!(n > 0, "connection closed before the end of the headers");
+            request.extend_from_slice(&buf[..n]);
+        }
+        let body = br#"{"id": "a6f3f0fc-0d5b-4a0c-8a6e-3b0c8d1c2f11"}"#;
+        stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: \
+                     {}\r\n\r\n",
+                    body.len()
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+        stream.write_all(body).await.unwrap();
+        String::from_utf8(request).unwrap()
+    });
+
+    let client = crate::Client::new("token", "http://oxide.local")
+        .with_transport(UnixSocketTransport::new(&path));
+    let me = client.hidden().session_me().await.unwrap();
+    assert_eq!(me.id, "a6f3f0fc-0d5b-4a0c-8a6e-3b0c8d1c2f11");
+
+    let request = server.await.unwrap().to_lowercase();
+    assert!(
+        request.starts_with("get /session/me http/1.1\r\n"),
+        "{}",
+        request
+    );
+    assert!(request.contains("\r\nhost: oxide.local\r\n"), "{}", request);
+    assert!(
+        request.contains("\r\nauthorization: bearer token\r\n"),
+        "{}",
+        request
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn test_tracing_span() {
+    use std::{
+        collections::BTreeMap,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    use bytes::Bytes;
+    use tracing::{
+        field::{Field, Visit},
+        span,
+    };
+    use tracing_subscriber::{
+        layer::{Context, SubscriberExt},
+        registry::LookupSpan,
+        Layer,
+    };
+
+    use crate::{
+        token::TokenProvider,
+        transport::{BoxFuture, Transport},
+    };
+
+    /// The fields recorded on each span, by span name.
+    type Spans = Arc<Mutex<BTreeMap<&'static str, BTreeMap<String, String>>>>;
+
+    struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+    impl Visit for Fields<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    struct Capture(Spans);
+
+    impl<S> Layer<S> for Capture
+    where
+        S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &span::Attributes<'_>, _id: &span::Id, ctx: Context<'_, S>) {
+            let mut spans = self.0.lock().unwrap();
+            let fields = spans.entry(attrs.metadata().name()).or_default();
+            attrs.record(&mut Fields(fields));
+            if let Some(parent) = ctx.lookup_current() {
+                fields.insert("parent".to_string(), parent.name().to_string());
+            }
+        }
+
+        fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+            let name = ctx.span(id).unwrap().name();
+            let mut spans = self.0.lock().unwrap();
+            values.record(&mut Fields(spans.entry(name).or_default()));
+        }
+    }
+
+    /// Hands out a new token each time it is refreshed.
+    #[derive(Default)]
+    struct Rotating {
+        refreshes: AtomicUsize,
+    }
+
+    impl TokenProvider for Rotating {
+        fn token(&self) -> BoxFuture<'_, anyhow::Result<String>> {
+            let token = format!("token-{}", self.refreshes.load(Ordering::SeqCst));
+            Box::pin(async move { Ok(token) })
+        }
+
+        fn refresh(&self) -> BoxFuture<'_, anyhow::Result<String>> {
+            self.refreshes.fetch_add(1, Ordering::SeqCst);
+            self.token()
+        }
+    }
+
+    /// Rejects the first request, then answers with an organization, which
+    /// always exists already.
+    #[derive(Default)]
+    struct Server {
+        requests: AtomicUsize,
+    }
+
+    impl Transport for Server {
+        fn send(
+            &self,
+            request: http::Request<Bytes>,
+        ) -> BoxFuture<'_, anyhow::Result<http::Response<Bytes>>> {
+            let first = self.requests.fetch_add(1, Ordering::SeqCst) == 0;
+            Box::pin(async move {
+                let response = if first {
+                    http::Response::builder().status(401).body(Bytes::new())?
+                } else if request.method() == http::Method::POST {
+                    http::Response::builder()
+                        .status(400)
+                        .body(Bytes::from_static(
+                            br#"{
+                            "error_code": "ObjectAlreadyExists",
+                            "message": "already exists: organization \"acme\"",
+                            "request_id": "req-2"
+                        }"#,
+                        ))?
+                } else {
+                    http::Response::builder()
+                        .status(200)
+                        .header("x-request-id", "req-1")
+                        .body(Bytes::from_static(
+                            br#"{
+                                "id": "a6f3f0fc-0d5b-4a0c-8a6e-3b0c8d1c2f11",
+                                "name": "acme",
+                                "description": "",
+                                "time_created": "2022-05-01T00:00:00Z",
+                                "time_modified": "2022-05-01T00:00:00Z"
+                            }"#,
+                        ))?
+                };
+                Ok(response)
+            })
+        }
+    }
+
+    let spans = Spans::default();
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(Capture(spans.clone())),
+    );
+
+    let client = crate::Client::new("", "http://oxide.test")
+        .with_token_provider(Rotating::default())
+        .with_transport(Server::default());
+    let organization = client.organizations().get("acme").await.unwrap();
+    assert_eq!(organization.name, "acme");
+
+    {
+        let mut spans = spans.lock().unwrap();
+        let fields = &spans["organizations_get_organization"];
+        assert_eq!(fields["operation_id"], "organizations_get_organization");
+        assert_eq!(fields["status"], "200");
+        assert_eq!(fields["retry_count"], "1");
+        assert_eq!(fields["request_id"], "req-1");
+        spans.clear();
+    }
+
+    // `ensure` gets a span of its own, with the `POST` and the `GET` that
+    // follows it each in a child span.
+    let ensured = client
+        .organizations()
+        .ensure(&crate::types::OrganizationCreate {
+            name: "acme".to_string(),
+            description: "".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(!ensured.created);
+
+    let spans = spans.lock().unwrap();
+    assert!(!spans["organizations_ensure"].contains_key("method"));
+    let post = &spans["organizations_post"];
+    assert_eq!(post["parent"], "organizations_ensure");
+    assert_eq!(post["method"], "POST");
+    assert_eq!(post["status"], "400");
+    assert_eq!(post["request_id"], "req-2");
+    let get = &spans["organizations_get_organization"];
+    assert_eq!(get["parent"], "organizations_ensure");
+    assert_eq!(get["method"], "GET");
+    assert_eq!(get["status"], "200");
+    assert_eq!(get["request_id"], "req-1");
+}
+
+#[test]
+fn test_session_cookies() {
+    use crate::cookies::Session;
+
+    let set_cookies = |values: &[&str]| {
+        let mut headers = http::HeaderMap::new();
+        for value in values {
+            headers.append(http::header::SET_COOKIE, value.parse().unwrap());
+        }
+        headers
+    };
+
+    let mut session = Session::default();
+    session.update(&set_cookies(&[
+        "session=abc; Path=/; HttpOnly",
+        "theme=dark; Expires=Fri, 01 Jan 2100 00:00:00 GMT",
+        "old=1; Max-Age=3600; Expires=Thu, 01 Jan 1970 00:00:00 GMT",
+    ]));
+    assert_eq!(
+        session.cookie_header().as_deref(),
+        Some("old=1; session=abc; theme=dark")
+    );
+
+    // A past `Expires` removes the cookie, as does a `Max-Age` of zero.
+    session.update(&set_cookies(&[
+        "session=abc; Expires=Thu, 01 Jan 1970 00:00:00 GMT",
+        "theme=dark; Expires=Thu, 01-Jan-1970 00:00:00 GMT",
+        "old=1; Max-Age=0",
+    ]));
+    assert!(session.is_empty(), "{:?}", session.cookies);
+
+    session.update(&set_cookies(&["session=def"]));
+    let path = std::env::temp_dir().join(format!("session-{}.json", uuid::Uuid::new_v4()));
+    session.save(&path).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    assert_eq!(Session::load(&path).unwrap(), session);
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,216 @@
+//! Where a [`Client`](crate::Client) gets the token it sends.
+//!
+//! The client asks its [`TokenProvider`] for a token before every request,
+//! so a provider can hand out a new one whenever the old one is rotated. If
+//! the server answers `401 Unauthorized` anyway, the client asks the provider
+//! to [`refresh`](TokenProvider::refresh) and tries the request once more.
+//!
+//! ```no_run
+//! use oxide_api::{token::CommandToken, Client};
+//!
+//! let client = Client::new("", "https://oxide.example.com")
+//!     .with_token_provider(CommandToken::new("oxide-credential-helper").arg("get"));
+//! ```
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::transport::BoxFuture;
+
+/// Supplies the token sent with each request.
+pub trait TokenProvider: Send + Sync {
+    /// The token to send now.
+    fn token(&self) -> BoxFuture<'_, Result<String>>;
+
+    /// A token to use instead of one the server rejected. By default this is
+    /// just [`token`](TokenProvider::token) again, and a rejected request is
+    /// only retried if the token changed.
+    fn refresh(&self) -> BoxFuture<'_, Result<String>> {
+        self.token()
+    }
+}
+
+impl<T: TokenProvider + ?Sized> TokenProvider for Arc<T> {
+    fn token(&self) -> BoxFuture<'_, Result<String>> {
+        (**self).token()
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<String>> {
+        (**self).refresh()
+    }
+}
+
+impl<T: TokenProvider + ?Sized> TokenProvider for Box<T> {
+    fn token(&self) -> BoxFuture<'_, Result<String>> {
+        (**self).token()
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<String>> {
+        (**self).refresh()
+    }
+}
+
+/// The same token every time. This is what [`Client::new`](crate::Client::new)
+/// uses.
+#[derive(Clone)]
+pub struct StaticToken(String);
+
+impl StaticToken {
+    pub fn new<T: ToString>(token: T) -> Self {
+        StaticToken(token.to_string())
+    }
+}
+
+impl std::fmt::Debug for StaticToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("StaticToken")
+            .field(&crate::redact::REDACTED)
+            .finish()
+    }
+}
+
+impl TokenProvider for StaticToken {
+    fn token(&self) -> BoxFuture<'_, Result<String>> {
+        Box::pin(async move { Ok(self.0.clone()) })
+    }
+}
+
+/// The contents of a file, read again whenever the file is modified, so that
+/// whatever rotates the token only has to rewrite the file.
+#[derive(Debug)]
+pub struct FileToken {
+    path: PathBuf,
+    /// The token, and the modification time of the file it was read from.
+    cached: Mutex<Option<(Option<SystemTime>, String)>>,
+}
+
+impl FileToken {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        FileToken {
+            path: path.as_ref().to_path_buf(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read(&self, force: bool) -> Result<String> {
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let mut cached = self.cached.lock().unwrap();
+        if let Some((when, token)) = &*cached {
+            if !force && when.is_some() && *when == modified {
+                return Ok(token.clone());
+            }
+        }
+
+        let token = std::fs::read_to_string(&self.path)
+            .map_err(|e| anyhow!("reading token file {} failed: {}", self.path.display(), e))?
+            .trim()
+            .to_string();
+        if token.is_empty() {
+            bail!("token file {} is empty", self.path.display());
+        }
+        *cached = Some((modified, token.clone()));
+        Ok(token)
+    }
+}
+
+impl TokenProvider for FileToken {
+    fn token(&self) -> BoxFuture<'_, Result<String>> {
+        Box::pin(async move { self.read(false) })
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<String>> {
+        Box::pin(async move { self.read(true) })
+    }
+}
+
+/// The output of a command, like a git credential helper. The command is run
+/// for the first request, and again only when the server rejects the token
+/// it printed.
+#[derive(Debug)]
+pub struct CommandToken {
+    program: String,
+    args: Vec<String>,
+    cached: tokio::sync::Mutex<Option<String>>,
+}
+
+impl CommandToken {
+    pub fn new<P: ToString>(program: P) -> Self {
+        CommandToken {
+            program: program.to_string(),
+            args: Vec::new(),
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Also pass `arg` to the command.
+    pub fn arg<A: ToString>(mut self, arg: A) -> Self {
+        self.args.push(arg.to_string());
+        self
+    }
+
+    async fn run(&self) -> Result<String> {
+        let output = tokio::process::Command::new(&self.program)
+            .args(&self.args)
+            .stdin(std::process::Stdio::null())
+            .output()
+            .await
+            .map_err(|e| anyhow!("running token command {} failed: {}", self.program, e))?;
+        if !output.status.success() {
+            bail!(
+                "token command {} failed with {}: {}",
+                self.program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let token = String::from_utf8(output.stdout)
+            .map_err(|_| {
+                anyhow!(
+                    "token command {} printed something that is not UTF-8",
+                    self.program
+                )
+            })?
+            .trim()
+            .to_string();
+        if token.is_empty() {
+            bail!("token command {} printed nothing", self.program);
+        }
+        Ok(token)
+    }
+}
+
+impl TokenProvider for CommandToken {
+    fn token(&self) -> BoxFuture<'_, Result<String>> {
+        Box::pin(async move {
+            // Hold the lock while the command runs, so that concurrent
+            // requests wait for one run rather than each starting their own.
+            let mut cached = self.cached.lock().await;
+            if let Some(token) = &*cached {
+                return Ok(token.clone());
+            }
+            let token = self.run().await?;
+            *cached = Some(token.clone());
+            Ok(token)
+        })
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<String>> {
+        Box::pin(async move {
+            let mut cached = self.cached.lock().await;
+            let token = self.run().await?;
+            *cached = Some(token.clone());
+            Ok(token)
+        })
+    }
+}
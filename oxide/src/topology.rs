@@ -0,0 +1,462 @@
+//! Export the topology of a VPC as a graph.
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    Instance, NameSortMode, NetworkInterface, RouteTarget, RouterRoute, Vpc, VpcFirewallRule,
+    VpcFirewallRuleTarget, VpcRouter, VpcSubnet,
+};
+
+/// The kind of resource a node in the graph represents.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    Vpc,
+    Subnet,
+    Router,
+    Route,
+    NetworkInterface,
+    Instance,
+    /// A route target outside of the VPC, such as an IP address or internet gateway.
+    External,
+}
+
+impl NodeKind {
+    fn label(&self) -> &'static str {
+        match self {
+            NodeKind::Vpc => "vpc",
+            NodeKind::Subnet => "subnet",
+            NodeKind::Router => "router",
+            NodeKind::Route => "route",
+            NodeKind::NetworkInterface => "network interface",
+            NodeKind::Instance => "instance",
+            NodeKind::External => "external",
+        }
+    }
+
+    fn shape(&self) -> &'static str {
+        match self {
+            NodeKind::Vpc => "folder",
+            NodeKind::Subnet => "box",
+            NodeKind::Router => "diamond",
+            NodeKind::Route => "note",
+            NodeKind::NetworkInterface => "ellipse",
+            NodeKind::Instance => "component",
+            NodeKind::External => "plaintext",
+        }
+    }
+}
+
+/// A resource in the graph.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct Node {
+    /// The id of the resource, or a synthesized id for external nodes.
+    pub id: String,
+    pub kind: NodeKind,
+    /// The name of the resource.
+    pub name: String,
+    /// Extra detail about the resource, such as its address.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub detail: String,
+    /// The firewall rules that target this resource.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub firewall_rules: Vec<String>,
+}
+
+/// A relationship between two nodes.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub label: String,
+}
+
+/// The resources in a VPC and how they are connected.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct Topology {
+    pub vpc_name: String,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+/// Everything fetched from the API that makes up a VPC's topology.
+#[derive(Debug, Clone)]
+pub struct VpcResources {
+    pub vpc: Vpc,
+    pub subnets: Vec<VpcSubnet>,
+    pub routers: Vec<VpcRouter>,
+    pub routes: Vec<RouterRoute>,
+    pub network_interfaces: Vec<NetworkInterface>,
+    pub instances: Vec<Instance>,
+    pub firewall_rules: Vec<VpcFirewallRule>,
+}
+
+impl Topology {
+    /// Build the graph from the resources of a VPC.
+    ///
+    /// Instances that have no network interface in the VPC are left out.
+    pub fn new(resources: &VpcResources) -> Self {
+        let mut graph = Graph::default();
+        let vpc = &resources.vpc;
+
+        graph.node(
+            &vpc.id,
+            NodeKind::Vpc,
+            &vpc.name,
+            vpc.ipv6_prefix.to_string(),
+        );
+
+        for subnet in &resources.subnets {
+            graph.node(
+                &subnet.id,
+                NodeKind::Subnet,
+                &subnet.name,
+                format!("{}\n{}", subnet.ipv4_block, subnet.ipv6_block),
+            );
+            graph.edge(&vpc.id, &subnet.id, "subnet");
+        }
+
+        for nic in &resources.network_interfaces {
+            graph.node(
+                &nic.id,
+                NodeKind::NetworkInterface,
+                &nic.name,
+                format!("{}\n{}", nic.ip, nic.mac),
+            );
+            graph.edge(&nic.subnet_id, &nic.id, "interface");
+        }
+
+        for instance in &resources.instances {
+            let nics: Vec<&NetworkInterface> = resources
+                .network_interfaces
+                .iter()
+                .filter(|nic| nic.instance_id == instance.id)
+                .collect();
+            if nics.is_empty() {
+                continue;
+            }
+
+            graph.node(
+                &instance.id,
+                NodeKind::Instance,
+                &instance.name,
+                instance.run_state.to_string(),
+            );
+            for nic in nics {
+                graph.edge(&instance.id, &nic.id, "attached");
+            }
+        }
+
+        for router in &resources.routers {
+            graph.node(
+                &router.id,
+                NodeKind::Router,
+                &router.name,
+                router.kind.to_string(),
+            );
+            graph.edge(&vpc.id, &router.id, "router");
+        }
+
+        for route in &resources.routes {
+            graph.node(
+                &route.id,
+                NodeKind::Route,
+                &route.name,
+                route.destination.to_string(),
+            );
+            graph.edge(&route.vpc_router_id, &route.id, "route");
+
+            let target = match &route.target {
+                RouteTarget::Vpc(name) if *name == vpc.name => Some(vpc.id.to_string()),
+                RouteTarget::Subnet(name) => resources
+                    .subnets
+                    .iter()
+                    .find(|s| s.name == *name)
+                    .map(|s| s.id.to_string()),
+                RouteTarget::Instance(name) => resources
+                    .instances
+                    .iter()
+                    .find(|i| i.name == *name && graph.index.contains_key(&i.id))
+                    .map(|i| i.id.to_string()),
+                _ => None,
+            };
+            let target = target.unwrap_or_else(|| {
+                let id = format!("external:{}", route.target);
+                graph.node(
+                    &id,
+                    NodeKind::External,
+                    &route.target.to_string(),
+                    "".to_string(),
+                );
+                id
+            });
+            graph.edge(&route.id, &target, "target");
+        }
+
+        for rule in &resources.firewall_rules {
+            let summary = rule_summary(rule);
+            for target in &rule.targets {
+                let ids: Vec<String> = match target {
+                    VpcFirewallRuleTarget::Vpc(name) if *name == vpc.name => {
+                        vec![vpc.id.to_string()]
+                    }
+                    VpcFirewallRuleTarget::Vpc(_) => vec![],
+                    VpcFirewallRuleTarget::Subnet(name) => resources
+                        .subnets
+                        .iter()
+                        .filter(|s| s.name == *name)
+                        .map(|s| s.id.to_string())
+                        .collect(),
+                    VpcFirewallRuleTarget::Instance(name) => resources
+                        .instances
+                        .iter()
+                        .filter(|i| i.name == *name)
+                        .map(|i| i.id.to_string())
+                        .collect(),
+                    VpcFirewallRuleTarget::Ip(ip) => resources
+                        .network_interfaces
+                        .iter()
+                        .filter(|nic| nic.ip == *ip)
+                        .map(|nic| nic.id.to_string())
+                        .collect(),
+                    VpcFirewallRuleTarget::IpNet(net) => resources
+                        .network_interfaces
+                        .iter()
+                        .filter(|nic| ip_net_contains(net, nic.ip))
+                        .map(|nic| nic.id.to_string())
+                        .collect(),
+                };
+                for id in ids {
+                    graph.annotate(&id, &summary);
+                }
+            }
+        }
+
+        Topology {
+            vpc_name: vpc.name.to_string(),
+            nodes: graph.nodes,
+            edges: graph.edges,
+        }
+    }
+
+    /// Render the graph in the Graphviz DOT language.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("digraph {} {{\n", dot_quote(&self.vpc_name)));
+        for node in &self.nodes {
+            let mut label = format!("{}\\n{}", node.kind.label(), dot_escape(&node.name));
+            if !node.detail.is_empty() {
+                label.push_str(&format!("\\n{}", dot_escape(&node.detail)));
+            }
+            for rule in &node.firewall_rules {
+                label.push_str(&format!("\\n[{}]", dot_escape(rule)));
+            }
+            out.push_str(&format!(
+                "    {} [label=\"{}\", shape={}];\n",
+                dot_quote(&node.id),
+                label,
+                node.kind.shape()
+            ));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "    {} -> {} [label={}];\n",
+                dot_quote(&edge.from),
+                dot_quote(&edge.to),
+                dot_quote(&edge.label)
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render the graph as pretty-printed JSON nodes and edges.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Nodes in insertion order so the output is stable, indexed by id.
+#[derive(Default)]
+struct Graph {
+    nodes: Vec<Node>,
+    index: BTreeMap<String, usize>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    fn node(&mut self, id: &str, kind: NodeKind, name: &str, detail: String) {
+        if self.index.contains_key(id) {
+            return;
+        }
+        self.index.insert(id.to_string(), self.nodes.len());
+        self.nodes.push(Node {
+            id: id.to_string(),
+            kind,
+            name: name.to_string(),
+            detail,
+            firewall_rules: Default::default(),
+        });
+    }
+
+    fn edge(&mut self, from: &str, to: &str, label: &str) {
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: label.to_string(),
+        });
+    }
+
+    fn annotate(&mut self, id: &str, rule: &str) {
+        if let Some(&i) = self.index.get(id) {
+            let node = &mut self.nodes[i];
+            if !node.firewall_rules.iter().any(|r| r == rule) {
+                node.firewall_rules.push(rule.to_string());
+            }
+        }
+    }
+}
+
+fn rule_summary(rule: &VpcFirewallRule) -> String {
+    let mut summary = format!("{}: {} {}", rule.name, rule.action, rule.direction);
+    let protocols: Vec<String> = rule
+        .filters
+        .protocols
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+    if !protocols.is_empty() {
+        summary.push_str(&format!(" {}", protocols.join(",")));
+    }
+    if !rule.filters.ports.is_empty() {
+        summary.push_str(&format!(" ports {}", rule.filters.ports.join(",")));
+    }
+    let hosts: Vec<String> = rule.filters.hosts.iter().map(|h| h.to_string()).collect();
+    if !hosts.is_empty() {
+        summary.push_str(&format!(" from {}", hosts.join(",")));
+    }
+    summary
+}
+
+fn ip_net_contains(net: &crate::types::IpNet, ip: std::net::IpAddr) -> bool {
+    match (net, ip) {
+        (crate::types::IpNet::V4(net), std::net::IpAddr::V4(ip)) => net.contains(ip),
+        (crate::types::IpNet::V6(net), std::net::IpAddr::V6(ip)) => net.contains(ip),
+        _ => false,
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn dot_quote(s: &str) -> String {
+    format!("\"{}\"", dot_escape(s))
+}
+
+impl crate::vpcs::Vpcs {
+    /// Fetch every resource that makes up the topology of a VPC.
+    pub async fn resources(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        vpc_name: &str,
+    ) -> Result<VpcResources> {
+        let vpc = self.get(organization_name, project_name, vpc_name).await?;
+        let subnets = self
+            .client
+            .subnets()
+            .get_all(
+                organization_name,
+                project_name,
+                NameSortMode::NameAscending,
+                vpc_name,
+            )
+            .await?;
+        let routers = self
+            .client
+            .routers()
+            .get_all(
+                organization_name,
+                project_name,
+                NameSortMode::NameAscending,
+                vpc_name,
+            )
+            .await?;
+
+        let mut routes = Vec::new();
+        for router in &routers {
+            routes.append(
+                &mut self
+                    .client
+                    .routes()
+                    .get_all(
+                        organization_name,
+                        project_name,
+                        &router.name,
+                        NameSortMode::NameAscending,
+                        vpc_name,
+                    )
+                    .await?,
+            );
+        }
+
+        let mut network_interfaces = Vec::new();
+        for subnet in &subnets {
+            network_interfaces.append(
+                &mut self
+                    .client
+                    .subnets()
+                    .network_interfaces_get_all(
+                        organization_name,
+                        project_name,
+                        NameSortMode::NameAscending,
+                        &subnet.name,
+                        vpc_name,
+                    )
+                    .await?,
+            );
+        }
+
+        let instances = self
+            .client
+            .instances()
+            .get_all(organization_name, project_name, NameSortMode::NameAscending)
+            .await?;
+        let firewall_rules = self
+            .client
+            .firewall()
+            .rules_get(organization_name, project_name, vpc_name)
+            .await?
+            .rules;
+
+        Ok(VpcResources {
+            vpc,
+            subnets,
+            routers,
+            routes,
+            network_interfaces,
+            instances,
+            firewall_rules,
+        })
+    }
+
+    /// Build the topology graph of a VPC.
+    pub async fn topology(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        vpc_name: &str,
+    ) -> Result<Topology> {
+        let resources = self
+            .resources(organization_name, project_name, vpc_name)
+            .await?;
+
+        Ok(Topology::new(&resources))
+    }
+}
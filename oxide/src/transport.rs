@@ -0,0 +1,326 @@
+//! How a [`Client`](crate::Client) sends requests.
+//!
+//! Every generated call builds an `http::Request` and hands it to the
+//! client's [`Transport`], which returns the whole `http::Response`. By
+//! default that is a [`ReqwestTransport`], but anything that can answer a
+//! request will do, which is how fakes and recording work:
+//!
+//! ```
+//! use bytes::Bytes;
+//! use oxide_api::transport::{BoxFuture, Transport};
+//!
+//! struct NotFound;
+//!
+//! impl Transport for NotFound {
+//!     fn send(
+//!         &self,
+//!         _request: http::Request<Bytes>,
+//!     ) -> BoxFuture<'_, anyhow::Result<http::Response<Bytes>>> {
+//!         Box::pin(async {
+//!             Ok(http::Response::builder()
+//!                 .status(http::StatusCode::NOT_FOUND)
+//!                 .body(Bytes::new())?)
+//!         })
+//!     }
+//! }
+//!
+//! let client = oxide_api::Client::new("token", "http://oxide.test").with_transport(NotFound);
+//! ```
+//!
+//! A deployment behind an internal CA or a proxy needs a `ReqwestTransport`
+//! built from [`TransportOptions`], and a local stand-in listening on a unix
+//! socket needs an [`UnixSocketTransport`].
+use std::{convert::TryFrom, fmt, net::SocketAddr, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, bail, Result};
+use bytes::Bytes;
+pub use futures::future::BoxFuture;
+
+/// Sends a request and waits for the whole response.
+pub trait Transport: Send + Sync {
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Bytes>>>;
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Bytes>>> {
+        (**self).send(request)
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Bytes>>> {
+        (**self).send(request)
+    }
+}
+
+/// Sends requests over the network with `reqwest`.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+/// How a [`ReqwestTransport`] connects, for deployments the defaults cannot
+/// reach:
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// use oxide_api::transport::TransportOptions;
+///
+/// let transport = TransportOptions::new()
+///     .root_certificates(&std::fs::read("/etc/ssl/certs/lab-ca.pem")?)
+///     .proxy("http://proxy.lab.example.com:3128")
+///     .no_proxy(&["localhost", ".internal.example.com"])
+///     .build()?;
+/// let client =
+///     oxide_api::Client::new("token", "https://oxide.lab.example.com").with_transport(transport);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct TransportOptions {
+    root_certificates: Vec<Vec<u8>>,
+    /// A client certificate chain and its private key, in one PEM document.
+    identity: Option<Vec<u8>>,
+    accept_invalid_certs: bool,
+    proxy: Option<String>,
+    no_proxy: Vec<String>,
+    resolve: Vec<(String, SocketAddr)>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+}
+
+impl fmt::Debug for TransportOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Never print the private key.
+        f.debug_struct("TransportOptions")
+            .field("root_certificates", &self.root_certificates.len())
+            .field("identity", &self.identity.is_some())
+            .field("accept_invalid_certs", &self.accept_invalid_certs)
+            .field("proxy", &self.proxy)
+            .field("no_proxy", &self.no_proxy)
+            .field("resolve", &self.resolve)
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .finish()
+    }
+}
+
+impl TransportOptions {
+    pub fn new() -> Self {
+        TransportOptions::default()
+    }
+
+    /// Trust the certificates in the PEM bundle `pem` as well as the Mozilla
+    /// roots bundled with `webpki-roots`. The operating system's certificate
+    /// store is not consulted.
+    pub fn root_certificates(mut self, pem: &[u8]) -> Self {
+        self.root_certificates.push(pem.to_vec());
+        self
+    }
+
+    /// Present a client certificate, for servers that require mutual TLS.
+    /// `certificate` is the PEM certificate chain, leaf first, and `key` the
+    /// PEM PKCS #8 or RSA private key.
+    pub fn identity(mut self, certificate: &[u8], key: &[u8]) -> Self {
+        let mut pem = certificate.to_vec();
+        pem.push(b'\n');
+        pem.extend_from_slice(key);
+        self.identity = Some(pem);
+        self
+    }
+
+    /// Accept any certificate, even an expired or self-signed one for another
+    /// host. This makes TLS worthless against an attacker, so it is only for
+    /// development.
+    pub fn danger_accept_invalid_certs(mut self) -> Self {
+        self.accept_invalid_certs = true;
+        self
+    }
+
+    /// Send requests through the HTTP or HTTPS proxy at `url`, rather than
+    /// any proxy set in the environment. Credentials may be given in the URL.
+    pub fn proxy(mut self, url: &str) -> Self {
+        self.proxy = Some(url.to_string());
+        self
+    }
+
+    /// Connect directly to these hosts rather than through the proxy set
+    /// with [`proxy`](TransportOptions::proxy). A host also matches its
+    /// subdomains, a leading `.` matches only subdomains, and `*` matches
+    /// everything. A proxy from the environment is bypassed according to
+    /// `NO_PROXY` instead, so [`build`](TransportOptions::build) fails if
+    /// this is used without `proxy`.
+    pub fn no_proxy<S: AsRef<str>>(mut self, hosts: &[S]) -> Self {
+        self.no_proxy.extend(
+            hosts
+                .iter()
+                .map(|host| host.as_ref().trim().to_lowercase())
+                .filter(|host| !host.is_empty()),
+        );
+        self
+    }
+
+    /// Connect to `addr` for `host`, rather than looking it up. The port of
+    /// `addr` is ignored in favor of the one in the URL.
+    pub fn resolve(mut self, host: &str, addr: SocketAddr) -> Self {
+        self.resolve.push((host.to_string(), addr));
+        self
+    }
+
+    /// Give up on a request that has not finished after `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Give up on connecting after `timeout`.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn build(&self) -> Result<ReqwestTransport> {
+        let mut builder = reqwest::Client::builder();
+        for pem in &self.root_certificates {
+            builder = builder.add_root_certificate(
+                reqwest::Certificate::from_pem(pem)
+                    .map_err(|e| anyhow!("parsing root certificates failed: {}", e))?,
+            );
+        }
+        if let Some(pem) = &self.identity {
+            builder = builder.identity(
+                reqwest::Identity::from_pem(pem)
+                    .map_err(|e| anyhow!("parsing client certificate failed: {}", e))?,
+            );
+        }
+        if self.accept_invalid_certs {
+            log::warn!("TLS certificates are not being verified");
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        if self.proxy.is_none() && !self.no_proxy.is_empty() {
+            bail!("no_proxy only applies to a proxy set with proxy()");
+        }
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Url::parse(proxy)
+                .map_err(|e| anyhow!("parsing proxy URL {} failed: {}", proxy, e))?;
+            let no_proxy = self.no_proxy.clone();
+            builder = builder.proxy(reqwest::Proxy::custom(move |url| match url.host_str() {
+                Some(host) if bypasses_proxy(&no_proxy, host) => None,
+                _ => Some(proxy.clone()),
+            }));
+        }
+        for (host, addr) in &self.resolve {
+            builder = builder.resolve(host, *addr);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        Ok(ReqwestTransport::new(builder.build()?))
+    }
+}
+
+/// Whether `host` is in the `no_proxy` list.
+fn bypasses_proxy(no_proxy: &[String], host: &str) -> bool {
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_lowercase();
+    no_proxy.iter().any(|entry| {
+        if entry == "*" {
+            true
+        } else if entry.starts_with('.') {
+            host.ends_with(entry.as_str())
+        } else {
+            host == *entry || host.ends_with(&format!(".{}", entry))
+        }
+    })
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Bytes>>> {
+        Box::pin(async move {
+            let request = reqwest::Request::try_from(request)?;
+            let response = self.client.execute(request).await?;
+
+            let mut builder = http::Response::builder()
+                .status(response.status())
+                .version(response.version());
+            if let Some(headers) = builder.headers_mut() {
+                *headers = response.headers().clone();
+            }
+            Ok(builder.body(response.bytes().await?)?)
+        })
+    }
+}
+
+/// Sends requests over a unix domain socket, to a local stand-in for the API.
+/// The host in each request's URL is only used for the `Host` header, so the
+/// client can be made with any host, such as `http://localhost`.
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub struct UnixSocketTransport {
+    path: std::path::PathBuf,
+}
+
+#[cfg(unix)]
+impl UnixSocketTransport {
+    pub fn new<P: AsRef<std::path::Path>>(path: P) -> Self {
+        UnixSocketTransport {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+#[cfg(unix)]
+impl Transport for UnixSocketTransport {
+    fn send(&self, request: http::Request<Bytes>) -> BoxFuture<'_, Result<http::Response<Bytes>>> {
+        Box::pin(async move {
+            let stream = tokio::net::UnixStream::connect(&self.path)
+                .await
+                .map_err(|e| anyhow!("connecting to {} failed: {}", self.path.display(), e))?;
+            // Every request gets its own connection, which closes once the
+            // response has been read.
+            let (mut sender, connection) = hyper::client::conn::handshake(stream).await?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    log::debug!("unix socket connection failed: {}", e);
+                }
+            });
+
+            let (mut parts, body) = request.into_parts();
+            if let Some(authority) = parts.uri.authority() {
+                if !parts.headers.contains_key(http::header::HOST) {
+                    let host = http::HeaderValue::from_str(authority.as_str())?;
+                    parts.headers.insert(http::header::HOST, host);
+                }
+            }
+            parts.uri = parts
+                .uri
+                .path_and_query()
+                .map_or("/", |path| path.as_str())
+                .parse()?;
+
+            let response = sender
+                .send_request(hyper::Request::from_parts(parts, hyper::Body::from(body)))
+                .await?;
+            let (parts, body) = response.into_parts();
+            Ok(http::Response::from_parts(
+                parts,
+                hyper::body::to_bytes(body).await?,
+            ))
+        })
+    }
+}
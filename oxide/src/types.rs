@@ -2094,6 +2094,13 @@ impl std::str::FromStr for IpNet {
 #[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
 pub struct Ipv4Net(pub ipnetwork::Ipv4Network);
 
+impl Default for Ipv4Net {
+    /// `0.0.0.0/0`, every IPv4 address.
+    fn default() -> Self {
+        Ipv4Net(ipnetwork::Ipv4Network::new(std::net::Ipv4Addr::UNSPECIFIED, 0).unwrap())
+    }
+}
+
 impl Ipv4Net {
     /// Return `true` if this IPv4 subnetwork is from an RFC 1918 private
     /// address space.
@@ -2169,6 +2176,13 @@ impl JsonSchema for Ipv4Net {
 #[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
 pub struct Ipv6Net(pub ipnetwork::Ipv6Network);
 
+impl Default for Ipv6Net {
+    /// `::/0`, every IPv6 address.
+    fn default() -> Self {
+        Ipv6Net(ipnetwork::Ipv6Network::new(std::net::Ipv6Addr::UNSPECIFIED, 0).unwrap())
+    }
+}
+
 impl Ipv6Net {
     /// The length for all VPC IPv6 prefixes
     pub const VPC_IPV6_PREFIX_LENGTH: u8 = 48;
@@ -2270,7 +2284,7 @@ pub struct LoginParams {
 }
 
 /// A `NetworkInterface` represents a virtual network interface device.
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default, Tabled)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Tabled)]
 pub struct NetworkInterface {
     /**
      * unique, immutable, system-controlled identifier for each resource
@@ -2315,22 +2329,14 @@ pub struct NetworkInterface {
     /**
      * The IP address assigned to this interface.
      */
-    #[serde(
-        default,
-        skip_serializing_if = "String::is_empty",
-        deserialize_with = "crate::utils::deserialize_null_string::deserialize"
-    )]
-    pub ip: String,
+    #[serde()]
+    pub ip: std::net::IpAddr,
 
     /**
      * A Media Access Control address, in EUI-48 format
      */
-    #[serde(
-        default,
-        skip_serializing_if = "String::is_empty",
-        deserialize_with = "crate::utils::deserialize_null_string::deserialize"
-    )]
-    pub mac: String,
+    #[serde()]
+    pub mac: crate::types::MacAddr,
 
     /**
      * The subnet to which the interface belongs.
@@ -2365,6 +2371,24 @@ pub struct NetworkInterface {
     pub vpc_id: String,
 }
 
+impl Default for NetworkInterface {
+    // `std::net::IpAddr` has no default, so this cannot be derived.
+    fn default() -> Self {
+        NetworkInterface {
+            id: Default::default(),
+            name: Default::default(),
+            description: Default::default(),
+            instance_id: Default::default(),
+            ip: std::net::Ipv4Addr::UNSPECIFIED.into(),
+            mac: Default::default(),
+            subnet_id: Default::default(),
+            time_created: Default::default(),
+            time_modified: Default::default(),
+            vpc_id: Default::default(),
+        }
+    }
+}
+
 /// Create-time parameters for a [`NetworkInterface`](omicron_common::api::external::NetworkInterface)
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Default, Tabled)]
 pub struct NetworkInterfaceCreate {
@@ -2388,12 +2412,9 @@ pub struct NetworkInterfaceCreate {
     /**
      * The IP address for the interface. One will be auto-assigned if not provided.
      */
-    #[serde(
-        default,
-        skip_serializing_if = "String::is_empty",
-        deserialize_with = "crate::utils::deserialize_null_string::deserialize"
-    )]
-    pub ip: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[header(hidden = true)]
+    pub ip: Option<std::net::IpAddr>,
 
     /**
      * Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
@@ -2964,7 +2985,7 @@ pub struct RoleResultsPage {
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "type", content = "value")]
 pub enum RouteDestination {
-    Ip(String),
+    Ip(std::net::IpAddr),
     IpNet(IpNet),
     Vpc(String),
     Subnet(String),
@@ -3101,7 +3122,7 @@ impl std::str::FromStr for RouteDestinationType {
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "type", content = "value")]
 pub enum RouteTarget {
-    Ip(String),
+    Ip(std::net::IpAddr),
     Vpc(String),
     Subnet(String),
     Instance(String),
@@ -4422,12 +4443,8 @@ pub struct Vpc {
     /**
      * An IPv6 subnet, including prefix and subnet mask
      */
-    #[serde(
-        default,
-        skip_serializing_if = "String::is_empty",
-        deserialize_with = "crate::utils::deserialize_null_string::deserialize"
-    )]
-    pub ipv6_prefix: String,
+    #[serde()]
+    pub ipv6_prefix: crate::types::Ipv6Net,
 
     /**
      * id for the project containing this VPC
@@ -4497,12 +4514,9 @@ pub struct VpcCreate {
      *  
      *  All IPv6 subnets created from this VPC must be taken from this range, which sould be a Unique Local Address in the range `fd00::/48`. The default VPC Subnet will have the first `/64` range from this prefix.
      */
-    #[serde(
-        default,
-        skip_serializing_if = "String::is_empty",
-        deserialize_with = "crate::utils::deserialize_null_string::deserialize"
-    )]
-    pub ipv6_prefix: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[header(hidden = true)]
+    pub ipv6_prefix: Option<crate::types::Ipv6Net>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Tabled)]
@@ -4684,7 +4698,7 @@ pub enum VpcFirewallRuleTarget {
     Vpc(String),
     Subnet(String),
     Instance(String),
-    Ip(String),
+    Ip(std::net::IpAddr),
     IpNet(IpNet),
 }
 
@@ -4926,7 +4940,7 @@ pub enum VpcFirewallRuleHostFilter {
     Vpc(String),
     Subnet(String),
     Instance(String),
-    Ip(String),
+    Ip(std::net::IpAddr),
     IpNet(IpNet),
 }
 
@@ -5437,22 +5451,14 @@ pub struct VpcSubnet {
     /**
      * An IPv4 subnet, including prefix and subnet mask
      */
-    #[serde(
-        default,
-        skip_serializing_if = "String::is_empty",
-        deserialize_with = "crate::utils::deserialize_null_string::deserialize"
-    )]
-    pub ipv4_block: String,
+    #[serde()]
+    pub ipv4_block: crate::types::Ipv4Net,
 
     /**
      * An IPv6 subnet, including prefix and subnet mask
      */
-    #[serde(
-        default,
-        skip_serializing_if = "String::is_empty",
-        deserialize_with = "crate::utils::deserialize_null_string::deserialize"
-    )]
-    pub ipv6_block: String,
+    #[serde()]
+    pub ipv6_block: crate::types::Ipv6Net,
 
     /**
      * timestamp when this resource was created
@@ -5500,24 +5506,17 @@ pub struct VpcSubnetCreate {
     /**
      * An IPv4 subnet, including prefix and subnet mask
      */
-    #[serde(
-        default,
-        skip_serializing_if = "String::is_empty",
-        deserialize_with = "crate::utils::deserialize_null_string::deserialize"
-    )]
-    pub ipv4_block: String,
+    #[serde()]
+    pub ipv4_block: crate::types::Ipv4Net,
 
     /**
      * The IPv6 address range for this subnet.
      *  
      *  It must be allocated from the RFC 4193 Unique Local Address range, with the prefix equal to the parent VPC's prefix. A random `/64` block will be assigned if one is not provided. It must not overlap with any existing subnet in the VPC.
      */
-    #[serde(
-        default,
-        skip_serializing_if = "String::is_empty",
-        deserialize_with = "crate::utils::deserialize_null_string::deserialize"
-    )]
-    pub ipv6_block: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[header(hidden = true)]
+    pub ipv6_block: Option<crate::types::Ipv6Net>,
 }
 
 /// A single page of results
@@ -5744,7 +5743,89 @@ pub type InstanceCpuCount = u16;
 /// An inclusive-inclusive range of IP ports. The second port may be omitted to represent a single port
 pub type L4PortRange = String;
 /// A Media Access Control address, in EUI-48 format
-pub type MacAddr = String;
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl std::fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            a, b, c, d, e, g
+        )
+    }
+}
+
+impl std::str::FromStr for MacAddr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut octets = [0u8; 6];
+        let mut parts = s.split(':');
+        for octet in octets.iter_mut() {
+            let part = parts
+                .next()
+                .ok_or_else(|| format!("invalid MAC address {}", s))?;
+            // `from_str_radix` would also take a sign, as in `+f`.
+            if part.len() != 2 || !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(format!("invalid MAC address {}", s));
+            }
+            *octet =
+                u8::from_str_radix(part, 16).map_err(|_| format!("invalid MAC address {}", s))?;
+        }
+        if parts.next().is_some() {
+            return Err(format!("invalid MAC address {}", s));
+        }
+        Ok(MacAddr(octets))
+    }
+}
+
+impl Serialize for MacAddr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for MacAddr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for MacAddr {
+    fn schema_name() -> String {
+        "MacAddr".to_string()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::Schema::Object(schemars::schema::SchemaObject {
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                title: Some("A MAC address".to_string()),
+                description: Some("A Media Access Control address, in EUI-48 format".to_string()),
+                examples: vec!["ff:ff:ff:ff:ff:ff".into()],
+                ..Default::default()
+            })),
+            instance_type: Some(schemars::schema::SingleOrVec::Single(Box::new(
+                schemars::schema::InstanceType::String,
+            ))),
+            string: Some(Box::new(schemars::schema::StringValidation {
+                // Six octets of two hex digits, separated by ":".
+                max_length: Some(17),
+                min_length: Some(17),
+                pattern: Some("^([0-9a-fA-F]{2}:){5}[0-9a-fA-F]{2}$".to_string()),
+            })),
+            ..Default::default()
+        })
+    }
+}
 /// Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
 pub type Name = String;
 /// Role names consist of two string components separated by dot (".").
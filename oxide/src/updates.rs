@@ -17,8 +17,25 @@ impl Updates {
      *
      * This function performs a `POST` to the `/updates/refresh` endpoint.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "updates_refresh",
+            skip_all,
+            err,
+            fields(
+                operation_id = "updates_refresh",
+                tag = "updates",
+                method = "POST",
+                path = "/updates/refresh",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn refresh(&self) -> Result<()> {
         let url = "/updates/refresh".to_string();
-        self.client.post(&url, None).await
+        self.client.post("updates_refresh", &url, None).await
     }
 }
@@ -0,0 +1,263 @@
+//! Resource usage totals per project, per organization and fleet-wide.
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tabled::Tabled;
+
+use crate::types::{Disk, Image, Instance, NameOrIdSortMode, NameSortMode, Snapshot};
+
+/// Totals for a set of resources. Sizes are in bytes.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default, JsonSchema, Tabled)]
+pub struct Usage {
+    pub instances: u64,
+    pub ncpus: u64,
+    pub memory: u64,
+    pub disks: u64,
+    pub disk_bytes: u64,
+    pub snapshots: u64,
+    pub snapshot_bytes: u64,
+    pub images: u64,
+    pub image_bytes: u64,
+}
+
+impl Usage {
+    fn add_instance(&mut self, instance: &Instance) {
+        self.instances += 1;
+        self.ncpus += instance.ncpus as u64;
+        self.memory += instance.memory;
+    }
+
+    fn add(&mut self, other: &Usage) {
+        self.instances += other.instances;
+        self.ncpus += other.ncpus;
+        self.memory += other.memory;
+        self.disks += other.disks;
+        self.disk_bytes += other.disk_bytes;
+        self.snapshots += other.snapshots;
+        self.snapshot_bytes += other.snapshot_bytes;
+        self.images += other.images;
+        self.image_bytes += other.image_bytes;
+    }
+}
+
+/// The resources in a project that count towards its usage.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct ProjectResources {
+    pub organization_name: String,
+    pub project_name: String,
+    pub instances: Vec<Instance>,
+    pub disks: Vec<Disk>,
+    pub snapshots: Vec<Snapshot>,
+    pub images: Vec<Image>,
+}
+
+/// The usage of a project, an organization or the whole fleet.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, JsonSchema, Tabled)]
+pub struct UsageRow {
+    /// The organization, or empty for the fleet-wide total.
+    pub organization: String,
+    /// The project, or empty for an organization or fleet-wide total.
+    pub project: String,
+    /// The state of the instances counted, or empty for all of them. Storage
+    /// is only counted in rows for all instances.
+    pub instance_state: String,
+    #[serde(flatten)]
+    #[header(inline)]
+    pub usage: Usage,
+}
+
+/// Usage totals, in the order: each project in an organization followed by
+/// the organization's total, then the fleet-wide total.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default, JsonSchema)]
+pub struct UsageReport {
+    pub rows: Vec<UsageRow>,
+}
+
+/// Usage in total, and for the instances in each state.
+#[derive(Default)]
+struct Totals {
+    all: Usage,
+    by_state: BTreeMap<String, Usage>,
+}
+
+impl Totals {
+    fn add(&mut self, other: &Totals) {
+        self.all.add(&other.all);
+        for (state, usage) in &other.by_state {
+            self.by_state
+                .entry(state.to_string())
+                .or_default()
+                .add(usage);
+        }
+    }
+
+    fn rows(&self, organization: &str, project: &str, split_by_state: bool) -> Vec<UsageRow> {
+        let row = |instance_state: &str, usage: &Usage| UsageRow {
+            organization: organization.to_string(),
+            project: project.to_string(),
+            instance_state: instance_state.to_string(),
+            usage: usage.clone(),
+        };
+
+        let mut rows = vec![row("", &self.all)];
+        if split_by_state {
+            rows.extend(self.by_state.iter().map(|(state, usage)| row(state, usage)));
+        }
+        rows
+    }
+}
+
+impl UsageReport {
+    /// Sum the usage of `projects`. With `split_by_state` set, instances are
+    /// also totalled separately for each state they are in, e.g. running and
+    /// stopped.
+    pub fn new(projects: &[ProjectResources], split_by_state: bool) -> Self {
+        let mut organizations: BTreeMap<&str, Vec<&ProjectResources>> = BTreeMap::new();
+        for project in projects {
+            organizations
+                .entry(&project.organization_name)
+                .or_default()
+                .push(project);
+        }
+
+        let mut rows = Vec::new();
+        let mut fleet = Totals::default();
+        for (organization, mut projects) in organizations {
+            projects.sort_by(|a, b| a.project_name.cmp(&b.project_name));
+
+            let mut organization_totals = Totals::default();
+            for project in projects {
+                let totals = project_totals(project);
+                rows.extend(totals.rows(organization, &project.project_name, split_by_state));
+                organization_totals.add(&totals);
+            }
+            rows.extend(organization_totals.rows(organization, "", split_by_state));
+            fleet.add(&organization_totals);
+        }
+        rows.extend(fleet.rows("", "", split_by_state));
+
+        UsageReport { rows }
+    }
+
+    /// Fetch the resources of every project visible to the client and sum
+    /// their usage.
+    pub async fn fetch(client: &crate::Client, split_by_state: bool) -> Result<Self> {
+        let mut projects = Vec::new();
+        for organization in client
+            .organizations()
+            .get_all(NameOrIdSortMode::NameAscending)
+            .await?
+        {
+            for project in client
+                .projects()
+                .get_all(&organization.name, NameOrIdSortMode::NameAscending)
+                .await?
+            {
+                let (o, p) = (organization.name.as_str(), project.name.as_str());
+                projects.push(ProjectResources {
+                    organization_name: o.to_string(),
+                    project_name: p.to_string(),
+                    instances: client
+                        .instances()
+                        .get_all(o, p, NameSortMode::NameAscending)
+                        .await?,
+                    disks: client
+                        .disks()
+                        .get_all(o, p, NameSortMode::NameAscending)
+                        .await?,
+                    snapshots: client
+                        .snapshots()
+                        .get_all(o, p, NameSortMode::NameAscending)
+                        .await?,
+                    images: client
+                        .images()
+                        .get_all(o, p, NameSortMode::NameAscending)
+                        .await?,
+                });
+            }
+        }
+
+        Ok(UsageReport::new(&projects, split_by_state))
+    }
+
+    /// The fleet-wide total for all instances.
+    pub fn fleet(&self) -> Option<&Usage> {
+        self.rows
+            .iter()
+            .find(|r| r.organization.is_empty() && r.instance_state.is_empty())
+            .map(|r| &r.usage)
+    }
+
+    /// Render the report as a table.
+    pub fn to_table(&self) -> String {
+        tabled::Table::new(&self.rows).to_string()
+    }
+
+    /// Render the report as CSV, with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "organization,project,instance_state,instances,ncpus,memory,disks,disk_bytes,\
+             snapshots,snapshot_bytes,images,image_bytes\n",
+        );
+        for row in &self.rows {
+            let u = &row.usage;
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                csv_field(&row.organization),
+                csv_field(&row.project),
+                csv_field(&row.instance_state),
+                u.instances,
+                u.ncpus,
+                u.memory,
+                u.disks,
+                u.disk_bytes,
+                u.snapshots,
+                u.snapshot_bytes,
+                u.images,
+                u.image_bytes
+            ));
+        }
+        out
+    }
+
+    /// Render the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+fn project_totals(project: &ProjectResources) -> Totals {
+    let mut totals = Totals::default();
+    for instance in &project.instances {
+        totals.all.add_instance(instance);
+        totals
+            .by_state
+            .entry(instance.run_state.to_string())
+            .or_default()
+            .add_instance(instance);
+    }
+    for disk in &project.disks {
+        totals.all.disks += 1;
+        totals.all.disk_bytes += disk.size;
+    }
+    for snapshot in &project.snapshots {
+        totals.all.snapshots += 1;
+        totals.all.snapshot_bytes += snapshot.size;
+    }
+    for image in &project.images {
+        totals.all.images += 1;
+        totals.all.image_bytes += image.size;
+    }
+    totals
+}
+
+/// Quote a CSV field if it needs to be.
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
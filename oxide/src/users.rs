@@ -25,6 +25,23 @@ impl Users {
      *  
      *  Currently, we only support scanning in ascending order.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "users_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "users_get",
+                tag = "users",
+                method = "GET",
+                path = "/users",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -44,7 +61,7 @@ impl Users {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/users?{}", query_);
 
-        let resp: crate::types::UserResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::UserResultsPage = self.client.get("users_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -57,6 +74,23 @@ impl Users {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "users_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "users_get",
+                tag = "users",
+                method = "GET",
+                path = "/users",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         sort_by: crate::types::NameSortMode,
@@ -68,7 +102,8 @@ impl Users {
         let query_ = serde_urlencoded::to_string(&query_args).unwrap();
         let url = format!("/users?{}", query_);
 
-        let mut resp: crate::types::UserResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::UserResultsPage =
+            self.client.get("users_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -78,12 +113,12 @@ impl Users {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("users_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("users_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -109,12 +144,29 @@ impl Users {
      *
      * * `user_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "users_get_user",
+            skip_all,
+            err,
+            fields(
+                operation_id = "users_get_user",
+                tag = "users",
+                method = "GET",
+                path = "/users/{user_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(&self, user_name: &str) -> Result<crate::types::User> {
         let url = format!(
             "/users/{}",
             crate::progenitor_support::encode_path(user_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("users_get_user", &url, None).await
     }
 }
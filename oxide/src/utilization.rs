@@ -0,0 +1,220 @@
+//! IP address utilization for VPC subnets.
+use std::{
+    collections::BTreeSet,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use anyhow::{anyhow, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tabled::Tabled;
+
+use crate::types::{Ipv4Net, NameSortMode, NetworkInterface, VpcSubnet};
+
+/// The number of addresses at the start of every IPv4 subnet that are reserved
+/// by the control plane and never handed out to network interfaces.
+pub const NUM_INITIAL_RESERVED_IP_ADDRESSES: u32 = 5;
+
+/// Address usage for a single VPC subnet.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, Tabled)]
+pub struct SubnetUtilization {
+    /// The name of the subnet.
+    pub name: String,
+    /// The IPv4 block of the subnet.
+    pub ipv4_block: Ipv4Net,
+    /// The number of addresses assigned to network interfaces.
+    pub used: u64,
+    /// The number of addresses that can be assigned to network interfaces.
+    pub capacity: u64,
+    /// The number of addresses still available.
+    pub free: u64,
+    /// The percentage of `capacity` that is in use.
+    pub percent_used: f64,
+}
+
+impl SubnetUtilization {
+    /// Compute the utilization of `subnet` given the network interfaces within it.
+    ///
+    /// Interfaces whose address does not fall within the subnet's assignable
+    /// range are not counted.
+    pub fn new(subnet: &VpcSubnet, interfaces: &[NetworkInterface]) -> Self {
+        let block = &subnet.ipv4_block;
+        let capacity = assignable(block).count() as u64;
+        let used = used_addresses(block, interfaces).len() as u64;
+
+        SubnetUtilization {
+            name: subnet.name.to_string(),
+            ipv4_block: *block,
+            used,
+            capacity,
+            free: capacity.saturating_sub(used),
+            percent_used: if capacity == 0 {
+                100.0
+            } else {
+                used as f64 * 100.0 / capacity as f64
+            },
+        }
+    }
+
+    /// Returns whether at least `threshold` percent of the subnet is in use.
+    pub fn is_near_exhaustion(&self, threshold: f64) -> bool {
+        self.percent_used >= threshold
+    }
+}
+
+/// Address usage for every subnet in a VPC.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct VpcUtilization {
+    /// The name of the VPC.
+    pub vpc_name: String,
+    /// The utilization of each subnet in the VPC.
+    pub subnets: Vec<SubnetUtilization>,
+}
+
+impl VpcUtilization {
+    /// The subnets with at least `threshold` percent of their addresses in use.
+    pub fn near_exhaustion(&self, threshold: f64) -> Vec<&SubnetUtilization> {
+        self.subnets
+            .iter()
+            .filter(|s| s.is_near_exhaustion(threshold))
+            .collect()
+    }
+
+    /// Render the report as a table.
+    pub fn to_table(&self) -> String {
+        tabled::Table::new(&self.subnets).to_string()
+    }
+
+    /// Render the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Returns the lowest address in `subnet` that is neither reserved nor
+/// assigned to one of `interfaces`, or `None` if the subnet is exhausted.
+///
+/// The result can be used as the `ip` of a `NetworkInterfaceCreate`.
+pub fn free_ipv4(subnet: &VpcSubnet, interfaces: &[NetworkInterface]) -> Option<Ipv4Addr> {
+    let used = used_addresses(&subnet.ipv4_block, interfaces);
+
+    assignable(&subnet.ipv4_block).find(|ip| !used.contains(ip))
+}
+
+/// The addresses of `block` that may be assigned to an interface: everything
+/// but the reserved addresses at the start and the broadcast address.
+fn assignable(block: &Ipv4Net) -> impl Iterator<Item = Ipv4Addr> {
+    let first = u32::from(block.network()).saturating_add(NUM_INITIAL_RESERVED_IP_ADDRESSES);
+    let last = u32::from(block.broadcast());
+    (first..last).map(Ipv4Addr::from)
+}
+
+fn used_addresses(block: &Ipv4Net, interfaces: &[NetworkInterface]) -> BTreeSet<Ipv4Addr> {
+    let reserved_end = u32::from(block.network()).saturating_add(NUM_INITIAL_RESERVED_IP_ADDRESSES);
+    interfaces
+        .iter()
+        .filter_map(|nic| match nic.ip {
+            IpAddr::V4(ip) => Some(ip),
+            IpAddr::V6(_) => None,
+        })
+        .filter(|ip| {
+            block.contains(*ip) && u32::from(*ip) >= reserved_end && *ip != block.broadcast()
+        })
+        .collect()
+}
+
+impl crate::subnets::Subnets {
+    /// Compute the address utilization of a subnet.
+    pub async fn utilization(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        subnet_name: &str,
+        vpc_name: &str,
+    ) -> Result<SubnetUtilization> {
+        let subnet = self
+            .get(organization_name, project_name, subnet_name, vpc_name)
+            .await?;
+        let interfaces = self
+            .network_interfaces_get_all(
+                organization_name,
+                project_name,
+                NameSortMode::NameAscending,
+                subnet_name,
+                vpc_name,
+            )
+            .await?;
+
+        Ok(SubnetUtilization::new(&subnet, &interfaces))
+    }
+
+    /// Find a free address in a subnet, suitable for the `ip` of a
+    /// `NetworkInterfaceCreate`.
+    ///
+    /// Another client may claim the address before it is used, in which case
+    /// creating the interface fails and the caller should pick again.
+    pub async fn free_ip(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        subnet_name: &str,
+        vpc_name: &str,
+    ) -> Result<Ipv4Addr> {
+        let subnet = self
+            .get(organization_name, project_name, subnet_name, vpc_name)
+            .await?;
+        let interfaces = self
+            .network_interfaces_get_all(
+                organization_name,
+                project_name,
+                NameSortMode::NameAscending,
+                subnet_name,
+                vpc_name,
+            )
+            .await?;
+
+        free_ipv4(&subnet, &interfaces)
+            .ok_or_else(|| anyhow!("subnet `{}` has no free addresses", subnet.name))
+    }
+}
+
+impl crate::vpcs::Vpcs {
+    /// Compute the address utilization of every subnet in a VPC.
+    pub async fn utilization(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        vpc_name: &str,
+    ) -> Result<VpcUtilization> {
+        let subnets_api = self.client.subnets();
+        let subnets = subnets_api
+            .get_all(
+                organization_name,
+                project_name,
+                NameSortMode::NameAscending,
+                vpc_name,
+            )
+            .await?;
+
+        let mut report = VpcUtilization {
+            vpc_name: vpc_name.to_string(),
+            subnets: Vec::with_capacity(subnets.len()),
+        };
+        for subnet in subnets {
+            let interfaces = subnets_api
+                .network_interfaces_get_all(
+                    organization_name,
+                    project_name,
+                    NameSortMode::NameAscending,
+                    &subnet.name,
+                    vpc_name,
+                )
+                .await?;
+            report
+                .subnets
+                .push(SubnetUtilization::new(&subnet, &interfaces));
+        }
+
+        Ok(report)
+    }
+}
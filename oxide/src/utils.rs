@@ -657,3 +657,115 @@ impl std::fmt::Display for DisplayOptionDateTime {
         }
     }
 }
+
+/// The result of an `ensure` function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ensured<T> {
+    /// The resource, whether it was created or already existed.
+    pub resource: T,
+    /// Whether the resource was created by this request.
+    pub created: bool,
+    /// The fields of an existing resource that differ from the request.
+    pub drift: Vec<FieldDrift>,
+}
+
+impl<T> Ensured<T> {
+    pub fn created(resource: T) -> Self {
+        Ensured {
+            resource,
+            created: true,
+            drift: Vec::new(),
+        }
+    }
+
+    /// Wrap a resource that already existed, comparing it with the request
+    /// that would have created it.
+    pub fn existing<B: Serialize>(request: &B, resource: T) -> anyhow::Result<Self>
+    where
+        T: Serialize,
+    {
+        let drift = drift(request, &resource)?;
+        Ok(Ensured {
+            resource,
+            created: false,
+            drift,
+        })
+    }
+
+    /// Returns the resource, or an error if it already existed and differs
+    /// from the request.
+    pub fn without_drift(self) -> anyhow::Result<T> {
+        if !self.drift.is_empty() {
+            anyhow::bail!(
+                "existing resource does not match the request: {}",
+                self.drift
+                    .iter()
+                    .map(|d| d.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        Ok(self.resource)
+    }
+}
+
+/// A field of an existing resource that does not match the request.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct FieldDrift {
+    pub field: String,
+    pub requested: serde_json::Value,
+    pub actual: serde_json::Value,
+}
+
+impl fmt::Display for FieldDrift {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: requested {}, found {}",
+            self.field, self.requested, self.actual
+        )
+    }
+}
+
+/// Compare the fields of a request with the resource it describes.
+///
+/// Only the fields present in both are compared, and fields left unset in
+/// the request are assumed to have been filled in by the server.
+pub fn drift<B: Serialize, T: Serialize>(
+    request: &B,
+    resource: &T,
+) -> anyhow::Result<Vec<FieldDrift>> {
+    let (request, resource) = match (
+        serde_json::to_value(request)?,
+        serde_json::to_value(resource)?,
+    ) {
+        (serde_json::Value::Object(request), serde_json::Value::Object(resource)) => {
+            (request, resource)
+        }
+        _ => return Ok(Vec::new()),
+    };
+
+    Ok(request
+        .into_iter()
+        .filter(|(_, requested)| !requested.is_null())
+        .filter_map(|(field, requested)| {
+            let actual = resource.get(&field)?;
+            if *actual == requested {
+                return None;
+            }
+            Some(FieldDrift {
+                field,
+                requested,
+                actual: actual.clone(),
+            })
+        })
+        .collect())
+}
+
+/// Returns whether a request failed because the object already exists.
+pub fn is_already_exists(e: &anyhow::Error) -> bool {
+    matches!(
+        e.downcast_ref::<crate::types::Error>(),
+        Some(crate::types::Error::ObjectAlreadyExists { .. })
+    )
+}
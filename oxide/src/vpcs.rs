@@ -27,6 +27,23 @@ impl Vpcs {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_vpcs_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_vpcs_get",
+                tag = "vpcs",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_page(
         &self,
         limit: u32,
@@ -53,7 +70,8 @@ impl Vpcs {
             query_
         );
 
-        let resp: crate::types::VpcResultsPage = self.client.get(&url, None).await?;
+        let resp: crate::types::VpcResultsPage =
+            self.client.get("project_vpcs_get", &url, None).await?;
 
         // Return our response data.
         Ok(resp.items)
@@ -66,6 +84,23 @@ impl Vpcs {
      *
      * As opposed to `get`, this function returns all the pages of the request at once.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_vpcs_get",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_vpcs_get",
+                tag = "vpcs",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get_all(
         &self,
         organization_name: &str,
@@ -84,7 +119,8 @@ impl Vpcs {
             query_
         );
 
-        let mut resp: crate::types::VpcResultsPage = self.client.get(&url, None).await?;
+        let mut resp: crate::types::VpcResultsPage =
+            self.client.get("project_vpcs_get", &url, None).await?;
 
         let mut items = resp.items;
         let mut page = resp.next_page;
@@ -94,12 +130,12 @@ impl Vpcs {
             if !url.contains('?') {
                 resp = self
                     .client
-                    .get(&format!("{}?page={}", url, page), None)
+                    .get("project_vpcs_get", &format!("{}?page={}", url, page), None)
                     .await?;
             } else {
                 resp = self
                     .client
-                    .get(&format!("{}&page={}", url, page), None)
+                    .get("project_vpcs_get", &format!("{}&page={}", url, page), None)
                     .await?;
             }
 
@@ -126,6 +162,23 @@ impl Vpcs {
      * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_vpcs_post",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_vpcs_post",
+                tag = "vpcs",
+                method = "POST",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn post(
         &self,
         organization_name: &str,
@@ -139,10 +192,77 @@ impl Vpcs {
         );
 
         self.client
-            .post(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .post(
+                "project_vpcs_post",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
+    /**
+     * Create a VPC in a project.
+     *
+     * This function performs a `POST` to the `/organizations/{organization_name}/projects/{project_name}/vpcs` endpoint.
+     *
+     * As opposed to `post`, if the resource already exists this function fetches it from the `/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}` endpoint rather than failing, and reports any fields that differ from the request.
+     *
+     * **Parameters:**
+     *
+     * * `organization_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
+     */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_vpcs_ensure",
+            skip_all,
+            err,
+            fields(
+                tag = "vpcs",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs",
+            )
+        )
+    )]
+    pub async fn ensure(
+        &self,
+        organization_name: &str,
+        project_name: &str,
+        body: &crate::types::VpcCreate,
+    ) -> Result<crate::utils::Ensured<crate::types::Vpc>> {
+        match self.post(organization_name, project_name, body).await {
+            Ok(resource) => Ok(crate::utils::Ensured::created(resource)),
+            Err(e) if crate::utils::is_already_exists(&e) => {
+                let vpc_name = body.name.as_str();
+                let url = format!(
+                    "/organizations/{}/projects/{}/vpcs/{}",
+                    crate::progenitor_support::encode_path(organization_name),
+                    crate::progenitor_support::encode_path(project_name),
+                    crate::progenitor_support::encode_path(vpc_name),
+                );
+                let get = self.client.get("project_vpcs_get_vpc", &url, None);
+                #[cfg(feature = "tracing")]
+                let get = tracing::Instrument::instrument(
+                    get,
+                    tracing::info_span!(
+                        "project_vpcs_get_vpc",
+                        operation_id = "project_vpcs_get_vpc",
+                        tag = "vpcs",
+                        method = "GET",
+                        path = "/organizations/{organization_name}/projects/{project_name}/vpcs/\
+                                {vpc_name}",
+                        status = tracing::field::Empty,
+                        retry_count = tracing::field::Empty,
+                        request_id = tracing::field::Empty,
+                    ),
+                );
+                let resource = get.await?;
+                crate::utils::Ensured::existing(body, resource)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Get a VPC in a project.
      *
@@ -154,6 +274,23 @@ impl Vpcs {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_vpcs_get_vpc",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_vpcs_get_vpc",
+                tag = "vpcs",
+                method = "GET",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn get(
         &self,
         organization_name: &str,
@@ -167,7 +304,7 @@ impl Vpcs {
             crate::progenitor_support::encode_path(vpc_name),
         );
 
-        self.client.get(&url, None).await
+        self.client.get("project_vpcs_get_vpc", &url, None).await
     }
 
     /**
@@ -181,6 +318,23 @@ impl Vpcs {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_vpcs_put_vpc",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_vpcs_put_vpc",
+                tag = "vpcs",
+                method = "PUT",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn put(
         &self,
         organization_name: &str,
@@ -196,7 +350,11 @@ impl Vpcs {
         );
 
         self.client
-            .put(&url, Some(reqwest::Body::from(serde_json::to_vec(body)?)))
+            .put(
+                "project_vpcs_put_vpc",
+                &url,
+                Some(bytes::Bytes::from(serde_json::to_vec(body)?)),
+            )
             .await
     }
 
@@ -211,6 +369,23 @@ impl Vpcs {
      * * `project_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      * * `vpc_name: &str` -- Names must begin with a lower case ASCII letter, be composed exclusively of lowercase ASCII, uppercase ASCII, numbers, and '-', and may not end with a '-'.
      */
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "project_vpcs_delete_vpc",
+            skip_all,
+            err,
+            fields(
+                operation_id = "project_vpcs_delete_vpc",
+                tag = "vpcs",
+                method = "DELETE",
+                path = "/organizations/{organization_name}/projects/{project_name}/vpcs/{vpc_name}",
+                status = tracing::field::Empty,
+                retry_count = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(
         &self,
         organization_name: &str,
@@ -224,6 +399,8 @@ impl Vpcs {
             crate::progenitor_support::encode_path(vpc_name),
         );
 
-        self.client.delete(&url, None).await
+        self.client
+            .delete("project_vpcs_delete_vpc", &url, None)
+            .await
     }
 }
@@ -0,0 +1,292 @@
+//! Watch a collection for changes by polling it.
+//!
+//! The API has no event stream, so [`watch`] lists a collection on an
+//! interval and compares each listing with the last, yielding an [`Event`]
+//! for every resource that was created, updated or deleted in between.
+use std::{
+    collections::{BTreeMap, VecDeque},
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    Disk, IdSortMode, Image, Instance, NameSortMode, Organization, Project, Saga, Snapshot, Vpc,
+    VpcRouter, VpcSubnet,
+};
+
+/// A resource that can be watched.
+pub trait Watched: Clone + PartialEq {
+    fn id(&self) -> &str;
+
+    /// When the resource was last modified. Resources that do not record this
+    /// are compared field by field instead.
+    fn time_modified(&self) -> Option<DateTime<Utc>>;
+}
+
+macro_rules! impl_watched {
+    ($($t:ty),*) => {
+        $(
+            impl Watched for $t {
+                fn id(&self) -> &str {
+                    &self.id
+                }
+
+                fn time_modified(&self) -> Option<DateTime<Utc>> {
+                    self.time_modified.0
+                }
+            }
+        )*
+    };
+}
+
+impl_watched!(
+    Disk,
+    Image,
+    Instance,
+    Organization,
+    Project,
+    Snapshot,
+    Vpc,
+    VpcRouter,
+    VpcSubnet
+);
+
+impl Watched for Saga {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn time_modified(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+}
+
+/// A change to a resource in a watched collection.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "event")]
+pub enum Event<T> {
+    Created(T),
+    /// The resource changed, or, on a resync, is being delivered again
+    /// unchanged.
+    Updated {
+        old: T,
+        new: T,
+    },
+    Deleted(T),
+}
+
+impl<T: Watched> Event<T> {
+    /// The resource as of the event.
+    pub fn resource(&self) -> &T {
+        match self {
+            Event::Created(resource) | Event::Deleted(resource) => resource,
+            Event::Updated { new, .. } => new,
+        }
+    }
+}
+
+/// Options for watching a collection.
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// How long to wait between listings.
+    pub interval: Duration,
+    /// Up to this much is added to each interval at random, so that many
+    /// watchers started together do not poll in lockstep.
+    pub jitter: Duration,
+    /// How often every resource is delivered again as an `Updated` event,
+    /// even if it has not changed, so that consumers can correct any drift.
+    /// Never, if unset.
+    pub resync: Option<Duration>,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        WatchOptions {
+            interval: Duration::from_secs(10),
+            jitter: Duration::from_secs(1),
+            resync: Some(Duration::from_secs(300)),
+        }
+    }
+}
+
+impl WatchOptions {
+    fn next_delay(&self) -> Duration {
+        let jitter_nanos = self.jitter.as_nanos();
+        if jitter_nanos == 0 {
+            return self.interval;
+        }
+        // A random v4 UUID is as good a source of randomness as any here.
+        let random = uuid::Uuid::new_v4().as_u128() % jitter_nanos;
+        self.interval + Duration::from_nanos(random as u64)
+    }
+}
+
+/// Tracks the last known state of a collection.
+#[derive(Debug, Clone)]
+pub struct Watcher<T> {
+    known: BTreeMap<String, T>,
+}
+
+impl<T> Default for Watcher<T> {
+    fn default() -> Self {
+        Watcher {
+            known: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Watched> Watcher<T> {
+    /// Compare a new listing of the collection with the last, returning the
+    /// events in between in order of id. With `resync` set, unchanged
+    /// resources are returned as updates as well.
+    pub fn observe(&mut self, resources: Vec<T>, resync: bool) -> Vec<Event<T>> {
+        let current: BTreeMap<String, T> = resources
+            .into_iter()
+            .map(|r| (r.id().to_string(), r))
+            .collect();
+        let mut events = Vec::new();
+
+        for (id, new) in &current {
+            match self.known.remove(id) {
+                None => events.push(Event::Created(new.clone())),
+                Some(old) => {
+                    let changed = match (old.time_modified(), new.time_modified()) {
+                        (Some(before), Some(after)) => before != after,
+                        _ => old != *new,
+                    };
+                    if changed || resync {
+                        events.push(Event::Updated {
+                            old,
+                            new: new.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        let deleted = std::mem::replace(&mut self.known, current);
+        events.extend(deleted.into_values().map(Event::Deleted));
+
+        events
+    }
+
+    /// The resources as of the last listing.
+    pub fn resources(&self) -> impl Iterator<Item = &T> {
+        self.known.values()
+    }
+}
+
+struct WatchState<T, F> {
+    list: F,
+    options: WatchOptions,
+    watcher: Watcher<T>,
+    pending: VecDeque<Result<Event<T>>>,
+    started: bool,
+    last_resync: Instant,
+}
+
+/// Watch a collection by calling `list` on an interval.
+///
+/// Every resource in the first listing is yielded as `Created`. A failed
+/// listing yields an error and is retried after the next interval.
+pub fn watch<T, F, Fut>(list: F, options: WatchOptions) -> impl Stream<Item = Result<Event<T>>>
+where
+    T: Watched,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Vec<T>>>,
+{
+    let state = WatchState {
+        list,
+        options,
+        watcher: Watcher::default(),
+        pending: VecDeque::new(),
+        started: false,
+        last_resync: Instant::now(),
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.pending.pop_front() {
+                return Some((event, state));
+            }
+
+            if state.started {
+                tokio::time::sleep(state.options.next_delay()).await;
+            }
+            state.started = true;
+
+            match (state.list)().await {
+                Ok(resources) => {
+                    let resync = match state.options.resync {
+                        Some(resync) if state.last_resync.elapsed() >= resync => {
+                            state.last_resync = Instant::now();
+                            true
+                        }
+                        _ => false,
+                    };
+                    state
+                        .pending
+                        .extend(state.watcher.observe(resources, resync).into_iter().map(Ok));
+                }
+                Err(e) => state.pending.push_back(Err(e)),
+            }
+        }
+    })
+}
+
+impl crate::instances::Instances {
+    /// Watch the instances in a project for changes.
+    pub fn watch<'a>(
+        &'a self,
+        organization_name: &'a str,
+        project_name: &'a str,
+        options: WatchOptions,
+    ) -> impl Stream<Item = Result<Event<Instance>>> + 'a {
+        watch(
+            move || self.get_all(organization_name, project_name, NameSortMode::NameAscending),
+            options,
+        )
+    }
+}
+
+impl crate::disks::Disks {
+    /// Watch the disks in a project for changes.
+    pub fn watch<'a>(
+        &'a self,
+        organization_name: &'a str,
+        project_name: &'a str,
+        options: WatchOptions,
+    ) -> impl Stream<Item = Result<Event<Disk>>> + 'a {
+        watch(
+            move || self.get_all(organization_name, project_name, NameSortMode::NameAscending),
+            options,
+        )
+    }
+}
+
+impl crate::vpcs::Vpcs {
+    /// Watch the VPCs in a project for changes.
+    pub fn watch<'a>(
+        &'a self,
+        organization_name: &'a str,
+        project_name: &'a str,
+        options: WatchOptions,
+    ) -> impl Stream<Item = Result<Event<Vpc>>> + 'a {
+        watch(
+            move || self.get_all(organization_name, project_name, NameSortMode::NameAscending),
+            options,
+        )
+    }
+}
+
+impl crate::sagas::Sagas {
+    /// Watch sagas for changes to their state.
+    pub fn watch(&self, options: WatchOptions) -> impl Stream<Item = Result<Event<Saga>>> + '_ {
+        watch(move || self.get_all(IdSortMode::IdAscending), options)
+    }
+}